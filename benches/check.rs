@@ -0,0 +1,197 @@
+//! Per-check cost of `Client::check_permission` against a reused
+//! `PermissionChecker`, on an in-process server that allows everything.
+//!
+//! Besides the timings, prints the heap allocations each path makes per
+//! check on the client thread:
+//!
+//! ```text
+//! cargo bench --bench check
+//! ```
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::convert::Infallible;
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use prescience::{Client, ObjectReference, SubjectReference};
+use tokio::runtime::Runtime;
+use tonic::body::BoxBody;
+use tonic::codec::ProstCodec;
+use tonic::server::{Grpc, NamedService, UnaryService};
+
+/// Counts allocations made on threads that have opted in.
+struct CountingAlloc;
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if COUNTING.with(Cell::get) {
+            ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// The fields of `CheckPermissionRequest` the server needs: none.
+#[derive(Clone, PartialEq, prost::Message)]
+struct AnyRequest {}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct ZedToken {
+    #[prost(string, tag = "1")]
+    token: String,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+struct CheckPermissionResponse {
+    #[prost(message, optional, tag = "1")]
+    checked_at: Option<ZedToken>,
+    #[prost(int32, tag = "2")]
+    permissionship: i32,
+}
+
+/// A PermissionsService whose every check is `PERMISSIONSHIP_HAS_PERMISSION`.
+#[derive(Clone)]
+struct AllowAll;
+
+impl NamedService for AllowAll {
+    const NAME: &'static str = "authzed.api.v1.PermissionsService";
+}
+
+impl UnaryService<AnyRequest> for AllowAll {
+    type Response = CheckPermissionResponse;
+    type Future = Ready<Result<tonic::Response<Self::Response>, tonic::Status>>;
+
+    fn call(&mut self, _: tonic::Request<AnyRequest>) -> Self::Future {
+        ready(Ok(tonic::Response::new(CheckPermissionResponse {
+            checked_at: Some(ZedToken {
+                token: "GhUKEzE3MDAwMDAwMDAwMDAwMDAwMDA=".into(),
+            }),
+            permissionship: 2,
+        })))
+    }
+}
+
+impl tower::Service<http::Request<BoxBody>> for AllowAll {
+    type Response = http::Response<BoxBody>;
+    type Error = Infallible;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Infallible>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<BoxBody>) -> Self::Future {
+        let service = self.clone();
+        Box::pin(async move {
+            let mut grpc = Grpc::new(ProstCodec::default());
+            Ok(grpc.unary(service, request).await)
+        })
+    }
+}
+
+/// Starts the server on its own runtime, so that its allocations are not
+/// counted, and returns its address.
+fn serve() -> std::net::SocketAddr {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    listener.set_nonblocking(true).unwrap();
+    std::thread::spawn(move || {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        runtime.block_on(async move {
+            let listener = tokio::net::TcpListener::from_std(listener).unwrap();
+            tonic::transport::Server::builder()
+                .add_service(AllowAll)
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener))
+                .await
+                .unwrap();
+        });
+    });
+    addr
+}
+
+/// Returns the allocations per call of `f`, averaged over `calls` calls.
+fn allocations_per_call(calls: u64, mut f: impl FnMut()) -> f64 {
+    COUNTING.with(|c| c.set(true));
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    for _ in 0..calls {
+        f();
+    }
+    let after = ALLOCATIONS.load(Ordering::Relaxed);
+    COUNTING.with(|c| c.set(false));
+    (after - before) as f64 / calls as f64
+}
+
+fn bench_check(c: &mut Criterion) {
+    let addr = serve();
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .unwrap();
+    let client = runtime
+        .block_on(Client::new(format!("http://{}", addr), "token"))
+        .unwrap();
+    let doc = ObjectReference::new("document", "doc-1").unwrap();
+    let alice = SubjectReference::new(
+        ObjectReference::new("user", "alice").unwrap(),
+        None::<String>,
+    )
+    .unwrap();
+    let mut checker = client.permission_checker();
+
+    let check_permission = |runtime: &Runtime| {
+        runtime
+            .block_on(async { client.check_permission(&doc, "view", &alice).await })
+            .unwrap()
+    };
+
+    // Warm up the connection before counting.
+    check_permission(&runtime);
+    let per_check = allocations_per_call(1_000, || {
+        check_permission(&runtime);
+    });
+    let per_checker = allocations_per_call(1_000, || {
+        runtime
+            .block_on(checker.check(&doc, "view", &alice))
+            .unwrap();
+    });
+    println!(
+        "allocations per check: check_permission {:.1}, permission_checker {:.1}",
+        per_check, per_checker
+    );
+
+    let mut group = c.benchmark_group("check");
+    group.bench_function("check_permission", |b| {
+        b.iter(|| check_permission(&runtime))
+    });
+    group.bench_function("permission_checker", |b| {
+        b.iter(|| {
+            runtime
+                .block_on(checker.check(&doc, "view", &alice))
+                .unwrap()
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_check);
+criterion_main!(benches);
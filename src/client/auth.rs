@@ -0,0 +1,185 @@
+//! Token rotation across a primary and fallback tokens
+//! (see [`ClientBuilder::fallback_tokens`]).
+//!
+//! [`ClientBuilder::fallback_tokens`]: super::ClientBuilder::fallback_tokens
+
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use tonic::metadata::{Ascii, MetadataValue};
+
+use super::lifecycle::StreamMonitor;
+use super::Client;
+
+/// The tokens a client may authenticate with, and which one is in use.
+///
+/// Shared between the interceptor, which reads the active token for every
+/// request, and the client, which advances it on `UNAUTHENTICATED`.
+#[derive(Debug)]
+pub(crate) struct TokenRotation {
+    tokens: Vec<MetadataValue<Ascii>>,
+    active: AtomicUsize,
+}
+
+impl TokenRotation {
+    /// Creates a rotation starting at the first of `tokens`.
+    pub(crate) fn new(tokens: Vec<MetadataValue<Ascii>>) -> Self {
+        debug_assert!(tokens.len() > 1, "rotation needs a fallback token");
+        Self {
+            tokens,
+            active: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the index of the token currently in use.
+    pub(crate) fn active(&self) -> usize {
+        self.active.load(Ordering::Acquire)
+    }
+
+    /// Returns the header value of the token currently in use.
+    pub(crate) fn active_token(&self) -> &MetadataValue<Ascii> {
+        &self.tokens[self.active()]
+    }
+
+    /// Moves to the token after `failed`, unless another request has
+    /// already moved past it.
+    fn advance_from(&self, failed: usize) {
+        let next = (failed + 1) % self.tokens.len();
+        let _ = self
+            .active
+            .compare_exchange(failed, next, Ordering::AcqRel, Ordering::Acquire);
+    }
+}
+
+impl Client {
+    /// Runs a unary (or server-streaming) RPC, retrying it with the next
+    /// fallback token each time it fails with `UNAUTHENTICATED`.
+    ///
+    /// Without fallback tokens this calls `call` exactly once. With them,
+    /// each token is tried at most once, starting from the one that last
+    /// worked; the token that succeeds stays active for later requests.
+    ///
+    /// `method` (e.g. `PermissionsService.CheckPermission`) names the RPC in
+    /// the slow-call warning; see [`Client::warn_if_slow`].
+    pub(crate) async fn authenticated<S, R, T, F, Fut>(
+        &self,
+        method: &'static str,
+        service: &S,
+        request: R,
+        call: F,
+    ) -> Result<T, tonic::Status>
+    where
+        S: Clone,
+        R: Clone,
+        F: FnMut(S, R) -> Fut,
+        Fut: Future<Output = Result<T, tonic::Status>>,
+    {
+        let started = Instant::now();
+        let result = self.with_fallback_tokens(service, request, call).await;
+        self.warn_if_slow(method, started);
+        self.record_outcome(&result);
+        result
+    }
+
+    /// Logs a warning if an RPC started at `started` has taken longer than
+    /// the configured [`ClientBuilder::slow_rpc_threshold`].
+    ///
+    /// For streaming RPCs this measures the time until the response headers
+    /// arrive, not the time to drain the stream.
+    ///
+    /// [`ClientBuilder::slow_rpc_threshold`]: super::ClientBuilder::slow_rpc_threshold
+    pub(crate) fn warn_if_slow(&self, method: &'static str, started: Instant) {
+        let Some(threshold) = self.slow_rpc_threshold else {
+            return;
+        };
+        let elapsed = started.elapsed();
+        if elapsed > threshold {
+            tracing::warn!(
+                method,
+                elapsed_ms = elapsed.as_millis() as u64,
+                threshold_ms = threshold.as_millis() as u64,
+                "slow SpiceDB call"
+            );
+        }
+    }
+
+    /// Feeds an RPC's outcome to the connection monitor, which reports
+    /// lost and restored connections; see [`ClientBuilder::on_connection_event`].
+    ///
+    /// [`ClientBuilder::on_connection_event`]: super::ClientBuilder::on_connection_event
+    pub(crate) fn record_outcome<T>(&self, result: &Result<T, tonic::Status>) {
+        self.connection.record(result, self.endpoint.as_deref());
+    }
+
+    /// Returns a handle that feeds errors yielded by a streaming RPC's
+    /// response stream to the connection monitor, as
+    /// [`Client::record_outcome`] does for the call itself.
+    pub(crate) fn stream_monitor(&self) -> StreamMonitor {
+        StreamMonitor::new(self.connection.clone(), self.endpoint.clone())
+    }
+
+    async fn with_fallback_tokens<S, R, T, F, Fut>(
+        &self,
+        service: &S,
+        request: R,
+        mut call: F,
+    ) -> Result<T, tonic::Status>
+    where
+        S: Clone,
+        R: Clone,
+        F: FnMut(S, R) -> Fut,
+        Fut: Future<Output = Result<T, tonic::Status>>,
+    {
+        let Some(rotation) = &self.token_rotation else {
+            return call(service.clone(), request).await;
+        };
+
+        let mut remaining = rotation.tokens.len() - 1;
+        loop {
+            let index = rotation.active();
+            match call(service.clone(), request.clone()).await {
+                Err(status) if status.code() == tonic::Code::Unauthenticated && remaining > 0 => {
+                    tracing::warn!(
+                        token_index = index,
+                        "token rejected; trying next fallback token"
+                    );
+                    rotation.advance_from(index);
+                    remaining -= 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rotation(n: usize) -> TokenRotation {
+        TokenRotation::new(
+            (0..n)
+                .map(|i| format!("Bearer t{}", i).parse().unwrap())
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn advance_wraps_around() {
+        let r = rotation(2);
+        r.advance_from(0);
+        assert_eq!(r.active_token().to_str().unwrap(), "Bearer t1");
+        r.advance_from(1);
+        assert_eq!(r.active(), 0);
+    }
+
+    #[test]
+    fn stale_advance_is_ignored() {
+        let r = rotation(3);
+        r.advance_from(0);
+        // A second request that also failed on token 0 must not skip token 1.
+        r.advance_from(0);
+        assert_eq!(r.active(), 1);
+    }
+}
@@ -1,17 +1,78 @@
 //! Client builder for configuring connections.
 
+use std::net::IpAddr;
+use std::sync::Arc;
 use std::time::Duration;
 
-use tonic::metadata::MetadataValue;
+use tonic::service::Interceptor;
 use tonic::transport::Endpoint;
 
 use crate::error::Error;
+use crate::proto;
+use crate::types::Consistency;
 
-use super::{BearerTokenInterceptor, Client};
-use crate::proto::permissions_service_client::PermissionsServiceClient;
-use crate::proto::schema_service_client::SchemaServiceClient;
-#[cfg(feature = "watch")]
-use crate::proto::watch_service_client::WatchServiceClient;
+use super::lifecycle::{ConnectionCallback, ConnectionEvent, ConnectionMonitor};
+use super::prefix::TypePrefix;
+use super::{redact_userinfo, BearerTokenInterceptor, Client, ClientOptions};
+
+/// How the token is presented in the `authorization` header.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AuthScheme {
+    /// Send `authorization: Bearer <token>`. This is what SpiceDB expects.
+    #[default]
+    Bearer,
+    /// Send the token verbatim as `authorization: <token>`, for gateways
+    /// that terminate auth in front of SpiceDB and expect a raw credential.
+    None,
+}
+
+/// A class of read RPCs, for [`ClientBuilder::default_consistency_for`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RpcKind {
+    /// Permission checks: [`Client::check_permission`] and the checks built
+    /// on it or on bulk checks.
+    Check,
+    /// [`Client::lookup_resources`] and [`Client::lookup_subjects`].
+    Lookup,
+    /// [`Client::read_relationships`], the reads built on it, and bulk
+    /// export.
+    Read,
+    /// [`Client::expand_permission_tree`] and the APIs built on it.
+    Expand,
+}
+
+/// The consistency each [`RpcKind`] starts with, unless a request sets its
+/// own.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct DefaultConsistency {
+    check: Option<proto::Consistency>,
+    lookup: Option<proto::Consistency>,
+    read: Option<proto::Consistency>,
+    expand: Option<proto::Consistency>,
+}
+
+impl DefaultConsistency {
+    fn slot(&mut self, kind: RpcKind) -> &mut Option<proto::Consistency> {
+        match kind {
+            RpcKind::Check => &mut self.check,
+            RpcKind::Lookup => &mut self.lookup,
+            RpcKind::Read => &mut self.read,
+            RpcKind::Expand => &mut self.expand,
+        }
+    }
+
+    /// The default for `kind`, or `None` for the server default.
+    pub(crate) fn get(&self, kind: RpcKind) -> Option<proto::Consistency> {
+        match kind {
+            RpcKind::Check => &self.check,
+            RpcKind::Lookup => &self.lookup,
+            RpcKind::Read => &self.read,
+            RpcKind::Expand => &self.expand,
+        }
+        .clone()
+    }
+}
 
 /// A builder for configuring and creating a [`Client`].
 ///
@@ -33,9 +94,24 @@ use crate::proto::watch_service_client::WatchServiceClient;
 pub struct ClientBuilder {
     endpoint: String,
     token: String,
+    fallback_tokens: Vec<String>,
     insecure: bool,
+    allow_private_network: bool,
+    auth_scheme: AuthScheme,
+    auth_header_name: Option<String>,
+    authority: Option<String>,
+    interceptor: Option<Box<dyn super::CustomInterceptor>>,
     connect_timeout: Option<Duration>,
     default_timeout: Option<Duration>,
+    slow_rpc_threshold: Option<Duration>,
+    default_consistency: DefaultConsistency,
+    on_connection_event: Option<ConnectionCallback>,
+    initial_stream_window_size: Option<u32>,
+    initial_connection_window_size: Option<u32>,
+    tcp_nodelay: Option<bool>,
+    type_prefix: TypePrefix,
+    #[cfg(feature = "experimental")]
+    use_experimental_service: bool,
 }
 
 impl ClientBuilder {
@@ -43,12 +119,48 @@ impl ClientBuilder {
         Self {
             endpoint: endpoint.into(),
             token: token.into(),
+            fallback_tokens: Vec::new(),
             insecure: false,
+            allow_private_network: false,
+            auth_scheme: AuthScheme::Bearer,
+            auth_header_name: None,
+            authority: None,
+            interceptor: None,
             connect_timeout: None,
             default_timeout: None,
+            slow_rpc_threshold: None,
+            default_consistency: DefaultConsistency::default(),
+            on_connection_event: None,
+            initial_stream_window_size: None,
+            initial_connection_window_size: None,
+            tcp_nodelay: None,
+            type_prefix: TypePrefix::default(),
+            #[cfg(feature = "experimental")]
+            use_experimental_service: false,
         }
     }
 
+    /// Sets tokens to fall back to when the server rejects the primary token,
+    /// for zero-downtime preshared key rotation.
+    ///
+    /// The primary token is always tried first. When an RPC fails with
+    /// `UNAUTHENTICATED`, it is re-sent with each fallback in order, and the
+    /// first token that is accepted is used for subsequent requests until it,
+    /// too, is rejected. Client-streaming RPCs
+    /// (bulk import) cannot be replayed and are sent
+    /// once with the active token.
+    ///
+    /// # Security
+    ///
+    /// Every fallback token is held in memory for the life of the client and
+    /// may be sent to the server, so a compromised endpoint learns all of
+    /// them. Keep the list to the keys in the current rotation window and
+    /// drop retired keys from configuration as soon as the rollover is done.
+    pub fn fallback_tokens(mut self, tokens: Vec<String>) -> Self {
+        self.fallback_tokens = tokens;
+        self
+    }
+
     /// Allow insecure (plaintext) connections to non-loopback addresses.
     ///
     /// By default, `http://` to a non-loopback address returns an error.
@@ -58,7 +170,95 @@ impl ClientBuilder {
         self
     }
 
+    /// Allow plaintext `http://` connections to private-network IP addresses
+    /// without `.insecure(true)`.
+    ///
+    /// When enabled, an endpoint whose host is a literal IPv4 address in an
+    /// RFC 1918 range (`10.0.0.0/8`, `172.16.0.0/12`, `192.168.0.0/16`) or an
+    /// IPv6 unique local address (`fc00::/7`) is accepted like a loopback
+    /// address. Hostnames are not resolved, so `http://spicedb.internal` still
+    /// requires `.insecure(true)`. Defaults to `false`.
+    pub fn allow_private_network(mut self, allow: bool) -> Self {
+        self.allow_private_network = allow;
+        self
+    }
+
+    /// Sets how the token is presented in the `authorization` header.
+    ///
+    /// Defaults to [`AuthScheme::Bearer`].
+    pub fn auth_scheme(mut self, scheme: AuthScheme) -> Self {
+        self.auth_scheme = scheme;
+        self
+    }
+
+    /// Sends the token under a custom metadata key instead of `authorization`,
+    /// for gateways that route on a header such as `x-spicedb-token`.
+    ///
+    /// The name must be a valid lowercase ASCII header name and must not end
+    /// in `-bin` (gRPC reserves that suffix for binary values); otherwise
+    /// [`build`](Self::build) returns `Err(InvalidArgument)`. Combine with
+    /// [`AuthScheme::None`] if the gateway expects the raw token.
+    pub fn auth_header_name(mut self, name: impl Into<String>) -> Self {
+        self.auth_header_name = Some(name.into());
+        self
+    }
+
+    /// Presents `authority` (e.g. `spicedb.internal` or `spicedb.internal:50051`)
+    /// as the HTTP/2 `:authority` of every request instead of the endpoint's
+    /// host, for ingresses and meshes that route by authority.
+    ///
+    /// The connection is still made to the endpoint passed to
+    /// [`Client::builder`], with the endpoint's scheme. The authority must
+    /// parse as a URI authority (`host[:port]`); otherwise
+    /// [`build`](Self::build) returns `Err(InvalidArgument)`.
+    pub fn authority(mut self, authority: impl Into<String>) -> Self {
+        self.authority = Some(authority.into());
+        self
+    }
+
+    /// Runs `interceptor` on every request, alongside the auth token.
+    ///
+    /// Use it to attach request IDs or tracing headers. The custom
+    /// interceptor runs first and the token is attached after it, so it
+    /// cannot overwrite or remove the auth header; returning `Err` from it
+    /// fails the request with that status. Calling this again replaces the
+    /// previous interceptor.
+    ///
+    /// ```rust,no_run
+    /// # use prescience::Client;
+    /// # async fn example() -> Result<(), prescience::Error> {
+    /// let client = Client::builder("https://spicedb.internal:50051", "my-token")
+    ///     .interceptor(|mut request: tonic::Request<()>| {
+    ///         request
+    ///             .metadata_mut()
+    ///             .insert("x-request-id", "req-1".parse().unwrap());
+    ///         Ok(request)
+    ///     })
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn interceptor<I>(mut self, interceptor: I) -> Self
+    where
+        I: Interceptor + Clone + Send + Sync + 'static,
+    {
+        self.interceptor = Some(Box::new(interceptor));
+        self
+    }
+
     /// Sets the connection timeout.
+    ///
+    /// This also bounds how long requests wait while the channel reconnects.
+    /// The channel queues requests behind a reconnect instead of failing
+    /// them: a refused connection (e.g. SpiceDB restarting) fails queued
+    /// requests with `UNAVAILABLE` right away, but a connection attempt that
+    /// hangs holds them until this timeout expires. The channel does not
+    /// expose whether a connection is ready, so there is no fail-fast mode.
+    /// On a latency-sensitive path, set this together with
+    /// [`default_timeout`](Self::default_timeout) to bound every call, and
+    /// treat the resulting `UNAVAILABLE` or `DEADLINE_EXCEEDED` with your
+    /// own retry policy (see [`Error::is_retryable`]).
     pub fn connect_timeout(mut self, timeout: Duration) -> Self {
         self.connect_timeout = Some(timeout);
         self
@@ -70,8 +270,150 @@ impl ClientBuilder {
         self
     }
 
-    /// Builds and connects the client.
-    pub async fn build(self) -> Result<Client, Error> {
+    /// Sets the consistency that requests of `kind` use unless they call
+    /// `.consistency()` themselves, e.g. fully consistent checks for access
+    /// decisions with cheaper, minimize-latency lookups for listings.
+    ///
+    /// A request's own `.consistency()` always wins, including
+    /// `.consistency(None)`, which sends no preference. Without this, every
+    /// kind defaults to the server's default (`MinimizeLatency`).
+    ///
+    /// ```rust,no_run
+    /// use prescience::client::RpcKind;
+    /// use prescience::{Client, Consistency};
+    ///
+    /// # async fn example() -> Result<(), prescience::Error> {
+    /// let client = Client::builder("http://localhost:50051", "my-token")
+    ///     .default_consistency_for(RpcKind::Check, Consistency::FullyConsistent)
+    ///     .default_consistency_for(RpcKind::Lookup, Consistency::MinimizeLatency)
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn default_consistency_for(mut self, kind: RpcKind, consistency: Consistency) -> Self {
+        *self.default_consistency.slot(kind) = Some((&consistency).into());
+        self
+    }
+
+    /// Logs a `tracing` warning for every RPC that takes longer than
+    /// `threshold`, with the method name and the elapsed time.
+    ///
+    /// For streaming RPCs (reads, lookups, exports, watch) the time is
+    /// measured until the response headers arrive. Disabled by default.
+    pub fn slow_rpc_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_rpc_threshold = Some(threshold);
+        self
+    }
+
+    /// Calls `callback` when the client connects, loses its connection, or
+    /// gets it back, e.g. to drive a dashboard or health metric.
+    ///
+    /// The channel reconnects by itself and does not expose its state, so
+    /// these events are derived from RPC outcomes: [`ConnectionEvent::Connected`]
+    /// fires once when [`build`](Self::build) connects, [`ConnectionEvent::Disconnected`]
+    /// when a call, or a watch, read, or lookup stream it returned, fails
+    /// with `UNAVAILABLE`, and [`ConnectionEvent::Reconnected`]
+    /// when the next call reaches the server. A drop is not noticed until a
+    /// call is made. The same transitions are logged with `tracing` whether
+    /// or not a callback is set.
+    ///
+    /// The callback runs inline on the task that made the RPC, so keep it
+    /// short and non-blocking.
+    ///
+    /// ```rust,no_run
+    /// use prescience::{Client, ConnectionEvent};
+    ///
+    /// # async fn example() -> Result<(), prescience::Error> {
+    /// let client = Client::builder("http://localhost:50051", "my-token")
+    ///     .on_connection_event(|event| {
+    ///         if event == ConnectionEvent::Disconnected {
+    ///             eprintln!("SpiceDB unreachable");
+    ///         }
+    ///     })
+    ///     .build()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_connection_event<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(ConnectionEvent) + Send + Sync + 'static,
+    {
+        self.on_connection_event = Some(Arc::new(callback));
+        self
+    }
+
+    /// Sets the HTTP/2 flow-control window of each stream, in bytes.
+    ///
+    /// A stream can have at most one window of unacknowledged data in
+    /// flight, so its throughput is capped at roughly window / round-trip
+    /// time. On a LAN the default is plenty. Over a WAN link, size it to the
+    /// bandwidth-delay product of the link: for example, 100 Mbit/s at
+    /// 80 ms RTT needs about 1 MiB, so 1–8 MiB is a reasonable range for
+    /// cross-region bulk exports. Pair it with
+    /// [`initial_connection_window_size`](Self::initial_connection_window_size).
+    /// Defaults to tonic's default.
+    pub fn initial_stream_window_size(mut self, size: u32) -> Self {
+        self.initial_stream_window_size = Some(size);
+        self
+    }
+
+    /// Sets the HTTP/2 flow-control window of the whole connection, in
+    /// bytes.
+    ///
+    /// All streams on the connection share this window, so keep it at least
+    /// as large as [`initial_stream_window_size`](Self::initial_stream_window_size),
+    /// and a few times larger if several bulk streams run concurrently.
+    /// Defaults to tonic's default.
+    pub fn initial_connection_window_size(mut self, size: u32) -> Self {
+        self.initial_connection_window_size = Some(size);
+        self
+    }
+
+    /// Sets `TCP_NODELAY` on the connection, disabling Nagle's algorithm.
+    ///
+    /// tonic enables it by default, which suits gRPC's small, latency-bound
+    /// requests on both LAN and WAN links; disabling it rarely helps.
+    /// Defaults to tonic's default.
+    pub fn tcp_nodelay(mut self, enabled: bool) -> Self {
+        self.tcp_nodelay = Some(enabled);
+        self
+    }
+
+    /// Sets a prefix transparently applied to every object and subject type.
+    ///
+    /// With `.type_prefix("billing")`, application code uses `invoice` while
+    /// SpiceDB sees `billing/invoice`. The prefix (joined with a `/`) is
+    /// prepended in requests to check, write, delete, lookup, read, expand,
+    /// watch, and the bulk APIs, and stripped from the types in their
+    /// responses, so a [`ReadRelationshipResult`](crate::ReadRelationshipResult)
+    /// or `WatchEvent` comes back with short names.
+    /// Types returned without the prefix are passed through unchanged.
+    ///
+    /// Schema reads and writes are not rewritten: the schema text must use
+    /// the full, prefixed definition names.
+    pub fn type_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.type_prefix = TypePrefix::new(&prefix.into());
+        self
+    }
+
+    /// Routes [`Client::bulk_check_permissions`] to the legacy
+    /// `ExperimentalService.BulkCheckPermission` RPC instead of
+    /// `PermissionsService.CheckBulkPermissions`.
+    ///
+    /// SpiceDB promoted bulk checks to `PermissionsService` in v1.33.0; older
+    /// servers answer the promoted RPC with `UNIMPLEMENTED`. Enable this when
+    /// talking to a server older than v1.33.0. Defaults to `false`.
+    #[cfg(feature = "experimental")]
+    pub fn use_experimental_service(mut self, enabled: bool) -> Self {
+        self.use_experimental_service = enabled;
+        self
+    }
+
+    /// Checks that a plaintext `http://` endpoint is loopback, an opted-in
+    /// private network address, or explicitly allowed with `insecure`.
+    fn check_plaintext_endpoint(&self) -> Result<(), Error> {
         // Validate insecure connections (FR-1.3)
         // Parse the URI to extract the host for proper loopback validation.
         // Substring matching (e.g. contains("localhost")) is unsafe because
@@ -81,21 +423,32 @@ impl ClientBuilder {
                 Error::InvalidArgument(format!("invalid endpoint URI: {}", e))
             })?;
             let host = uri.host().unwrap_or("");
-            let is_loopback =
-                host == "localhost" || host == "127.0.0.1" || host == "::1" || host == "[::1]";
+            let allowed =
+                is_loopback(host) || (self.allow_private_network && is_private_network(host));
 
-            if !is_loopback {
+            if !allowed {
                 return Err(Error::InvalidArgument(format!(
                     "insecure connection to non-loopback address '{}' requires \
                          .insecure(true) on the builder. Use https:// for production.",
-                    self.endpoint
+                    redact_userinfo(&self.endpoint)
                 )));
             }
         }
+        Ok(())
+    }
+
+    /// Builds and connects the client.
+    pub async fn build(self) -> Result<Client, Error> {
+        self.check_plaintext_endpoint()?;
 
         let mut endpoint = Endpoint::from_shared(self.endpoint.clone())
             .map_err(|e| Error::InvalidArgument(format!("invalid endpoint: {}", e)))?;
 
+        if let Some(authority) = &self.authority {
+            let origin = origin_uri(endpoint.uri(), authority)?;
+            endpoint = endpoint.origin(origin);
+        }
+
         if let Some(timeout) = self.connect_timeout {
             endpoint = endpoint.connect_timeout(timeout);
         }
@@ -104,26 +457,165 @@ impl ClientBuilder {
             endpoint = endpoint.timeout(timeout);
         }
 
+        if let Some(size) = self.initial_stream_window_size {
+            endpoint = endpoint.initial_stream_window_size(size);
+        }
+
+        if let Some(size) = self.initial_connection_window_size {
+            endpoint = endpoint.initial_connection_window_size(size);
+        }
+
+        if let Some(enabled) = self.tcp_nodelay {
+            endpoint = endpoint.tcp_nodelay(enabled);
+        }
+
+        // Validate credentials before connecting so a bad token or header
+        // name fails fast.
+        let mut interceptor = BearerTokenInterceptor::with_fallbacks(
+            &self.token,
+            &self.fallback_tokens,
+            self.auth_scheme,
+        )?;
+        if let Some(name) = &self.auth_header_name {
+            interceptor = interceptor.with_header_name(name)?;
+        }
+        interceptor.custom = self.interceptor;
+
         let channel = endpoint.connect().await?;
+        let connection = Arc::new(ConnectionMonitor::new(self.on_connection_event));
+        connection.connected(Some(&self.endpoint));
+
+        Ok(Client::from_parts(
+            channel,
+            interceptor,
+            ClientOptions {
+                endpoint: Some(self.endpoint),
+                default_timeout: self.default_timeout,
+                type_prefix: self.type_prefix,
+                slow_rpc_threshold: self.slow_rpc_threshold,
+                default_consistency: self.default_consistency,
+                connection,
+                #[cfg(feature = "experimental")]
+                use_experimental_service: self.use_experimental_service,
+            },
+        ))
+    }
+}
+
+/// Builds the origin URI presenting `authority` over `endpoint`'s scheme.
+fn origin_uri(endpoint: &http::Uri, authority: &str) -> Result<http::Uri, Error> {
+    let parsed: http::uri::Authority = authority
+        .parse()
+        .map_err(|e| Error::InvalidArgument(format!("invalid authority {:?}: {}", authority, e)))?;
+    if parsed.as_str().contains('@') {
+        return Err(Error::InvalidArgument(format!(
+            "invalid authority {:?}: must not contain userinfo",
+            authority
+        )));
+    }
+    http::Uri::builder()
+        .scheme(endpoint.scheme_str().unwrap_or("https"))
+        .authority(parsed)
+        .path_and_query("/")
+        .build()
+        .map_err(|e| Error::InvalidArgument(format!("invalid authority {:?}: {}", authority, e)))
+}
+
+fn is_loopback(host: &str) -> bool {
+    host == "localhost" || host == "127.0.0.1" || host == "::1" || host == "[::1]"
+}
+
+/// Whether `host` is a literal RFC 1918 IPv4 or unique local IPv6 address.
+fn is_private_network(host: &str) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    match host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => ip.is_private(),
+        Ok(IpAddr::V6(ip)) => (ip.segments()[0] & 0xfe00) == 0xfc00,
+        Err(_) => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn private_network_ranges() {
+        for host in [
+            "10.1.2.3",
+            "172.16.0.1",
+            "172.31.255.255",
+            "192.168.1.10",
+            "[fd00::1]",
+        ] {
+            assert!(is_private_network(host), "{host}");
+        }
+        for host in [
+            "8.8.8.8",
+            "172.32.0.1",
+            "spicedb.internal",
+            "[2001:db8::1]",
+            "",
+        ] {
+            assert!(!is_private_network(host), "{host}");
+        }
+    }
+
+    #[test]
+    fn origin_uses_endpoint_scheme_and_given_authority() {
+        let endpoint: http::Uri = "https://ingress:443".parse().unwrap();
+        let origin = origin_uri(&endpoint, "spicedb.internal").unwrap();
+        assert_eq!(origin.to_string(), "https://spicedb.internal/");
+
+        let endpoint: http::Uri = "http://localhost:50051".parse().unwrap();
+        let origin = origin_uri(&endpoint, "spicedb.internal:50051").unwrap();
+        assert_eq!(origin.to_string(), "http://spicedb.internal:50051/");
+
+        for bad in ["", "spicedb.internal/path", "user@spicedb.internal", "a b"] {
+            assert!(
+                matches!(origin_uri(&endpoint, bad), Err(Error::InvalidArgument(_))),
+                "{bad}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn invalid_auth_header_name_fails_before_connecting() {
+        // Nothing listens on this port; the header error must come first.
+        let err = ClientBuilder::new("http://localhost:1", "t")
+            .auth_header_name("x-token-bin")
+            .build()
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::InvalidArgument(ref m) if m.contains("x-token-bin")));
+    }
+
+    #[test]
+    fn private_network_requires_opt_in() {
+        let builder = ClientBuilder::new("http://10.0.0.5:50051", "t");
+        assert!(matches!(
+            builder.check_plaintext_endpoint(),
+            Err(Error::InvalidArgument(_))
+        ));
+        let builder = builder.allow_private_network(true);
+        assert!(builder.check_plaintext_endpoint().is_ok());
+
+        // The opt-in covers private addresses only.
+        let builder = ClientBuilder::new("http://8.8.8.8:50051", "t").allow_private_network(true);
+        assert!(builder.check_plaintext_endpoint().is_err());
+    }
 
-        let header_value = format!("Bearer {}", self.token);
-        let meta_value: MetadataValue<tonic::metadata::Ascii> = header_value
-            .parse()
-            .map_err(|_| Error::InvalidArgument("invalid bearer token".into()))?;
-        let interceptor = BearerTokenInterceptor { token: meta_value };
-
-        let permissions =
-            PermissionsServiceClient::with_interceptor(channel.clone(), interceptor.clone());
-        let schema = SchemaServiceClient::with_interceptor(channel.clone(), interceptor.clone());
-        #[cfg(feature = "watch")]
-        let watch = WatchServiceClient::with_interceptor(channel, interceptor);
-
-        Ok(Client {
-            permissions,
-            schema,
-            #[cfg(feature = "watch")]
-            watch,
-            default_timeout: self.default_timeout,
-        })
+    #[test]
+    fn default_consistency_is_per_kind() {
+        let builder = ClientBuilder::new("http://localhost:50051", "token")
+            .default_consistency_for(RpcKind::Check, Consistency::FullyConsistent);
+        let defaults = builder.default_consistency;
+        assert_eq!(
+            defaults.get(RpcKind::Check),
+            Some((&Consistency::FullyConsistent).into())
+        );
+        assert_eq!(defaults.get(RpcKind::Lookup), None);
+        assert_eq!(defaults.get(RpcKind::Read), None);
+        assert_eq!(defaults.get(RpcKind::Expand), None);
     }
 }
@@ -0,0 +1,96 @@
+//! Request-scoped correlation IDs (see `CheckPermissionRequest::correlation_id`).
+//!
+//! A correlation ID is sent as the `x-request-id` metadata header, which
+//! SpiceDB logs with the request, and recorded on a `tracing` span around
+//! the call so client-side logs carry it too.
+
+use tonic::metadata::{Ascii, MetadataValue};
+
+use crate::error::Error;
+
+/// The metadata header a correlation ID is sent in.
+pub(crate) const HEADER: &str = "x-request-id";
+
+/// The longest correlation ID accepted, in bytes.
+const MAX_LEN: usize = 128;
+
+/// The correlation ID set on a request builder, or none. It is validated
+/// when the request is sent, not when it is set.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CorrelationId(Option<String>);
+
+impl CorrelationId {
+    pub(crate) fn new(id: String) -> Self {
+        Self(Some(id))
+    }
+
+    /// Checks the ID, if set: 1 to 128 printable ASCII characters, without
+    /// spaces.
+    pub(crate) fn validate(&self) -> Result<(), Error> {
+        match &self.0 {
+            Some(id)
+                if id.is_empty()
+                    || id.len() > MAX_LEN
+                    || !id.bytes().all(|b| b.is_ascii_graphic()) =>
+            {
+                Err(Error::InvalidArgument(format!(
+                    "invalid correlation ID {:?}: must be 1 to {} printable ASCII \
+                     characters without spaces",
+                    id, MAX_LEN
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Wraps `message` in a request carrying the correlation ID, if set.
+    pub(crate) fn request<T>(&self, message: T) -> tonic::Request<T> {
+        let mut request = tonic::Request::new(message);
+        // A validated ID is always a valid header value.
+        if let Some(value) = self
+            .0
+            .as_deref()
+            .and_then(|id| MetadataValue::<Ascii>::try_from(id).ok())
+        {
+            request.metadata_mut().insert(HEADER, value);
+        }
+        request
+    }
+
+    /// A span recording the correlation ID, or a disabled span if unset.
+    pub(crate) fn span(&self) -> tracing::Span {
+        match self.0.as_deref() {
+            Some(id) => tracing::debug_span!("spicedb_request", correlation_id = id),
+            None => tracing::Span::none(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_id_is_sent_as_header() {
+        let id = CorrelationId::new("req-7f3a".into());
+        id.validate().unwrap();
+        let request = id.request(());
+        assert_eq!(request.metadata().get(HEADER).unwrap(), "req-7f3a");
+
+        let none = CorrelationId::default().request(());
+        assert!(none.metadata().get(HEADER).is_none());
+    }
+
+    #[test]
+    fn invalid_ids_rejected() {
+        for bad in ["", "has space", "tab\tid", "naïve", &"x".repeat(129)] {
+            assert!(
+                matches!(
+                    CorrelationId::new(bad.to_string()).validate(),
+                    Err(Error::InvalidArgument(_))
+                ),
+                "{bad:?}"
+            );
+        }
+    }
+}
@@ -2,19 +2,33 @@
 //!
 //! These wrap the bulk RPCs that have been promoted to PermissionsService
 //! in the SpiceDB API but are feature-gated in this library since they
-//! may still evolve.
+//! may still evolve. For servers that predate the promotion, bulk checks can
+//! be routed to `ExperimentalService` with
+//! [`ClientBuilder::use_experimental_service`](super::ClientBuilder::use_experimental_service).
 
 use std::collections::HashMap;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+use std::time::Instant;
 
 use futures_core::Stream;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
 use tokio_stream::StreamExt;
 
 use crate::error::Error;
+use crate::limits;
 use crate::proto;
 use crate::types::context::context_to_struct;
 use crate::types::*;
 
+use super::lifecycle::StreamMonitor;
+use super::prefix::TypePrefix;
 use super::Client;
+use super::RpcKind;
 
 // ── BulkCheckItem ──────────────────────────────────────────────
 
@@ -53,9 +67,24 @@ impl BulkCheckItem {
     }
 }
 
+// ── RelationshipCount ──────────────────────────────────────────
+
+/// The value of a registered relationship counter.
+///
+/// Returned by [`Client::count`]. Relationship counters are an experimental
+/// SpiceDB API and may change or be removed in future server releases.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelationshipCount {
+    /// The number of relationships matching the counter's filter.
+    pub count: u64,
+    /// The revision at which the count was computed.
+    pub read_at: ZedToken,
+}
+
 // ── BulkCheckPermissions ──────────────────────────────────────────
 
 /// Builder for a BulkCheckPermissions request.
+#[derive(Clone)]
 pub struct BulkCheckPermissionsRequest<'a> {
     client: &'a Client,
     items: Vec<proto::CheckBulkPermissionsRequestItem>,
@@ -63,113 +92,766 @@ pub struct BulkCheckPermissionsRequest<'a> {
 }
 
 impl<'a> BulkCheckPermissionsRequest<'a> {
-    /// Sets the consistency mode.
-    pub fn consistency(mut self, c: Consistency) -> Self {
-        self.consistency = Some((&c).into());
+    /// Sets the consistency mode, or `None` for the server default.
+    pub fn consistency(mut self, c: impl Into<Option<Consistency>>) -> Self {
+        self.consistency = c.into().as_ref().map(Into::into);
         self
     }
 }
 
+impl<'a> BulkCheckPermissionsRequest<'a> {
+    async fn send(self) -> Result<BulkCheckResults, Error> {
+        if self.items.len() > limits::MAX_BULK_CHECK_ITEMS {
+            return Err(Error::InvalidArgument(format!(
+                "{} items exceeds the maximum of {} per bulk check; split them into smaller batches",
+                self.items.len(),
+                limits::MAX_BULK_CHECK_ITEMS
+            )));
+        }
+
+        // Prefix the kept items too, so echoed requests still match them.
+        let items = self.client.type_prefix.apply(self.items);
+        let exact = exact_snapshot(self.consistency.as_ref());
+        let req = proto::CheckBulkPermissionsRequest {
+            consistency: self.consistency,
+            items: items.clone(),
+            with_tracing: false,
+        };
+
+        let pairs = if self.client.use_experimental_service {
+            check_bulk_via_experimental_service(self.client, req)
+                .await
+                .map_err(|e| e.with_snapshot(exact.as_ref()))?
+        } else {
+            self.client
+                .authenticated(
+                    "PermissionsService.CheckBulkPermissions",
+                    &self.client.permissions,
+                    req,
+                    |mut s, r| async move { s.check_bulk_permissions(r).await },
+                )
+                .await
+                .map_err(|status| {
+                    Error::from_status_for("PermissionsService.CheckBulkPermissions")(status)
+                        .with_snapshot(exact.as_ref())
+                })?
+                .into_inner()
+                .pairs
+        };
+
+        align_bulk_pairs(&items, pairs)
+    }
+
+    /// Sends the items in batches of at most
+    /// [`limits::MAX_BULK_CHECK_ITEMS`], with up to `max_in_flight` batches
+    /// outstanding at once, and concatenates the results in item order.
+    ///
+    /// Fails with the first batch error, abandoning batches still in flight.
+    async fn send_batched(self, max_in_flight: usize) -> Result<BulkCheckResults, Error> {
+        if max_in_flight == 0 {
+            return Err(Error::InvalidArgument(
+                "max_in_flight must be at least 1".into(),
+            ));
+        }
+        if self.items.len() <= limits::MAX_BULK_CHECK_ITEMS {
+            return self.send().await;
+        }
+
+        let batches = self
+            .items
+            .chunks(limits::MAX_BULK_CHECK_ITEMS)
+            .map(|chunk| {
+                BulkCheckPermissionsRequest {
+                    client: self.client,
+                    items: chunk.to_vec(),
+                    consistency: self.consistency.clone(),
+                }
+                .send()
+            })
+            .collect();
+
+        let mut results = Vec::with_capacity(self.items.len());
+        for batch in run_bounded(batches, max_in_flight).await? {
+            results.extend(batch);
+        }
+        Ok(results.into())
+    }
+}
+
 impl<'a> std::future::IntoFuture for BulkCheckPermissionsRequest<'a> {
-    type Output = Result<Vec<CheckResult>, Error>;
+    type Output = Result<BulkCheckResults, Error>;
     type IntoFuture =
         std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'a>>;
 
     fn into_future(self) -> Self::IntoFuture {
-        Box::pin(async move {
-            let req = proto::CheckBulkPermissionsRequest {
-                consistency: self.consistency,
-                items: self.items,
-                with_tracing: false,
-            };
-
-            let response = self
-                .client
-                .permissions
-                .clone()
-                .check_bulk_permissions(req)
-                .await
-                .map_err(Error::from_status)?;
+        Box::pin(self.send())
+    }
+}
 
-            let inner = response.into_inner();
-            let results: Vec<CheckResult> = inner
-                .pairs
+/// Runs `futures` with at most `max_in_flight` polled at a time, returning
+/// their outputs in input order, or the first error.
+async fn run_bounded<T, F>(futures: Vec<F>, max_in_flight: usize) -> Result<Vec<T>, Error>
+where
+    F: std::future::Future<Output = Result<T, Error>>,
+{
+    let mut pending = futures.into_iter().enumerate();
+    let mut in_flight: Vec<(usize, Pin<Box<F>>)> = Vec::new();
+    let mut outputs: Vec<Option<T>> = Vec::new();
+    outputs.resize_with(pending.len(), || None);
+
+    loop {
+        while in_flight.len() < max_in_flight {
+            match pending.next() {
+                Some((index, future)) => in_flight.push((index, Box::pin(future))),
+                None => break,
+            }
+        }
+        if in_flight.is_empty() {
+            break;
+        }
+
+        let (slot, output) = std::future::poll_fn(|cx| {
+            for (slot, (_, future)) in in_flight.iter_mut().enumerate() {
+                if let Poll::Ready(output) = future.as_mut().poll(cx) {
+                    return Poll::Ready((slot, output));
+                }
+            }
+            Poll::Pending
+        })
+        .await;
+        let (index, _) = in_flight.swap_remove(slot);
+        outputs[index] = Some(output?);
+    }
+
+    Ok(outputs.into_iter().flatten().collect())
+}
+
+// ── EffectivePermissions ──────────────────────────────────────────
+
+/// Builder for checking several permissions of one subject on one resource.
+#[derive(Clone)]
+pub struct EffectivePermissionsRequest<'a> {
+    bulk: BulkCheckPermissionsRequest<'a>,
+    permissions: Vec<String>,
+    max_in_flight: usize,
+}
+
+impl<'a> EffectivePermissionsRequest<'a> {
+    /// Sets the consistency mode, or `None` for the server default.
+    pub fn consistency(mut self, c: impl Into<Option<Consistency>>) -> Self {
+        self.bulk = self.bulk.consistency(c);
+        self
+    }
+
+    /// Sets how many bulk check batches may be outstanding at once when
+    /// there are more permissions than fit in one bulk check.
+    ///
+    /// Defaults to 1 (batches are sent one after another). Sending fails
+    /// with `Err(InvalidArgument)` if `n` is 0.
+    pub fn max_in_flight(mut self, n: usize) -> Self {
+        self.max_in_flight = n;
+        self
+    }
+}
+
+impl<'a> std::future::IntoFuture for EffectivePermissionsRequest<'a> {
+    type Output = Result<HashMap<String, PermissionResult>, Error>;
+    type IntoFuture =
+        std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            if self.permissions.is_empty() {
+                return Ok(HashMap::new());
+            }
+            let results = self.bulk.send_batched(self.max_in_flight).await?;
+            self.permissions
                 .into_iter()
-                .map(|pair| match pair.response {
-                    Some(proto::check_bulk_permissions_pair::Response::Item(item)) => {
-                        PermissionResult::from_check_response(
-                            item.permissionship,
-                            item.partial_caveat_info,
-                        )
-                    }
-                    Some(proto::check_bulk_permissions_pair::Response::Error(status)) => {
-                        Err(Error::Status {
-                            code: tonic::Code::from_i32(status.code),
-                            message: status.message,
-                            details: None,
-                        })
+                .zip(results)
+                .map(|(permission, result)| result.map(|r| (permission, r)))
+                .collect()
+        })
+    }
+}
+
+// ── CheckFirstAllowed ──────────────────────────────────────────
+
+/// Builder for finding the first allowed of several permissions.
+#[derive(Clone)]
+pub struct CheckFirstAllowedRequest<'a> {
+    bulk: BulkCheckPermissionsRequest<'a>,
+    permissions: Vec<String>,
+}
+
+impl<'a> CheckFirstAllowedRequest<'a> {
+    /// Sets the consistency mode, or `None` for the server default.
+    pub fn consistency(mut self, c: impl Into<Option<Consistency>>) -> Self {
+        self.bulk = self.bulk.consistency(c);
+        self
+    }
+}
+
+impl<'a> std::future::IntoFuture for CheckFirstAllowedRequest<'a> {
+    type Output = Result<Option<String>, Error>;
+    type IntoFuture =
+        std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            if self.permissions.is_empty() {
+                return Ok(None);
+            }
+            let results = self.bulk.send_batched(1).await?;
+            for (permission, result) in self.permissions.into_iter().zip(results) {
+                if result? == PermissionResult::Allowed {
+                    return Ok(Some(permission));
+                }
+            }
+            Ok(None)
+        })
+    }
+}
+
+// ── CheckResources ──────────────────────────────────────────
+
+/// Builder for checking one permission of one subject on many resources.
+#[derive(Clone)]
+pub struct CheckResourcesRequest<'a> {
+    bulk: BulkCheckPermissionsRequest<'a>,
+    max_in_flight: usize,
+}
+
+impl<'a> CheckResourcesRequest<'a> {
+    /// Sets the consistency mode, or `None` for the server default.
+    ///
+    /// When the resources span several batches, each batch is evaluated at
+    /// the revision its consistency mode selects; pin a revision with
+    /// [`Consistency::AtExactSnapshot`] for a single consistent view.
+    pub fn consistency(mut self, c: impl Into<Option<Consistency>>) -> Self {
+        self.bulk = self.bulk.consistency(c);
+        self
+    }
+
+    /// Sets how many bulk check batches may be outstanding at once.
+    ///
+    /// Defaults to 1 (batches are sent one after another), to avoid
+    /// overwhelming the server. Sending fails with `Err(InvalidArgument)` if
+    /// `n` is 0.
+    pub fn max_in_flight(mut self, n: usize) -> Self {
+        self.max_in_flight = n;
+        self
+    }
+}
+
+impl<'a> std::future::IntoFuture for CheckResourcesRequest<'a> {
+    type Output = Result<BulkCheckResults, Error>;
+    type IntoFuture =
+        std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(self.bulk.send_batched(self.max_in_flight))
+    }
+}
+
+// ── CheckAll / CheckAny ──────────────────────────────────────────
+
+/// Builder for combining bulk check results into a single pass/fail
+/// decision, via [`Client::check_all`] or [`Client::check_any`].
+#[derive(Clone)]
+pub struct CombinedCheckRequest<'a> {
+    bulk: BulkCheckPermissionsRequest<'a>,
+    require_all: bool,
+    conditional_policy: ConditionalPolicy,
+}
+
+impl<'a> CombinedCheckRequest<'a> {
+    /// Sets the consistency mode, or `None` for the server default.
+    pub fn consistency(mut self, c: impl Into<Option<Consistency>>) -> Self {
+        self.bulk = self.bulk.consistency(c);
+        self
+    }
+
+    /// Sets how a `Conditional` result counts.
+    ///
+    /// Defaults to [`ConditionalPolicy::TreatAsDenied`]: a check that is
+    /// missing caveat context fails.
+    pub fn conditional_policy(mut self, policy: ConditionalPolicy) -> Self {
+        self.conditional_policy = policy;
+        self
+    }
+
+    /// Sets whether a `Conditional` result counts as a pass.
+    #[deprecated(note = "use `conditional_policy`")]
+    pub fn conditional_passes(self, passes: bool) -> Self {
+        self.conditional_policy(if passes {
+            ConditionalPolicy::TreatAsAllowed
+        } else {
+            ConditionalPolicy::TreatAsDenied
+        })
+    }
+
+    /// Returns whether `result` passes, or the per-item error.
+    fn passes(&self, result: CheckResult) -> Result<bool, Error> {
+        Ok(result?.to_bool(self.conditional_policy))
+    }
+}
+
+impl<'a> std::future::IntoFuture for CombinedCheckRequest<'a> {
+    type Output = Result<bool, Error>;
+    type IntoFuture =
+        std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            if self.bulk.items.is_empty() {
+                return Err(Error::InvalidArgument("items must not be empty".into()));
+            }
+            // A result that differs from `require_all` decides the outcome:
+            // a failure for check_all, a pass for check_any.
+            for chunk in self.bulk.items.chunks(limits::MAX_BULK_CHECK_ITEMS) {
+                let results = BulkCheckPermissionsRequest {
+                    client: self.bulk.client,
+                    items: chunk.to_vec(),
+                    consistency: self.bulk.consistency.clone(),
+                }
+                .send()
+                .await?;
+                for result in results {
+                    if self.passes(result)? != self.require_all {
+                        return Ok(!self.require_all);
                     }
-                    None => Err(Error::Serialization(
-                        "missing response in bulk check pair".into(),
-                    )),
-                })
-                .collect();
-            Ok(results)
+                }
+            }
+            Ok(self.require_all)
+        })
+    }
+}
+
+/// Sends a bulk check through the pre-v1.33.0 `ExperimentalService` RPC,
+/// translating to and from the promoted `PermissionsService` messages.
+async fn check_bulk_via_experimental_service(
+    client: &Client,
+    req: proto::CheckBulkPermissionsRequest,
+) -> Result<Vec<proto::CheckBulkPermissionsPair>, Error> {
+    let legacy = proto::BulkCheckPermissionRequest {
+        consistency: req.consistency,
+        items: req
+            .items
+            .into_iter()
+            .map(|item| proto::BulkCheckPermissionRequestItem {
+                resource: item.resource,
+                permission: item.permission,
+                subject: item.subject,
+                context: item.context,
+            })
+            .collect(),
+    };
+
+    let response = client
+        .authenticated(
+            "ExperimentalService.BulkCheckPermission",
+            &client.experimental,
+            legacy,
+            |mut s, r| async move { s.bulk_check_permission(r).await },
+        )
+        .await
+        .map_err(Error::from_status_for(
+            "ExperimentalService.BulkCheckPermission",
+        ))?;
+
+    Ok(response
+        .into_inner()
+        .pairs
+        .into_iter()
+        .map(|pair| {
+            use proto::bulk_check_permission_pair::Response as Legacy;
+            use proto::check_bulk_permissions_pair::Response;
+            proto::CheckBulkPermissionsPair {
+                request: pair
+                    .request
+                    .map(|item| proto::CheckBulkPermissionsRequestItem {
+                        resource: item.resource,
+                        permission: item.permission,
+                        subject: item.subject,
+                        context: item.context,
+                    }),
+                response: pair.response.map(|response| match response {
+                    Legacy::Item(item) => Response::Item(proto::CheckBulkPermissionsResponseItem {
+                        permissionship: item.permissionship,
+                        partial_caveat_info: item.partial_caveat_info,
+                        debug_trace: None,
+                    }),
+                    Legacy::Error(status) => Response::Error(status),
+                }),
+            }
         })
+        .collect())
+}
+
+/// Converts bulk check response pairs into results ordered like `items`.
+///
+/// Each pair is matched back to its request item via the echoed request rather
+/// than by position, so results stay correct even if the server reorders pairs.
+/// Duplicate items are matched in input order.
+fn align_bulk_pairs(
+    items: &[proto::CheckBulkPermissionsRequestItem],
+    pairs: Vec<proto::CheckBulkPermissionsPair>,
+) -> Result<BulkCheckResults, Error> {
+    use prost::Message;
+    use std::collections::VecDeque;
+
+    if pairs.len() != items.len() {
+        return Err(Error::Serialization(format!(
+            "bulk check returned {} results for {} items",
+            pairs.len(),
+            items.len()
+        )));
+    }
+
+    // Encoded protobuf bytes are a stable key: Struct fields are ordered maps.
+    let mut positions: HashMap<Vec<u8>, VecDeque<usize>> = HashMap::new();
+    for (i, item) in items.iter().enumerate() {
+        positions
+            .entry(item.encode_to_vec())
+            .or_default()
+            .push_back(i);
+    }
+
+    let mut slots: Vec<Option<CheckResult>> = Vec::with_capacity(items.len());
+    slots.resize_with(items.len(), || None);
+    for pair in pairs {
+        let request = pair.request.ok_or_else(|| {
+            Error::Serialization("missing echoed request in bulk check pair".into())
+        })?;
+        let index = positions
+            .get_mut(&request.encode_to_vec())
+            .and_then(VecDeque::pop_front)
+            .ok_or_else(|| {
+                Error::Serialization(format!(
+                    "bulk check pair does not match any request item: {}#{}",
+                    request
+                        .resource
+                        .as_ref()
+                        .map(|r| format!("{}:{}", r.object_type, r.object_id))
+                        .unwrap_or_default(),
+                    request.permission
+                ))
+            })?;
+        slots[index] = Some(pair_result(pair.response));
+    }
+
+    // Equal lengths plus one slot per matched pair means every slot is filled.
+    Ok(slots.into_iter().flatten().collect::<Vec<_>>().into())
+}
+
+fn pair_result(response: Option<proto::check_bulk_permissions_pair::Response>) -> CheckResult {
+    match response {
+        Some(proto::check_bulk_permissions_pair::Response::Item(item)) => {
+            PermissionResult::from_check_response(item.permissionship, item.partial_caveat_info)
+        }
+        Some(proto::check_bulk_permissions_pair::Response::Error(status)) => Err(Error::Status {
+            code: tonic::Code::from_i32(status.code),
+            message: status.message,
+            details: None,
+        }),
+        None => Err(Error::Serialization(
+            "missing response in bulk check pair".into(),
+        )),
     }
 }
 
 // ── BulkImportRelationships ──────────────────────────────────────────
 
+/// Relationships sent per ImportBulkRelationships message.
+const IMPORT_BATCH_SIZE: usize = 128;
+
+/// How a bulk import handles relationships that already exist.
+///
+/// The ImportBulkRelationships RPC itself has no conflict option: any
+/// existing relationship fails the import with `ALREADY_EXISTS`. `Skip` and
+/// `Touch` are implemented by the client, as `zed backup restore` does, and
+/// so work against any SpiceDB that supports bulk import.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ImportConflictStrategy {
+    /// Fail the whole import on the first existing relationship. The import
+    /// is a single transaction, so nothing is loaded.
+    #[default]
+    Fail,
+    /// Leave existing relationships as they are and load the rest.
+    Skip,
+    /// Overwrite existing relationships, replacing their caveat and context.
+    Touch,
+}
+
+/// The outcome of a bulk import.
+///
+/// SpiceDB reports only the number of relationships loaded; the other
+/// counts are tracked by the client. `ImportSummary` dereferences to
+/// [`num_loaded`](Self::num_loaded), converts into a `u64`, and compares
+/// equal to one, so code written when imports returned a bare `u64` keeps
+/// working.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ImportSummary {
+    /// Relationships loaded, including those written through the
+    /// WriteRelationships fallback of a conflict strategy.
+    pub num_loaded: u64,
+    /// ImportBulkRelationships messages sent, each carrying up to 128
+    /// relationships.
+    pub batches: u64,
+    /// Relationships left as they were because they already existed. Only
+    /// [`ImportConflictStrategy::Skip`] skips any.
+    pub skipped: u64,
+}
+
+impl std::ops::Deref for ImportSummary {
+    type Target = u64;
+
+    fn deref(&self) -> &u64 {
+        &self.num_loaded
+    }
+}
+
+impl From<ImportSummary> for u64 {
+    fn from(summary: ImportSummary) -> u64 {
+        summary.num_loaded
+    }
+}
+
+impl PartialEq<u64> for ImportSummary {
+    fn eq(&self, other: &u64) -> bool {
+        self.num_loaded == *other
+    }
+}
+
+impl std::ops::AddAssign for ImportSummary {
+    fn add_assign(&mut self, other: ImportSummary) {
+        self.num_loaded += other.num_loaded;
+        self.batches += other.batches;
+        self.skipped += other.skipped;
+    }
+}
+
 /// Builder for a BulkImportRelationships request.
 pub struct BulkImportRelationshipsRequest<'a, S> {
     client: &'a Client,
     stream: S,
+    conflict_strategy: ImportConflictStrategy,
 }
 
 impl<'a, S> BulkImportRelationshipsRequest<'a, S>
 where
     S: Stream<Item = Relationship> + Send + 'static,
 {
-    /// Sends the client-streaming import request and returns the number of relationships loaded.
-    pub async fn send(self) -> Result<u64, Error> {
-        // Collect all relationships and batch them into chunks to reduce
-        // per-message overhead while keeping memory bounded per message.
-        let batch_size = 128;
-        let request_stream = async_stream::stream! {
-            let mut batch: Vec<proto::Relationship> = Vec::with_capacity(batch_size);
-            let mut stream = std::pin::pin!(self.stream);
-            while let Some(rel) = StreamExt::next(&mut stream).await {
-                batch.push((&rel).into());
-                if batch.len() >= batch_size {
-                    yield proto::ImportBulkRelationshipsRequest {
-                        relationships: std::mem::take(&mut batch),
-                    };
-                    batch = Vec::with_capacity(batch_size);
+    /// Sets how relationships that already exist are handled. Defaults to
+    /// [`ImportConflictStrategy::Fail`].
+    ///
+    /// With `Skip` or `Touch`, the import is sent as one ImportBulkRelationships
+    /// call per batch of 128 relationships instead of a single stream, so it
+    /// is atomic per batch only. A batch that hits an existing relationship is
+    /// retried through WriteRelationships: with `Touch` as one write of TOUCH
+    /// updates, with `Skip` as one CREATE per relationship, ignoring those
+    /// that exist. Re-running an interrupted import with either strategy is
+    /// idempotent.
+    pub fn conflict_strategy(mut self, strategy: ImportConflictStrategy) -> Self {
+        self.conflict_strategy = strategy;
+        self
+    }
+
+    /// Sends the import and returns an [`ImportSummary`], whose
+    /// `num_loaded` is the number of relationships loaded.
+    ///
+    /// With [`ImportConflictStrategy::Skip`], skipped relationships are not
+    /// counted as loaded but as `skipped`.
+    pub async fn send(self) -> Result<ImportSummary, Error> {
+        match self.conflict_strategy {
+            ImportConflictStrategy::Fail => self.send_stream().await,
+            strategy => {
+                let client = self.client;
+                let mut stream = std::pin::pin!(self.stream);
+                let mut summary = ImportSummary::default();
+                let mut done = false;
+                while !done {
+                    let mut batch = Vec::with_capacity(IMPORT_BATCH_SIZE);
+                    while batch.len() < IMPORT_BATCH_SIZE {
+                        match StreamExt::next(&mut stream).await {
+                            Some(rel) => batch.push(rel),
+                            None => {
+                                done = true;
+                                break;
+                            }
+                        }
+                    }
+                    if !batch.is_empty() {
+                        summary += import_batch(client, batch, strategy).await?;
+                    }
                 }
+                Ok(summary)
             }
-            if !batch.is_empty() {
-                yield proto::ImportBulkRelationshipsRequest {
-                    relationships: batch,
-                };
-            }
+        }
+    }
+
+    /// Imports the whole stream as a single client-streaming call.
+    async fn send_stream(self) -> Result<ImportSummary, Error> {
+        // Batch relationships into chunks to reduce per-message overhead
+        // while keeping memory bounded per message.
+        let batch_size = IMPORT_BATCH_SIZE;
+        let type_prefix = self.client.type_prefix.clone();
+        let batches = Arc::new(AtomicU64::new(0));
+        let sent = batches.clone();
+        let request_stream = ImportRequests {
+            stream: Box::pin(self.stream),
+            batch: Vec::with_capacity(batch_size),
+            batch_size,
+            type_prefix,
+            sent,
+            done: false,
         };
 
+        let started = Instant::now();
         let response = self
             .client
             .permissions
             .clone()
             .import_bulk_relationships(request_stream)
-            .await
-            .map_err(Error::from_status)?;
+            .await;
+        self.client
+            .warn_if_slow("PermissionsService.ImportBulkRelationships", started);
+        self.client.record_outcome(&response);
+        let response = response.map_err(Error::from_status_for(
+            "PermissionsService.ImportBulkRelationships",
+        ))?;
+
+        Ok(ImportSummary {
+            num_loaded: response.into_inner().num_loaded,
+            batches: batches.load(Ordering::Relaxed),
+            skipped: 0,
+        })
+    }
+}
+
+/// The request stream of a single-call import: the relationships of `stream`
+/// in messages of up to `batch_size`, counting each message sent.
+struct ImportRequests<S> {
+    stream: Pin<Box<S>>,
+    batch: Vec<proto::Relationship>,
+    batch_size: usize,
+    type_prefix: TypePrefix,
+    sent: Arc<AtomicU64>,
+    done: bool,
+}
+
+impl<S> ImportRequests<S> {
+    fn take_batch(&mut self) -> proto::ImportBulkRelationshipsRequest {
+        self.sent.fetch_add(1, Ordering::Relaxed);
+        proto::ImportBulkRelationshipsRequest {
+            relationships: std::mem::replace(&mut self.batch, Vec::with_capacity(self.batch_size)),
+        }
+    }
+}
+
+impl<S: Stream<Item = Relationship>> Stream for ImportRequests<S> {
+    type Item = proto::ImportBulkRelationshipsRequest;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        while !this.done {
+            match ready!(this.stream.as_mut().poll_next(cx)) {
+                Some(rel) => {
+                    this.batch.push(this.type_prefix.apply((&rel).into()));
+                    if this.batch.len() >= this.batch_size {
+                        return Poll::Ready(Some(this.take_batch()));
+                    }
+                }
+                None => this.done = true,
+            }
+        }
+        if this.batch.is_empty() {
+            Poll::Ready(None)
+        } else {
+            Poll::Ready(Some(this.take_batch()))
+        }
+    }
+}
 
-        Ok(response.into_inner().num_loaded)
+/// Imports one batch, falling back to WriteRelationships if any of it
+/// already exists.
+async fn import_batch(
+    client: &Client,
+    batch: Vec<Relationship>,
+    strategy: ImportConflictStrategy,
+) -> Result<ImportSummary, Error> {
+    let request = proto::ImportBulkRelationshipsRequest {
+        relationships: batch
+            .iter()
+            .map(|rel| client.type_prefix.apply(rel.into()))
+            .collect(),
+    };
+    // The batch is a single message, so unlike the streaming import it can
+    // be cloned and resent with a fallback token.
+    let response = client
+        .authenticated(
+            "PermissionsService.ImportBulkRelationships",
+            &client.permissions,
+            request,
+            |mut s, r| async move { s.import_bulk_relationships(tokio_stream::once(r)).await },
+        )
+        .await;
+    let status = match response {
+        Ok(response) => {
+            return Ok(ImportSummary {
+                num_loaded: response.into_inner().num_loaded,
+                batches: 1,
+                skipped: 0,
+            })
+        }
+        Err(status) => status,
+    };
+    if status.code() != tonic::Code::AlreadyExists {
+        return Err(Error::from_status_for(
+            "PermissionsService.ImportBulkRelationships",
+        )(status));
+    }
+
+    match strategy {
+        ImportConflictStrategy::Fail => Err(Error::from_status(status)),
+        ImportConflictStrategy::Touch => {
+            let count = batch.len() as u64;
+            client
+                .write_relationships(RelationshipUpdate::touch_all(batch))
+                .await?;
+            Ok(ImportSummary {
+                num_loaded: count,
+                batches: 1,
+                skipped: 0,
+            })
+        }
+        ImportConflictStrategy::Skip => {
+            let count = batch.len() as u64;
+            let mut created = 0;
+            for rel in batch {
+                match client
+                    .write_relationships(vec![RelationshipUpdate::create(rel)])
+                    .await
+                {
+                    Ok(_) => created += 1,
+                    Err(err) if err.code() == Some(tonic::Code::AlreadyExists) => {}
+                    Err(err) => return Err(err),
+                }
+            }
+            Ok(ImportSummary {
+                num_loaded: created,
+                batches: 1,
+                skipped: count - created,
+            })
+        }
     }
 }
 
 // ── BulkExportRelationships ──────────────────────────────────────────
 
 /// Builder for a BulkExportRelationships streaming request.
+#[derive(Clone)]
 pub struct BulkExportRelationshipsRequest<'a> {
     client: &'a Client,
     filter: Option<proto::RelationshipFilter>,
@@ -177,14 +859,20 @@ pub struct BulkExportRelationshipsRequest<'a> {
 }
 
 impl<'a> BulkExportRelationshipsRequest<'a> {
-    /// Sets the consistency mode.
-    pub fn consistency(mut self, c: Consistency) -> Self {
-        self.consistency = Some((&c).into());
+    /// Sets the consistency mode, or `None` for the server default.
+    ///
+    /// The export is a single streaming call, and SpiceDB serves every batch
+    /// of it at the revision chosen when the call starts, so writes made
+    /// while it streams never appear. For a point-in-time backup, pass
+    /// [`Consistency::AtExactSnapshot`] with a known token.
+    pub fn consistency(mut self, c: impl Into<Option<Consistency>>) -> Self {
+        self.consistency = c.into().as_ref().map(Into::into);
         self
     }
 
     /// Sends the request and returns a stream of relationships.
-    pub async fn send(self) -> Result<impl Stream<Item = Result<Relationship, Error>>, Error> {
+    pub async fn send(self) -> Result<BulkExportStream, Error> {
+        let exact = exact_snapshot(self.consistency.as_ref());
         let req = proto::ExportBulkRelationshipsRequest {
             consistency: self.consistency,
             optional_limit: 0,
@@ -194,31 +882,156 @@ impl<'a> BulkExportRelationshipsRequest<'a> {
 
         let response = self
             .client
-            .permissions
-            .clone()
-            .export_bulk_relationships(req)
+            .authenticated(
+                "PermissionsService.ExportBulkRelationships",
+                &self.client.permissions,
+                self.client.type_prefix.apply(req),
+                |mut s, r| async move { s.export_bulk_relationships(r).await },
+            )
             .await
-            .map_err(Error::from_status)?;
-
-        // Each response batch contains multiple relationships; flatten into individual items.
-        let inner = response.into_inner();
-        let stream = async_stream::try_stream! {
-            let mut inner = inner;
-            while let Some(result) = inner.next().await {
-                match result {
-                    Ok(batch) => {
-                        for rel in batch.relationships {
-                            let r: Relationship = rel.try_into()?;
-                            yield r;
-                        }
-                    }
-                    Err(status) => {
-                        Err(Error::from_status(status))?;
-                    }
+            .map_err(|status| {
+                Error::from_status_for("PermissionsService.ExportBulkRelationships")(status)
+                    .with_snapshot(exact.as_ref())
+            })?;
+
+        Ok(BulkExportStream {
+            inner: response.into_inner(),
+            batch: Vec::new().into_iter(),
+            type_prefix: self.client.type_prefix.clone(),
+            exact,
+            monitor: self.client.stream_monitor(),
+            done: false,
+        })
+    }
+}
+
+impl<'a> BulkExportRelationshipsRequest<'a> {
+    /// Exports the relationships, rewrites each with `map`, and bulk imports
+    /// the results into `target`, without collecting them in memory.
+    ///
+    /// This is the export-to-import pipeline for migrations such as renaming
+    /// an object type or moving data between servers; `target` may be the
+    /// same client. Returns the import's [`ImportSummary`].
+    ///
+    /// Relationships flow through one batch at a time: the export is only
+    /// read as fast as the import stream is sent, so a slow target slows the
+    /// export instead of buffering it. The import is a single
+    /// ImportBulkRelationships call and so a single transaction: if the
+    /// export fails partway, the import is cancelled and nothing is written.
+    /// Any existing relationship fails the import; for other conflict
+    /// handling, feed the export stream to
+    /// [`Client::bulk_import_relationships`] yourself.
+    ///
+    /// ```rust,no_run
+    /// # use prescience::{Client, ObjectReference, RelationshipFilter};
+    /// # async fn example(client: &Client) -> Result<(), prescience::Error> {
+    /// // Copy every `doc` relationship to the renamed `document` type.
+    /// let copied = client
+    ///     .bulk_export_relationships(RelationshipFilter::new("doc"))
+    ///     .reimport_to(client, |mut rel| {
+    ///         rel.resource = ObjectReference::new_unchecked("document", rel.resource.object_id());
+    ///         rel
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn reimport_to<F>(self, target: &Client, mut map: F) -> Result<ImportSummary, Error>
+    where
+        F: FnMut(Relationship) -> Relationship + Send + 'static,
+    {
+        let mut export = self.send().await?;
+
+        // A channel of one keeps the export in step with the import. The
+        // import only ends, and so commits, when `sender` is dropped, which
+        // happens after the import is cancelled if the export fails.
+        let (sender, receiver) = mpsc::channel(1);
+        let mut import = Box::pin(
+            target
+                .bulk_import_relationships(ReceiverStream::new(receiver))
+                .send(),
+        );
+        let pump = async {
+            while let Some(result) = StreamExt::next(&mut export).await {
+                if sender.send(map(result?)).await.is_err() {
+                    // The import ended early and will report why.
+                    break;
                 }
             }
+            Ok(())
         };
-        Ok(stream)
+
+        let pumped: Result<(), Error> = tokio::select! {
+            imported = &mut import => return imported,
+            pumped = pump => pumped,
+        };
+        match pumped {
+            Ok(()) => {
+                drop(sender);
+                import.await
+            }
+            Err(error) => {
+                drop(import);
+                drop(sender);
+                Err(error)
+            }
+        }
+    }
+}
+
+/// The stream of relationships returned by
+/// [`BulkExportRelationshipsRequest::send`].
+///
+/// SpiceDB sends relationships in batches; this flattens them into one
+/// `Result<Relationship, Error>` per item. The stream ends after the first
+/// error. Being a named type, it can be stored in a struct field without
+/// boxing.
+pub struct BulkExportStream {
+    inner: tonic::Streaming<proto::ExportBulkRelationshipsResponse>,
+    batch: std::vec::IntoIter<proto::Relationship>,
+    type_prefix: TypePrefix,
+    /// The snapshot token requested, for reporting a GC'd revision.
+    exact: Option<ZedToken>,
+    monitor: StreamMonitor,
+    done: bool,
+}
+
+impl fmt::Debug for BulkExportStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BulkExportStream")
+            .field("buffered", &self.batch.len())
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Stream for BulkExportStream {
+    type Item = Result<Relationship, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(rel) = self.batch.next() {
+                return Poll::Ready(Some(rel.try_into()));
+            }
+            if self.done {
+                return Poll::Ready(None);
+            }
+            match ready!(Pin::new(&mut self.inner).poll_next(cx)) {
+                Some(Ok(batch)) => {
+                    self.batch = self.type_prefix.strip(batch).relationships.into_iter();
+                }
+                Some(Err(status)) => {
+                    self.done = true;
+                    self.monitor.record(&status);
+                    let err = Error::from_status(status).with_snapshot(self.exact.as_ref());
+                    return Poll::Ready(Some(Err(err)));
+                }
+                None => {
+                    self.done = true;
+                    return Poll::Ready(None);
+                }
+            }
+        }
     }
 }
 
@@ -227,8 +1040,11 @@ impl<'a> BulkExportRelationshipsRequest<'a> {
 impl Client {
     /// Checks permissions for a batch of items in a single round-trip.
     ///
-    /// Returns a `Vec<CheckResult>` where each item is either a
-    /// `PermissionResult` or a per-item `Error`.
+    /// Returns [`BulkCheckResults`] where each item is either a
+    /// `PermissionResult` or a per-item `Error`, in the same order as `items`
+    /// (pairs are matched by the request SpiceDB echoes back, not by position).
+    /// Returns `Err(InvalidArgument)`
+    /// if `items` exceeds [`limits::MAX_BULK_CHECK_ITEMS`].
     pub fn bulk_check_permissions(
         &self,
         items: Vec<BulkCheckItem>,
@@ -246,14 +1062,324 @@ impl Client {
         BulkCheckPermissionsRequest {
             client: self,
             items: proto_items,
-            consistency: None,
+            consistency: self.default_consistency.get(RpcKind::Check),
+        }
+    }
+
+    /// Registers a named relationship counter for the given filter.
+    ///
+    /// SpiceDB maintains the count in the background; read it with
+    /// [`Client::count`]. Returns `Err(InvalidArgument)` if `name` is empty.
+    ///
+    /// This wraps SpiceDB's experimental `ExperimentalService` and may change.
+    pub async fn register_relationship_counter(
+        &self,
+        name: impl Into<String>,
+        filter: RelationshipFilter,
+    ) -> Result<(), Error> {
+        let name = name.into();
+        if name.is_empty() {
+            return Err(Error::InvalidArgument(
+                "counter name must not be empty".into(),
+            ));
+        }
+
+        self.authenticated(
+            "ExperimentalService.ExperimentalRegisterRelationshipCounter",
+            &self.experimental,
+            self.type_prefix
+                .apply(proto::ExperimentalRegisterRelationshipCounterRequest {
+                    name,
+                    relationship_filter: Some((&filter).into()),
+                }),
+            |mut s, r| async move { s.experimental_register_relationship_counter(r).await },
+        )
+        .await
+        .map_err(Error::from_status_for(
+            "ExperimentalService.ExperimentalRegisterRelationshipCounter",
+        ))?;
+        Ok(())
+    }
+
+    /// Reads the current value of a registered relationship counter.
+    ///
+    /// If SpiceDB has not finished computing the counter yet, this returns
+    /// [`Error::CounterStillCalculating`]; poll again after a delay.
+    ///
+    /// This wraps SpiceDB's experimental `ExperimentalService` and may change.
+    pub async fn count(&self, name: impl Into<String>) -> Result<RelationshipCount, Error> {
+        let name = name.into();
+        if name.is_empty() {
+            return Err(Error::InvalidArgument(
+                "counter name must not be empty".into(),
+            ));
+        }
+
+        let response = self
+            .authenticated(
+                "ExperimentalService.ExperimentalCountRelationships",
+                &self.experimental,
+                proto::ExperimentalCountRelationshipsRequest { name: name.clone() },
+                |mut s, r| async move { s.experimental_count_relationships(r).await },
+            )
+            .await
+            .map_err(Error::from_status_for(
+                "ExperimentalService.ExperimentalCountRelationships",
+            ))?;
+
+        match response.into_inner().counter_result {
+            Some(proto::experimental_count_relationships_response::CounterResult::ReadCounterValue(
+                value,
+            )) => Ok(RelationshipCount {
+                count: value.relationship_count,
+                read_at: value
+                    .read_at
+                    .ok_or_else(|| Error::Serialization("missing read_at token".into()))?
+                    .try_into()?,
+            }),
+            Some(
+                proto::experimental_count_relationships_response::CounterResult::CounterStillCalculating(
+                    _,
+                ),
+            ) => Err(Error::CounterStillCalculating { name }),
+            None => Err(Error::Serialization(
+                "missing counter result in count response".into(),
+            )),
+        }
+    }
+
+    /// Checks several permissions of one subject on one resource in a single
+    /// round-trip, returning the results keyed by permission name.
+    ///
+    /// Built on [`Client::bulk_check_permissions`]; the typical use is deciding
+    /// which actions to offer on a resource. If any permission's check fails,
+    /// the first such error is returned. Duplicate names are checked once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use prescience::{Client, ObjectReference, SubjectReference};
+    /// # async fn example(client: &Client, doc: &ObjectReference, alice: &SubjectReference)
+    /// # -> Result<(), prescience::Error> {
+    /// let perms = client
+    ///     .effective_permissions(doc, alice, vec!["view", "edit", "delete"])
+    ///     .await?;
+    /// let show_edit_button = perms["edit"].is_allowed().unwrap_or(false);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn effective_permissions(
+        &self,
+        resource: &ObjectReference,
+        subject: &SubjectReference,
+        permissions: Vec<impl Into<String>>,
+    ) -> EffectivePermissionsRequest<'_> {
+        let mut permissions: Vec<String> = permissions.into_iter().map(Into::into).collect();
+        let mut seen = std::collections::HashSet::new();
+        permissions.retain(|p| seen.insert(p.clone()));
+
+        let items = permissions
+            .iter()
+            .map(|permission| BulkCheckItem::new(resource.clone(), permission, subject.clone()))
+            .collect();
+        EffectivePermissionsRequest {
+            bulk: self.bulk_check_permissions(items),
+            permissions,
+            max_in_flight: 1,
+        }
+    }
+
+    /// Checks several permissions of one subject on one resource in a single
+    /// round-trip, returning the first of `permissions`, in order, that is
+    /// `Allowed`, or `None` if none is.
+    ///
+    /// Built on [`Client::bulk_check_permissions`], this is an ordered OR:
+    /// use it to map several application roles onto SpiceDB permissions.
+    /// `Conditional` results do not count as allowed. If the check of a
+    /// permission before the first allowed one fails, that error is
+    /// returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use prescience::{Client, ObjectReference, SubjectReference};
+    /// # async fn example(client: &Client, doc: &ObjectReference, alice: &SubjectReference)
+    /// # -> Result<(), prescience::Error> {
+    /// match client.check_first_allowed(doc, alice, vec!["admin", "edit"]).await?.as_deref() {
+    ///     Some("admin") => println!("full access"),
+    ///     Some(_) => println!("can edit"),
+    ///     None => println!("access denied"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn check_first_allowed(
+        &self,
+        resource: &ObjectReference,
+        subject: &SubjectReference,
+        permissions: Vec<impl Into<String>>,
+    ) -> CheckFirstAllowedRequest<'_> {
+        let permissions: Vec<String> = permissions.into_iter().map(Into::into).collect();
+        let items = permissions
+            .iter()
+            .map(|permission| BulkCheckItem::new(resource.clone(), permission, subject.clone()))
+            .collect();
+        CheckFirstAllowedRequest {
+            bulk: self.bulk_check_permissions(items),
+            permissions,
+        }
+    }
+
+    /// Checks one permission of one subject on many resources, returning
+    /// one result per resource in the same order as `resources`.
+    ///
+    /// Built on [`Client::bulk_check_permissions`], but any number of
+    /// resources is accepted: they are split into batches of at most
+    /// [`limits::MAX_BULK_CHECK_ITEMS`], sent one at a time unless
+    /// [`max_in_flight`](CheckResourcesRequest::max_in_flight) allows more.
+    /// If a whole batch fails, that error is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use prescience::{Client, ObjectReference, SubjectReference};
+    /// # async fn example(client: &Client, docs: Vec<ObjectReference>, alice: &SubjectReference)
+    /// # -> Result<(), prescience::Error> {
+    /// let results = client
+    ///     .check_resources(docs.clone(), "view", alice)
+    ///     .max_in_flight(4)
+    ///     .await?;
+    /// let visible: Vec<_> = docs
+    ///     .iter()
+    ///     .zip(&results)
+    ///     .filter(|(_, r)| matches!(r, Ok(p) if p.is_allowed().unwrap_or(false)))
+    ///     .map(|(doc, _)| doc)
+    ///     .collect();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn check_resources(
+        &self,
+        resources: Vec<ObjectReference>,
+        permission: impl Into<String>,
+        subject: &SubjectReference,
+    ) -> CheckResourcesRequest<'_> {
+        let permission = permission.into();
+        let items = resources
+            .into_iter()
+            .map(|resource| BulkCheckItem::new(resource, permission.clone(), subject.clone()))
+            .collect();
+        CheckResourcesRequest {
+            bulk: self.bulk_check_permissions(items),
+            max_in_flight: 1,
+        }
+    }
+
+    /// Like [`Client::check_resources`], but with caveat context for each
+    /// resource.
+    ///
+    /// Each resource is checked with its own context, so resources caveated
+    /// differently (e.g. by IP allowlist or expiry) resolve to `Allowed` or
+    /// `Denied` instead of all coming back `Conditional`. Pass an empty map
+    /// for a resource that needs none.
+    ///
+    /// ```rust,no_run
+    /// # use std::collections::HashMap;
+    /// # use prescience::{Client, ContextValue, ObjectReference, SubjectReference};
+    /// # async fn example(client: &Client, alice: &SubjectReference) -> Result<(), prescience::Error> {
+    /// let mut office = HashMap::new();
+    /// office.insert("user_ip".to_string(), ContextValue::String("10.0.0.7".into()));
+    /// let results = client
+    ///     .check_resources_with_context(
+    ///         vec![
+    ///             (ObjectReference::new("document", "handbook")?, HashMap::new()),
+    ///             (ObjectReference::new("document", "payroll")?, office),
+    ///         ],
+    ///         "view",
+    ///         alice,
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn check_resources_with_context(
+        &self,
+        resources: Vec<(ObjectReference, HashMap<String, ContextValue>)>,
+        permission: impl Into<String>,
+        subject: &SubjectReference,
+    ) -> CheckResourcesRequest<'_> {
+        let permission = permission.into();
+        let items = resources
+            .into_iter()
+            .map(|(resource, context)| {
+                let item = BulkCheckItem::new(resource, permission.clone(), subject.clone());
+                if context.is_empty() {
+                    item
+                } else {
+                    item.with_context(context)
+                }
+            })
+            .collect();
+        CheckResourcesRequest {
+            bulk: self.bulk_check_permissions(items),
+            max_in_flight: 1,
+        }
+    }
+
+    /// Returns `true` if every item passes, stopping at the first that
+    /// does not.
+    ///
+    /// Items are checked with [`Client::bulk_check_permissions`], in
+    /// batches of at most [`limits::MAX_BULK_CHECK_ITEMS`] sent one after
+    /// another; no further batch is sent once an item has failed. Results
+    /// are considered in item order, and a per-item error met before a
+    /// failing item is returned as `Err`. `Conditional` results fail unless
+    /// [`conditional_policy`](CombinedCheckRequest::conditional_policy) is
+    /// [`ConditionalPolicy::TreatAsAllowed`]. An empty `items` is an
+    /// [`Error::InvalidArgument`] rather than a vacuous pass.
+    ///
+    /// ```rust,no_run
+    /// # use prescience::{Client, ObjectReference, SubjectReference};
+    /// # use prescience::BulkCheckItem;
+    /// # async fn example(client: &Client, doc: ObjectReference, folder: ObjectReference,
+    /// #     alice: SubjectReference) -> Result<(), prescience::Error> {
+    /// let allowed = client
+    ///     .check_all(vec![
+    ///         BulkCheckItem::new(doc, "edit", alice.clone()),
+    ///         BulkCheckItem::new(folder, "view", alice),
+    ///     ])
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn check_all(&self, items: Vec<BulkCheckItem>) -> CombinedCheckRequest<'_> {
+        CombinedCheckRequest {
+            bulk: self.bulk_check_permissions(items),
+            require_all: true,
+            conditional_policy: ConditionalPolicy::TreatAsDenied,
+        }
+    }
+
+    /// Returns `true` if at least one item passes, stopping at the first
+    /// that does.
+    ///
+    /// The counterpart of [`Client::check_all`], with the same batching,
+    /// ordering, and `Conditional` handling, and the same error for an empty
+    /// `items`.
+    pub fn check_any(&self, items: Vec<BulkCheckItem>) -> CombinedCheckRequest<'_> {
+        CombinedCheckRequest {
+            bulk: self.bulk_check_permissions(items),
+            require_all: false,
+            conditional_policy: ConditionalPolicy::TreatAsDenied,
         }
     }
 
     /// Bulk imports relationships via client-streaming.
     ///
-    /// Accepts any `Stream<Item = Relationship>`. Returns the number of
-    /// relationships loaded.
+    /// Accepts any `Stream<Item = Relationship>`. Resolves to an
+    /// [`ImportSummary`] with the number of relationships loaded. By
+    /// default any relationship that already exists fails the import; see
+    /// [`conflict_strategy`](BulkImportRelationshipsRequest::conflict_strategy).
     pub fn bulk_import_relationships<S>(&self, stream: S) -> BulkImportRelationshipsRequest<'_, S>
     where
         S: Stream<Item = Relationship> + Send + 'static,
@@ -261,6 +1387,7 @@ impl Client {
         BulkImportRelationshipsRequest {
             client: self,
             stream,
+            conflict_strategy: ImportConflictStrategy::default(),
         }
     }
 
@@ -274,7 +1401,161 @@ impl Client {
         BulkExportRelationshipsRequest {
             client: self,
             filter: Some((&filter).into()),
-            consistency: None,
+            consistency: self.default_consistency.get(RpcKind::Read),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(doc: &str) -> proto::CheckBulkPermissionsRequestItem {
+        proto::CheckBulkPermissionsRequestItem {
+            resource: Some((&ObjectReference::new("document", doc).unwrap()).into()),
+            permission: "view".into(),
+            subject: Some(
+                (&SubjectReference::new(
+                    ObjectReference::new("user", "alice").unwrap(),
+                    None::<String>,
+                )
+                .unwrap())
+                    .into(),
+            ),
+            context: None,
         }
     }
+
+    fn pair(
+        request: proto::CheckBulkPermissionsRequestItem,
+        permissionship: proto::check_permission_response::Permissionship,
+    ) -> proto::CheckBulkPermissionsPair {
+        proto::CheckBulkPermissionsPair {
+            request: Some(request),
+            response: Some(proto::check_bulk_permissions_pair::Response::Item(
+                proto::CheckBulkPermissionsResponseItem {
+                    permissionship: permissionship as i32,
+                    partial_caveat_info: None,
+                    debug_trace: None,
+                },
+            )),
+        }
+    }
+
+    #[test]
+    fn scrambled_pairs_realigned_to_input_order() {
+        use proto::check_permission_response::Permissionship;
+        let items = vec![item("a"), item("b"), item("c")];
+        let pairs = vec![
+            pair(item("c"), Permissionship::NoPermission),
+            pair(item("a"), Permissionship::HasPermission),
+            pair(item("b"), Permissionship::NoPermission),
+        ];
+
+        let results = align_bulk_pairs(&items, pairs).unwrap();
+        assert_eq!(results[0].as_ref().unwrap(), &PermissionResult::Allowed);
+        assert_eq!(results[1].as_ref().unwrap(), &PermissionResult::Denied);
+        assert_eq!(results[2].as_ref().unwrap(), &PermissionResult::Denied);
+    }
+
+    #[test]
+    fn unmatched_pair_is_error() {
+        use proto::check_permission_response::Permissionship;
+        let items = vec![item("a")];
+        let pairs = vec![pair(item("z"), Permissionship::HasPermission)];
+        assert!(matches!(
+            align_bulk_pairs(&items, pairs),
+            Err(Error::Serialization(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn run_bounded_limits_concurrency_and_keeps_order() {
+        use std::cell::Cell;
+
+        let running = Cell::new(0usize);
+        let peak = Cell::new(0usize);
+        let futures = (0..7)
+            .map(|i| {
+                let (running, peak) = (&running, &peak);
+                async move {
+                    running.set(running.get() + 1);
+                    peak.set(peak.get().max(running.get()));
+                    // Finish out of order: later futures yield fewer times.
+                    for _ in 0..(7 - i) {
+                        tokio::task::yield_now().await;
+                    }
+                    running.set(running.get() - 1);
+                    Ok::<_, Error>(i)
+                }
+            })
+            .collect();
+
+        let outputs = run_bounded(futures, 3).await.unwrap();
+        assert_eq!(outputs, (0..7).collect::<Vec<_>>());
+        assert_eq!(peak.get(), 3);
+    }
+
+    #[tokio::test]
+    async fn run_bounded_returns_first_error() {
+        let futures = (0..3)
+            .map(|i| async move {
+                if i == 1 {
+                    Err(Error::InvalidArgument("batch 1".into()))
+                } else {
+                    Ok(i)
+                }
+            })
+            .collect();
+        assert!(matches!(
+            run_bounded(futures, 1).await,
+            Err(Error::InvalidArgument(m)) if m == "batch 1"
+        ));
+    }
+
+    #[test]
+    fn import_summary_reads_as_num_loaded() {
+        let mut summary = ImportSummary {
+            num_loaded: 3,
+            batches: 1,
+            skipped: 0,
+        };
+        summary += ImportSummary {
+            num_loaded: 1,
+            batches: 1,
+            skipped: 2,
+        };
+        assert_eq!(summary, 4);
+        assert_eq!(*summary + 1, 5);
+        assert_eq!(u64::from(summary), 4);
+        assert_eq!((summary.batches, summary.skipped), (2, 2));
+    }
+
+    #[tokio::test]
+    async fn import_requests_batch_and_count_messages() {
+        let rels = (0..5).map(|i| {
+            Relationship::new(
+                ObjectReference::new("document", format!("doc-{}", i)).unwrap(),
+                "viewer",
+                SubjectReference::new(
+                    ObjectReference::new("user", "alice").unwrap(),
+                    None::<String>,
+                )
+                .unwrap(),
+            )
+        });
+        let sent = Arc::new(AtomicU64::new(0));
+        let requests = ImportRequests {
+            stream: Box::pin(tokio_stream::iter(rels)),
+            batch: Vec::new(),
+            batch_size: 2,
+            type_prefix: TypePrefix::new("billing"),
+            sent: sent.clone(),
+            done: false,
+        };
+
+        let sizes: Vec<_> = requests.map(|r| r.relationships.len()).collect().await;
+        assert_eq!(sizes, [2, 2, 1]);
+        assert_eq!(sent.load(Ordering::Relaxed), 3);
+    }
 }
@@ -0,0 +1,203 @@
+//! Connection lifecycle events (see [`ClientBuilder::on_connection_event`]).
+//!
+//! tonic's `Channel` reconnects on its own and does not expose whether it
+//! is currently connected, so the state tracked here is inferred from RPC
+//! outcomes: a call failing with `UNAVAILABLE` marks the connection lost,
+//! and the next call that reaches the server marks it restored. Errors a
+//! streaming RPC yields after it started count as outcomes too.
+//!
+//! [`ClientBuilder::on_connection_event`]: super::ClientBuilder::on_connection_event
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use tokio_stream::{Stream, StreamExt};
+
+use super::redact_userinfo;
+
+/// A change in the client's connection to SpiceDB, as reported to
+/// [`ClientBuilder::on_connection_event`](crate::client::ClientBuilder::on_connection_event).
+///
+/// Events are inferred from RPC outcomes rather than observed on the socket,
+/// so a connection that drops while idle is only reported as
+/// [`Disconnected`](Self::Disconnected) when the next call fails, and a
+/// server that answers `UNAVAILABLE` itself (e.g. while shutting down) is
+/// reported the same way as a dropped connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ConnectionEvent {
+    /// [`ClientBuilder::build`](crate::client::ClientBuilder::build) established the
+    /// initial connection.
+    Connected,
+    /// An RPC or its response stream failed with `UNAVAILABLE`; the
+    /// channel will reconnect on the next call.
+    Disconnected,
+    /// An RPC reached the server after a [`Disconnected`](Self::Disconnected)
+    /// event.
+    Reconnected,
+}
+
+/// A callback registered with `ClientBuilder::on_connection_event`.
+pub(crate) type ConnectionCallback = Arc<dyn Fn(ConnectionEvent) + Send + Sync>;
+
+/// Tracks whether the last RPC reached the server, shared by all clones of
+/// a client.
+#[derive(Default)]
+pub(crate) struct ConnectionMonitor {
+    disconnected: AtomicBool,
+    callback: Option<ConnectionCallback>,
+}
+
+impl fmt::Debug for ConnectionMonitor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConnectionMonitor")
+            .field("disconnected", &self.disconnected)
+            .field("callback", &self.callback.is_some())
+            .finish()
+    }
+}
+
+impl ConnectionMonitor {
+    pub(crate) fn new(callback: Option<ConnectionCallback>) -> Self {
+        Self {
+            disconnected: AtomicBool::new(false),
+            callback,
+        }
+    }
+
+    /// Reports the initial connection made by the builder.
+    pub(crate) fn connected(&self, endpoint: Option<&str>) {
+        let endpoint = endpoint.map(redact_userinfo);
+        tracing::debug!(endpoint = endpoint.as_deref(), "connected to SpiceDB");
+        self.emit(ConnectionEvent::Connected);
+    }
+
+    /// Returns `true` unless the last RPC failed with `UNAVAILABLE`.
+    pub(crate) fn is_connected(&self) -> bool {
+        !self.disconnected.load(Ordering::Acquire)
+    }
+
+    /// Updates the state from the outcome of an RPC, logging and reporting
+    /// a transition if there was one.
+    pub(crate) fn record<T>(&self, result: &Result<T, tonic::Status>, endpoint: Option<&str>) {
+        let lost = matches!(result, Err(status) if status.code() == tonic::Code::Unavailable);
+        self.update(lost, endpoint);
+    }
+
+    fn update(&self, lost: bool, endpoint: Option<&str>) {
+        if self.disconnected.swap(lost, Ordering::AcqRel) == lost {
+            return;
+        }
+        let endpoint = endpoint.map(redact_userinfo);
+        let endpoint = endpoint.as_deref();
+        if lost {
+            tracing::warn!(endpoint, "SpiceDB connection lost");
+            self.emit(ConnectionEvent::Disconnected);
+        } else {
+            tracing::info!(endpoint, "SpiceDB connection restored");
+            self.emit(ConnectionEvent::Reconnected);
+        }
+    }
+
+    fn emit(&self, event: ConnectionEvent) {
+        if let Some(callback) = &self.callback {
+            callback(event);
+        }
+    }
+}
+
+/// Reports the errors a streaming RPC yields mid-stream to the client's
+/// [`ConnectionMonitor`], which only sees the outcome of starting the call.
+#[derive(Debug, Clone)]
+pub(crate) struct StreamMonitor {
+    connection: Arc<ConnectionMonitor>,
+    endpoint: Option<String>,
+}
+
+impl StreamMonitor {
+    pub(crate) fn new(connection: Arc<ConnectionMonitor>, endpoint: Option<String>) -> Self {
+        Self {
+            connection,
+            endpoint,
+        }
+    }
+
+    /// Records an error yielded by a response stream.
+    pub(crate) fn record(&self, status: &tonic::Status) {
+        self.connection.update(
+            status.code() == tonic::Code::Unavailable,
+            self.endpoint.as_deref(),
+        );
+    }
+
+    /// Passes `stream` through, recording each error it yields.
+    #[allow(clippy::result_large_err)] // passes tonic's stream items through
+    pub(crate) fn observe<S, T>(self, stream: S) -> impl Stream<Item = Result<T, tonic::Status>>
+    where
+        S: Stream<Item = Result<T, tonic::Status>>,
+    {
+        stream.map(move |item| {
+            if let Err(status) = &item {
+                self.record(status);
+            }
+            item
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    fn unavailable() -> tonic::Status {
+        tonic::Status::unavailable("connection refused")
+    }
+
+    #[test]
+    fn reports_only_transitions() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let monitor = ConnectionMonitor::new(Some(Arc::new(move |event| {
+            sink.lock().unwrap().push(event)
+        })));
+
+        monitor.connected(None);
+        monitor.record(&Ok(()), None);
+        monitor.record(&Err::<(), _>(unavailable()), None);
+        assert!(!monitor.is_connected());
+        monitor.record(&Err::<(), _>(unavailable()), None);
+        // A server-side error still proves the server was reached.
+        monitor.record(&Err::<(), _>(tonic::Status::not_found("x")), None);
+        assert!(monitor.is_connected());
+        monitor.record(&Ok(()), None);
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                ConnectionEvent::Connected,
+                ConnectionEvent::Disconnected,
+                ConnectionEvent::Reconnected,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn stream_errors_are_recorded() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let monitor = Arc::new(ConnectionMonitor::new(Some(Arc::new(move |event| {
+            sink.lock().unwrap().push(event)
+        }))));
+
+        let items = tokio_stream::iter(vec![Ok(1), Err(unavailable())]);
+        let stream = StreamMonitor::new(monitor.clone(), None).observe(items);
+        let results: Vec<_> = stream.collect().await;
+        assert_eq!(results.len(), 2);
+        assert!(!monitor.is_connected());
+
+        assert_eq!(*events.lock().unwrap(), [ConnectionEvent::Disconnected]);
+    }
+}
@@ -1,20 +1,34 @@
 //! PermissionsService RPC implementations.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use std::time::Duration;
 
 use futures_core::Stream;
 use tokio_stream::StreamExt;
+use tracing::Instrument;
 
 use crate::error::Error;
+use crate::limits;
 use crate::proto;
+use crate::schema::AllowedSubject;
 use crate::types::context::context_to_struct;
 use crate::types::*;
 
+use super::correlation::CorrelationId;
 use super::Client;
+use super::RpcKind;
 
 // ── CheckPermission ──────────────────────────────────────────────
 
 /// Builder for a CheckPermission request.
+///
+/// Like the other request builders, this is `Clone`: build a base request
+/// once and clone it to send variations. Each clone is consumed
+/// independently when awaited.
+#[derive(Clone)]
 pub struct CheckPermissionRequest<'a> {
     client: &'a Client,
     resource: proto::ObjectReference,
@@ -22,12 +36,28 @@ pub struct CheckPermissionRequest<'a> {
     subject: proto::SubjectReference,
     consistency: Option<proto::Consistency>,
     context: Option<prost_types::Struct>,
+    correlation_id: CorrelationId,
 }
 
 impl<'a> CheckPermissionRequest<'a> {
+    /// Tags the request with a correlation ID, sent as the `x-request-id`
+    /// header so SpiceDB's request logs can be matched to your traces.
+    ///
+    /// The ID is also recorded as the `correlation_id` field of a
+    /// `spicedb_request` debug span around the call. Sending fails with
+    /// `Err(InvalidArgument)` unless `id` is 1 to 128 printable ASCII
+    /// characters without spaces.
+    pub fn correlation_id(mut self, id: impl Into<String>) -> Self {
+        self.correlation_id = CorrelationId::new(id.into());
+        self
+    }
+
     /// Sets the consistency mode for this request.
-    pub fn consistency(mut self, c: Consistency) -> Self {
-        self.consistency = Some((&c).into());
+    ///
+    /// Pass `None` to send no preference, leaving the choice to the server
+    /// (equivalent to [`Consistency::MinimizeLatency`]).
+    pub fn consistency(mut self, c: impl Into<Option<Consistency>>) -> Self {
+        self.consistency = c.into().as_ref().map(Into::into);
         self
     }
 
@@ -36,6 +66,144 @@ impl<'a> CheckPermissionRequest<'a> {
         self.context = Some(context_to_struct(&ctx));
         self
     }
+
+    /// Sets the caveat evaluation context from a JSON object string, such as
+    /// one taken straight from an HTTP request body.
+    ///
+    /// Returns `Err(InvalidArgument)` if `json` is not valid JSON or is not
+    /// an object. The JSON is read by the same parser as caveat context in
+    /// [`Relationship::parse`], so this needs no `serde` feature.
+    ///
+    /// ```rust,no_run
+    /// # use prescience::{Client, ObjectReference, SubjectReference};
+    /// # async fn example(client: &Client) -> Result<(), prescience::Error> {
+    /// # let doc = ObjectReference::new("document", "doc-1")?;
+    /// # let user = SubjectReference::new(ObjectReference::new("user", "alice")?, None::<String>)?;
+    /// let result = client
+    ///     .check_permission(&doc, "view", &user)
+    ///     .context_json(r#"{"user_ip": "10.0.0.7"}"#)?
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn context_json(self, json: &str) -> Result<Self, Error> {
+        let ctx = crate::types::context::parse_context_json(json)?;
+        Ok(self.context(ctx))
+    }
+
+    /// Sets the caveat evaluation context after validating it against `caveat`.
+    ///
+    /// Returns `Err(InvalidArgument)` without sending anything if the context
+    /// has an undeclared parameter or a value of the wrong type. Parameters
+    /// it leaves out are allowed, since the relationship may supply them;
+    /// see [`CaveatDefinition::validate_context`]. Use this to fail fast
+    /// instead of discovering the mistake through a `Conditional` result.
+    pub fn context_checked(
+        self,
+        ctx: HashMap<String, ContextValue>,
+        caveat: &CaveatDefinition,
+    ) -> Result<Self, Error> {
+        caveat.validate_context(&ctx)?;
+        Ok(self.context(ctx))
+    }
+}
+
+impl<'a> CheckPermissionRequest<'a> {
+    /// Sends the request, returning the result and the token it was checked at.
+    ///
+    /// The token is returned for every result, including
+    /// [`PermissionResult::Conditional`]: after gathering the missing context,
+    /// re-check at [`Consistency::AtExactSnapshot`] of this token to resolve
+    /// the caveat against the same data, unaffected by writes in between.
+    ///
+    /// ```rust,no_run
+    /// # use std::collections::HashMap;
+    /// # use prescience::{Client, Consistency, ObjectReference, PermissionResult, SubjectReference};
+    /// # async fn example(client: &Client, doc: &ObjectReference, alice: &SubjectReference)
+    /// # -> Result<(), prescience::Error> {
+    /// let (result, checked_at) = client.check_permission(doc, "view", alice).send_with_token().await?;
+    /// if let PermissionResult::Conditional { missing_fields } = result {
+    ///     let context = HashMap::new(); // gather `missing_fields` here
+    ///     let resolved = client
+    ///         .check_permission(doc, "view", alice)
+    ///         .context(context)
+    ///         .consistency(Consistency::AtExactSnapshot(checked_at))
+    ///         .await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn send_with_token(self) -> Result<(PermissionResult, ZedToken), Error> {
+        let inner = self.send_proto(false).await?;
+        let checked_at = inner
+            .checked_at
+            .ok_or_else(|| Error::Serialization("missing checked_at token".into()))?
+            .try_into()?;
+        let result =
+            PermissionResult::from_check_response(inner.permissionship, inner.partial_caveat_info)?;
+        Ok((result, checked_at))
+    }
+
+    /// Sends the request with tracing enabled, returning the result and how
+    /// SpiceDB computed it.
+    ///
+    /// Tracing makes the check slower, so use it to diagnose an unexpected
+    /// result, not on the hot path. Print the trace
+    /// with [`DebugTrace::render`]:
+    ///
+    /// ```rust,no_run
+    /// # use prescience::{Client, ObjectReference, SubjectReference};
+    /// # async fn example(client: &Client, doc: &ObjectReference, alice: &SubjectReference)
+    /// # -> Result<(), prescience::Error> {
+    /// let (_, trace) = client.check_permission(doc, "view", alice).send_with_debug().await?;
+    /// println!("{}", trace.render());
+    /// // document:doc-1 view = ALLOWED via viewer -> user:alice
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Returns `Err(Serialization)` if the server sent no trace, as older
+    /// SpiceDB versions that ignore `with_tracing` do.
+    pub async fn send_with_debug(self) -> Result<(PermissionResult, DebugTrace), Error> {
+        let type_prefix = self.client.type_prefix.clone();
+        let inner = self.send_proto(true).await?;
+        let result =
+            PermissionResult::from_check_response(inner.permissionship, inner.partial_caveat_info)?;
+        let trace = inner
+            .debug_trace
+            .and_then(|debug| debug.check)
+            .ok_or_else(|| Error::Serialization("missing debug trace".into()))?;
+        Ok((result, DebugTrace::from_proto(type_prefix.strip(trace))?))
+    }
+
+    async fn send_proto(self, with_tracing: bool) -> Result<proto::CheckPermissionResponse, Error> {
+        self.correlation_id.validate()?;
+        let exact = exact_snapshot(self.consistency.as_ref());
+        let req = proto::CheckPermissionRequest {
+            consistency: self.consistency,
+            resource: Some(self.resource),
+            permission: self.permission,
+            subject: Some(self.subject),
+            context: self.context,
+            with_tracing,
+        };
+
+        let response = self
+            .client
+            .authenticated(
+                "PermissionsService.CheckPermission",
+                &self.client.permissions,
+                self.client.type_prefix.apply(req),
+                |mut s, r| {
+                    let request = self.correlation_id.request(r);
+                    async move { s.check_permission(request).await }
+                },
+            )
+            .instrument(self.correlation_id.span())
+            .await
+            .map_err(|status| Error::from_status(status).with_snapshot(exact.as_ref()))?;
+        Ok(response.into_inner())
+    }
 }
 
 impl<'a> std::future::IntoFuture for CheckPermissionRequest<'a> {
@@ -44,26 +212,58 @@ impl<'a> std::future::IntoFuture for CheckPermissionRequest<'a> {
         std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'a>>;
 
     fn into_future(self) -> Self::IntoFuture {
-        Box::pin(async move {
-            let req = proto::CheckPermissionRequest {
-                consistency: self.consistency,
-                resource: Some(self.resource),
-                permission: self.permission,
-                subject: Some(self.subject),
-                context: self.context,
-                with_tracing: false,
-            };
+        Box::pin(async move { self.send_with_token().await.map(|(result, _)| result) })
+    }
+}
 
-            let response = self
-                .client
-                .permissions
-                .clone()
-                .check_permission(req)
-                .await
-                .map_err(Error::from_status)?;
+/// Builder for [`Client::require_permission`]: a CheckPermission that fails
+/// unless the permission is granted.
+#[derive(Clone)]
+pub struct RequirePermissionRequest<'a> {
+    check: CheckPermissionRequest<'a>,
+    resource: ObjectReference,
+    subject: SubjectReference,
+}
 
-            let inner = response.into_inner();
-            PermissionResult::from_check_response(inner.permissionship, inner.partial_caveat_info)
+impl<'a> RequirePermissionRequest<'a> {
+    /// Sets the consistency mode, as for [`Client::check_permission`].
+    pub fn consistency(mut self, c: impl Into<Option<Consistency>>) -> Self {
+        self.check = self.check.consistency(c);
+        self
+    }
+
+    /// Sets the caveat evaluation context.
+    pub fn context(mut self, ctx: HashMap<String, ContextValue>) -> Self {
+        self.check = self.check.context(ctx);
+        self
+    }
+
+    /// Tags the check with a correlation ID, as for
+    /// [`CheckPermissionRequest::correlation_id`].
+    pub fn correlation_id(mut self, id: impl Into<String>) -> Self {
+        self.check = self.check.correlation_id(id);
+        self
+    }
+}
+
+impl<'a> std::future::IntoFuture for RequirePermissionRequest<'a> {
+    type Output = Result<ZedToken, Error>;
+    type IntoFuture =
+        std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            let permission = self.check.permission.clone();
+            let (result, checked_at) = self.check.send_with_token().await?;
+            if result.is_allowed()? {
+                Ok(checked_at)
+            } else {
+                Err(Error::PermissionDenied {
+                    resource: Box::new(self.resource),
+                    permission,
+                    subject: Box::new(self.subject),
+                })
+            }
         })
     }
 }
@@ -71,18 +271,336 @@ impl<'a> std::future::IntoFuture for CheckPermissionRequest<'a> {
 // ── WriteRelationships ──────────────────────────────────────────
 
 /// Builder for a WriteRelationships request.
+#[derive(Clone)]
 pub struct WriteRelationshipsRequest<'a> {
     client: &'a Client,
-    updates: Vec<proto::RelationshipUpdate>,
-    preconditions: Vec<proto::Precondition>,
+    updates: Vec<RelationshipUpdate>,
+    preconditions: Vec<Precondition>,
+    metadata: Option<prost_types::Struct>,
+    reject_self_reference: bool,
+    correlation_id: CorrelationId,
 }
 
 impl<'a> WriteRelationshipsRequest<'a> {
-    /// Adds preconditions that must be satisfied before the write commits.
+    /// Tags the request with a correlation ID, as for
+    /// [`CheckPermissionRequest::correlation_id`].
+    pub fn correlation_id(mut self, id: impl Into<String>) -> Self {
+        self.correlation_id = CorrelationId::new(id.into());
+        self
+    }
+
+    /// Sets the preconditions that must be satisfied before the write commits,
+    /// replacing any previously added.
+    ///
+    /// If one is not met, the error's
+    /// [`failed_precondition`](crate::error::SpiceDbErrorDetails::failed_precondition)
+    /// gives its index in `preconditions` when it can be identified.
     pub fn preconditions(mut self, preconditions: Vec<Precondition>) -> Self {
-        self.preconditions = preconditions.iter().map(Into::into).collect();
+        self.preconditions = preconditions;
+        self
+    }
+
+    /// Appends a single precondition that must be satisfied before the write commits.
+    pub fn require(mut self, precondition: Precondition) -> Self {
+        self.preconditions.push(precondition);
         self
     }
+
+    /// Appends several preconditions that must be satisfied before the write commits.
+    pub fn require_all(mut self, preconditions: impl IntoIterator<Item = Precondition>) -> Self {
+        self.preconditions.extend(preconditions);
+        self
+    }
+
+    /// Attaches transaction metadata to the write, such as who made the
+    /// change and why.
+    ///
+    /// SpiceDB stores it with the transaction and returns it on the watch
+    /// events for the change (`WatchEvent::metadata`, with the `watch`
+    /// feature), so watchers can attribute changes.
+    pub fn metadata(mut self, metadata: HashMap<String, ContextValue>) -> Self {
+        self.metadata = Some(context_to_struct(&metadata));
+        self
+    }
+
+    /// Rejects the write with `Err(InvalidArgument)`, without sending it,
+    /// if a create or touch relates an object to itself, such as
+    /// `document:1#viewer@document:1`.
+    ///
+    /// SpiceDB accepts self-references and some schemas rely on them, so
+    /// this is off by default. The subject relation is ignored, so
+    /// `group:1#admin@group:1#member` is rejected too. Deletes are always
+    /// allowed, so existing self-references can be cleaned up.
+    pub fn reject_self_reference(mut self, enabled: bool) -> Self {
+        self.reject_self_reference = enabled;
+        self
+    }
+
+    /// Checks whether the write would succeed, without writing anything,
+    /// and returns the problems found (empty if none).
+    ///
+    /// This is a best-effort, client-side validation, not a transactional
+    /// dry run: SpiceDB has none. It runs the same local checks as sending
+    /// (returning their `Err(InvalidArgument)` as sending would), then
+    /// reads the schema and, for each update, checks that the resource type
+    /// and relation exist and that the relation allows the subject's type,
+    /// subject relation, wildcard, and caveat. Finally it reads, fully
+    /// consistently, whether each create's relationship already exists and
+    /// whether each precondition holds. Caveat parameters and other
+    /// server-side rules are not checked, and the data can change before
+    /// the real write, so a clean dry run does not guarantee success.
+    ///
+    /// With the `experimental` feature the schema comes from
+    /// `ExperimentalReflectSchema`. Without it, or if the server has the
+    /// experimental API disabled, the dry run falls back to the
+    /// [`read_schema`](Client::read_schema) text read by the shallow
+    /// [`schema::parse`](crate::schema::parse), and fails with its
+    /// `Err(InvalidArgument)` if that cannot read the schema.
+    ///
+    /// Each create and precondition costs one read, so a dry run of a large
+    /// batch is much slower than the write itself.
+    ///
+    /// ```rust,no_run
+    /// # use prescience::{Client, Relationship, RelationshipUpdate};
+    /// # async fn example(client: &Client) -> Result<(), prescience::Error> {
+    /// let updates = vec![RelationshipUpdate::create(Relationship::parse(
+    ///     "document:1#viewer@user:alice",
+    /// )?)];
+    /// for problem in client.write_relationships(updates).dry_run().await? {
+    ///     eprintln!("{problem}");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn dry_run(self) -> Result<Vec<WriteProblem>, Error> {
+        let updates = self.proto_updates();
+        self.validate(&updates)?;
+
+        let schema = self.write_schema().await?;
+
+        let mut problems = Vec::new();
+        for (index, update) in updates.iter().enumerate() {
+            let Some(relationship) = &update.relationship else {
+                continue;
+            };
+            let prefixed = self.client.type_prefix.apply(relationship.clone());
+            if let Some(problem) = schema_problem(&schema, index, relationship, &prefixed) {
+                problems.push(problem);
+                continue;
+            }
+            if update.operation == proto::relationship_update::Operation::Create as i32 {
+                let relationship = Relationship::try_from(relationship.clone())?;
+                let filter = (&RelationshipFilter::matching(&relationship)).into();
+                if self.exists_now(filter).await? {
+                    problems.push(WriteProblem::AlreadyExists { update: index });
+                }
+            }
+        }
+
+        for (index, precondition) in self.preconditions.iter().enumerate() {
+            let must_exist = precondition.operation == PreconditionOp::MustExist;
+            let exists = self.exists_now((&precondition.filter).into()).await?;
+            if exists != must_exist {
+                problems.push(WriteProblem::PreconditionFailed {
+                    precondition: index,
+                });
+            }
+        }
+        Ok(problems)
+    }
+
+    /// Reads the relations a dry run checks updates against.
+    async fn write_schema(&self) -> Result<WriteSchema, Error> {
+        #[cfg(feature = "experimental")]
+        {
+            match self.client.reflect_schema(vec![]).await {
+                Ok(reflected) => return Ok(WriteSchema::from_reflected(reflected.definitions)),
+                Err(Error::Unsupported { .. }) => {}
+                Err(e) if e.code() == Some(tonic::Code::NotFound) => {
+                    return Ok(WriteSchema::default())
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        match self.client.read_schema().await {
+            Ok((text, _)) => Ok(WriteSchema::from_parsed(crate::schema::parse(&text)?)),
+            Err(e) if e.code() == Some(tonic::Code::NotFound) => Ok(WriteSchema::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Whether any relationship matches `filter` at the latest revision.
+    async fn exists_now(&self, filter: proto::RelationshipFilter) -> Result<bool, Error> {
+        let mut read = self.client.read_relationships(RelationshipFilter::new(""));
+        read.filter = filter;
+        read.limit = 1;
+        RelationshipExistsRequest { read }
+            .consistency(Consistency::FullyConsistent)
+            .await
+    }
+
+    /// The updates as sent, before the client's type prefix is applied.
+    fn proto_updates(&self) -> Vec<proto::RelationshipUpdate> {
+        self.updates.iter().map(Into::into).collect()
+    }
+
+    /// The checks made before sending, which need no server round trip.
+    fn validate(&self, updates: &[proto::RelationshipUpdate]) -> Result<(), Error> {
+        if updates.is_empty() {
+            return Err(Error::InvalidArgument("updates must not be empty".into()));
+        }
+        if updates.len() > limits::MAX_WRITE_UPDATES {
+            return Err(Error::InvalidArgument(format!(
+                "{} updates exceeds the maximum of {} per write; \
+                 use write_relationships_stream to write in chunks",
+                updates.len(),
+                limits::MAX_WRITE_UPDATES
+            )));
+        }
+        check_duplicate_updates(updates)?;
+        if self.reject_self_reference {
+            check_self_references(updates)?;
+        }
+        check_precondition_count(self.preconditions.len())?;
+        self.correlation_id.validate()
+    }
+
+    /// Attributes a failed write to one of its updates and preconditions.
+    ///
+    /// Only called on the error path: the request sent is gone by then, so
+    /// the updates and preconditions are converted again, with the type
+    /// prefix the server saw.
+    fn attribute(&self, err: Error) -> Error {
+        let prefix = &self.client.type_prefix;
+        let updates = prefix.apply(self.proto_updates());
+        let preconditions: Vec<proto::Precondition> =
+            prefix.apply(self.preconditions.iter().map(Into::into).collect());
+        err.with_failed_update(&updates)
+            .with_failed_precondition(&preconditions)
+    }
+}
+
+/// The relations [`WriteRelationshipsRequest::dry_run`] checks updates
+/// against: for each definition, the subjects each of its relations allows.
+#[derive(Debug, Default)]
+struct WriteSchema {
+    definitions: HashMap<String, HashMap<String, Vec<AllowedSubject>>>,
+}
+
+impl WriteSchema {
+    fn from_parsed(schema: crate::schema::ParsedSchema) -> Self {
+        let definitions = schema
+            .definitions
+            .into_iter()
+            .map(|definition| {
+                let relations = definition
+                    .relations
+                    .into_iter()
+                    .map(|relation| (relation.name, relation.allowed))
+                    .collect();
+                (definition.name, relations)
+            })
+            .collect();
+        Self { definitions }
+    }
+
+    #[cfg(feature = "experimental")]
+    fn from_reflected(definitions: Vec<proto::ExpDefinition>) -> Self {
+        use proto::exp_type_reference::Typeref;
+
+        let definitions = definitions
+            .into_iter()
+            .map(|definition| {
+                let relations = definition
+                    .relations
+                    .into_iter()
+                    .map(|relation| {
+                        let allowed = relation
+                            .subject_types
+                            .into_iter()
+                            .map(|t| AllowedSubject {
+                                wildcard: t.typeref == Some(Typeref::IsPublicWildcard(true)),
+                                relation: match t.typeref {
+                                    Some(Typeref::OptionalRelationName(r)) if !r.is_empty() => {
+                                        Some(r)
+                                    }
+                                    _ => None,
+                                },
+                                caveat: Some(t.optional_caveat_name).filter(|c| !c.is_empty()),
+                                object_type: t.subject_definition_name,
+                            })
+                            .collect();
+                        (relation.name, allowed)
+                    })
+                    .collect();
+                (definition.name, relations)
+            })
+            .collect();
+        Self { definitions }
+    }
+}
+
+/// Checks `relationship` (as passed, and `prefixed` with the client's type
+/// prefix) against `schema`, returning the first problem found.
+fn schema_problem(
+    schema: &WriteSchema,
+    update: usize,
+    relationship: &proto::Relationship,
+    prefixed: &proto::Relationship,
+) -> Option<WriteProblem> {
+    let resource = relationship.resource.clone().unwrap_or_default();
+    let Some(relations) = prefixed
+        .resource
+        .as_ref()
+        .and_then(|r| schema.definitions.get(&r.object_type))
+    else {
+        return Some(WriteProblem::UnknownType {
+            update,
+            object_type: resource.object_type,
+        });
+    };
+    let Some(allowed) = relations.get(&relationship.relation) else {
+        return Some(WriteProblem::UnknownRelation {
+            update,
+            object_type: resource.object_type,
+            relation: relationship.relation.clone(),
+        });
+    };
+
+    let caveat = relationship
+        .optional_caveat
+        .as_ref()
+        .map(|c| c.caveat_name.as_str());
+    let subject = prefixed.subject.clone().unwrap_or_default();
+    let object = subject.object.clone().unwrap_or_default();
+    let is_allowed = allowed.iter().any(|entry| {
+        entry.object_type == object.object_type
+            && entry.wildcard == (object.object_id == "*")
+            && entry.relation.as_deref().unwrap_or_default() == subject.optional_relation
+            && entry.caveat.as_deref() == caveat
+    });
+    if is_allowed {
+        return None;
+    }
+
+    // Spell the subject as the schema would, without the type prefix.
+    let subject = relationship.subject.clone().unwrap_or_default();
+    let object = subject.object.unwrap_or_default();
+    let mut subject_type = if object.object_id == "*" {
+        format!("{}:*", object.object_type)
+    } else if subject.optional_relation.is_empty() {
+        object.object_type
+    } else {
+        format!("{}#{}", object.object_type, subject.optional_relation)
+    };
+    if let Some(name) = caveat {
+        subject_type = format!("{} with {}", subject_type, name);
+    }
+    Some(WriteProblem::SubjectNotAllowed {
+        update,
+        relation: relationship.relation.clone(),
+        subject_type,
+    })
 }
 
 impl<'a> std::future::IntoFuture for WriteRelationshipsRequest<'a> {
@@ -90,25 +608,34 @@ impl<'a> std::future::IntoFuture for WriteRelationshipsRequest<'a> {
     type IntoFuture =
         std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'a>>;
 
-    fn into_future(self) -> Self::IntoFuture {
+    fn into_future(mut self) -> Self::IntoFuture {
         Box::pin(async move {
-            if self.updates.is_empty() {
-                return Err(Error::InvalidArgument("updates must not be empty".into()));
-            }
+            let updates = self.proto_updates();
+            self.validate(&updates)?;
 
-            let req = proto::WriteRelationshipsRequest {
-                updates: self.updates,
-                optional_preconditions: self.preconditions,
-                optional_transaction_metadata: None,
-            };
+            let req = self
+                .client
+                .type_prefix
+                .apply(proto::WriteRelationshipsRequest {
+                    updates,
+                    optional_preconditions: self.preconditions.iter().map(Into::into).collect(),
+                    optional_transaction_metadata: self.metadata.take(),
+                });
 
             let response = self
                 .client
-                .permissions
-                .clone()
-                .write_relationships(req)
+                .authenticated(
+                    "PermissionsService.WriteRelationships",
+                    &self.client.permissions,
+                    req,
+                    |mut s, r| {
+                        let request = self.correlation_id.request(r);
+                        async move { s.write_relationships(request).await }
+                    },
+                )
+                .instrument(self.correlation_id.span())
                 .await
-                .map_err(Error::from_status)?;
+                .map_err(|status| self.attribute(Error::from_status(status)))?;
 
             let inner = response.into_inner();
             inner
@@ -119,21 +646,133 @@ impl<'a> std::future::IntoFuture for WriteRelationshipsRequest<'a> {
     }
 }
 
+/// Rejects a batch that updates the same relationship more than once, which
+/// SpiceDB refuses with a less specific error.
+fn check_duplicate_updates(updates: &[proto::RelationshipUpdate]) -> Result<(), Error> {
+    fn operation(update: &proto::RelationshipUpdate) -> &'static str {
+        use proto::relationship_update::Operation;
+        match Operation::try_from(update.operation) {
+            Ok(Operation::Create) => "create",
+            Ok(Operation::Touch) => "touch",
+            Ok(Operation::Delete) => "delete",
+            _ => "unspecified",
+        }
+    }
+
+    let mut seen = HashMap::with_capacity(updates.len());
+    for (index, update) in updates.iter().enumerate() {
+        let Some(relationship) = &update.relationship else {
+            continue;
+        };
+        let tuple = crate::error::tuple_string(relationship);
+        if let Some(first) = seen.insert(tuple.clone(), index) {
+            return Err(Error::InvalidArgument(format!(
+                "updates {} ({}) and {} ({}) both target {}; \
+                 a write may update each relationship only once",
+                first,
+                operation(&updates[first]),
+                index,
+                operation(update),
+                tuple
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn check_self_references(updates: &[proto::RelationshipUpdate]) -> Result<(), Error> {
+    use proto::relationship_update::Operation;
+
+    for (index, update) in updates.iter().enumerate() {
+        if update.operation == Operation::Delete as i32 {
+            continue;
+        }
+        let Some(relationship) = &update.relationship else {
+            continue;
+        };
+        let subject = relationship
+            .subject
+            .as_ref()
+            .and_then(|s| s.object.as_ref());
+        if relationship.resource.is_some() && relationship.resource.as_ref() == subject {
+            return Err(Error::InvalidArgument(format!(
+                "update {} relates {} to itself: {}",
+                index,
+                subject
+                    .map(|o| format!("{}:{}", o.object_type, o.object_id))
+                    .unwrap_or_default(),
+                crate::error::tuple_string(relationship)
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn check_precondition_count(count: usize) -> Result<(), Error> {
+    if count > limits::MAX_PRECONDITIONS {
+        return Err(Error::InvalidArgument(format!(
+            "{} preconditions exceeds the maximum of {} per request",
+            count,
+            limits::MAX_PRECONDITIONS
+        )));
+    }
+    Ok(())
+}
+
 // ── DeleteRelationships ──────────────────────────────────────────
 
 /// Builder for a DeleteRelationships request.
+#[derive(Clone)]
 pub struct DeleteRelationshipsRequest<'a> {
     client: &'a Client,
     filter: proto::RelationshipFilter,
     preconditions: Vec<proto::Precondition>,
+    metadata: Option<prost_types::Struct>,
+    correlation_id: CorrelationId,
 }
 
 impl<'a> DeleteRelationshipsRequest<'a> {
-    /// Adds preconditions that must be satisfied before the delete commits.
+    /// Tags the request with a correlation ID, as for
+    /// [`CheckPermissionRequest::correlation_id`].
+    pub fn correlation_id(mut self, id: impl Into<String>) -> Self {
+        self.correlation_id = CorrelationId::new(id.into());
+        self
+    }
+
+    /// Sets the preconditions that must be satisfied before the delete commits,
+    /// replacing any previously added.
+    ///
+    /// If one is not met, the error's
+    /// [`failed_precondition`](crate::error::SpiceDbErrorDetails::failed_precondition)
+    /// gives its index in `preconditions` when it can be identified.
     pub fn preconditions(mut self, preconditions: Vec<Precondition>) -> Self {
         self.preconditions = preconditions.iter().map(Into::into).collect();
         self
     }
+
+    /// Appends a single precondition that must be satisfied before the delete commits.
+    pub fn require(mut self, precondition: Precondition) -> Self {
+        self.preconditions.push((&precondition).into());
+        self
+    }
+
+    /// Appends several preconditions that must be satisfied before the delete commits.
+    pub fn require_all(mut self, preconditions: impl IntoIterator<Item = Precondition>) -> Self {
+        self.preconditions
+            .extend(preconditions.into_iter().map(|p| (&p).into()));
+        self
+    }
+
+    /// Attaches transaction metadata to the delete, such as who made the
+    /// change and why.
+    ///
+    /// SpiceDB stores it with the transaction and returns it on the watch
+    /// events for the change (`WatchEvent::metadata`, with the `watch`
+    /// feature), so watchers can attribute changes.
+    pub fn metadata(mut self, metadata: HashMap<String, ContextValue>) -> Self {
+        self.metadata = Some(context_to_struct(&metadata));
+        self
+    }
 }
 
 impl<'a> std::future::IntoFuture for DeleteRelationshipsRequest<'a> {
@@ -143,21 +782,37 @@ impl<'a> std::future::IntoFuture for DeleteRelationshipsRequest<'a> {
 
     fn into_future(self) -> Self::IntoFuture {
         Box::pin(async move {
-            let req = proto::DeleteRelationshipsRequest {
-                relationship_filter: Some(self.filter),
-                optional_preconditions: self.preconditions,
-                optional_limit: 0,
-                optional_allow_partial_deletions: false,
-                optional_transaction_metadata: None,
-            };
+            check_precondition_count(self.preconditions.len())?;
+            self.correlation_id.validate()?;
+
+            let req = self
+                .client
+                .type_prefix
+                .apply(proto::DeleteRelationshipsRequest {
+                    relationship_filter: Some(self.filter),
+                    optional_preconditions: self.preconditions,
+                    optional_limit: 0,
+                    optional_allow_partial_deletions: false,
+                    optional_transaction_metadata: self.metadata,
+                });
+            let preconditions = req.optional_preconditions.clone();
 
             let response = self
                 .client
-                .permissions
-                .clone()
-                .delete_relationships(req)
+                .authenticated(
+                    "PermissionsService.DeleteRelationships",
+                    &self.client.permissions,
+                    req,
+                    |mut s, r| {
+                        let request = self.correlation_id.request(r);
+                        async move { s.delete_relationships(request).await }
+                    },
+                )
+                .instrument(self.correlation_id.span())
                 .await
-                .map_err(Error::from_status)?;
+                .map_err(|status| {
+                    Error::from_status(status).with_failed_precondition(&preconditions)
+                })?;
 
             let inner = response.into_inner();
             inner
@@ -171,6 +826,7 @@ impl<'a> std::future::IntoFuture for DeleteRelationshipsRequest<'a> {
 // ── LookupResources ──────────────────────────────────────────────
 
 /// Builder for a LookupResources streaming request.
+#[derive(Clone)]
 pub struct LookupResourcesRequest<'a> {
     client: &'a Client,
     resource_type: String,
@@ -178,12 +834,20 @@ pub struct LookupResourcesRequest<'a> {
     subject: proto::SubjectReference,
     consistency: Option<proto::Consistency>,
     context: Option<prost_types::Struct>,
+    correlation_id: CorrelationId,
 }
 
 impl<'a> LookupResourcesRequest<'a> {
-    /// Sets the consistency mode.
-    pub fn consistency(mut self, c: Consistency) -> Self {
-        self.consistency = Some((&c).into());
+    /// Tags the request with a correlation ID, as for
+    /// [`CheckPermissionRequest::correlation_id`].
+    pub fn correlation_id(mut self, id: impl Into<String>) -> Self {
+        self.correlation_id = CorrelationId::new(id.into());
+        self
+    }
+
+    /// Sets the consistency mode, or `None` for the server default.
+    pub fn consistency(mut self, c: impl Into<Option<Consistency>>) -> Self {
+        self.consistency = c.into().as_ref().map(Into::into);
         self
     }
 
@@ -197,6 +861,9 @@ impl<'a> LookupResourcesRequest<'a> {
     pub async fn send(
         self,
     ) -> Result<impl Stream<Item = Result<LookupResourceResult, Error>>, Error> {
+        self.correlation_id.validate()?;
+        let resource_type = self.resource_type.clone();
+        let exact = exact_snapshot(self.consistency.as_ref());
         let req = proto::LookupResourcesRequest {
             consistency: self.consistency,
             resource_object_type: self.resource_type,
@@ -209,22 +876,232 @@ impl<'a> LookupResourcesRequest<'a> {
 
         let response = self
             .client
-            .permissions
-            .clone()
-            .lookup_resources(req)
+            .authenticated(
+                "PermissionsService.LookupResources",
+                &self.client.permissions,
+                self.client.type_prefix.apply(req),
+                |mut s, r| {
+                    let request = self.correlation_id.request(r);
+                    async move { s.lookup_resources(request).await }
+                },
+            )
+            .instrument(self.correlation_id.span())
             .await
-            .map_err(Error::from_status)?;
+            .map_err(|status| Error::from_status(status).with_snapshot(exact.as_ref()))?;
 
-        Ok(response.into_inner().map(|r| match r {
-            Ok(proto) => LookupResourceResult::from_proto(proto),
-            Err(status) => Err(Error::from_status(status)),
+        let items = self.client.stream_monitor().observe(response.into_inner());
+        Ok(items.map(move |r| match r {
+            Ok(proto) => LookupResourceResult::from_proto(proto, resource_type.clone()),
+            Err(status) => Err(Error::from_status(status).with_snapshot(exact.as_ref())),
         }))
     }
 }
 
+// ── LookupResourcesForAny ──────────────────────────────────────────────
+
+/// How many lookups [`Client::lookup_resources_for_any`] runs at once by
+/// default.
+const DEFAULT_LOOKUPS_IN_FLIGHT: usize = 4;
+
+type LookupStream<'a> =
+    Pin<Box<dyn Stream<Item = Result<LookupResourceResult, Error>> + Send + 'a>>;
+
+/// Builder for the union of LookupResources across several subjects.
+#[derive(Clone)]
+pub struct LookupResourcesForAnyRequest<'a> {
+    lookup: LookupResourcesRequest<'a>,
+    subjects: Vec<proto::SubjectReference>,
+    max_in_flight: usize,
+}
+
+impl<'a> LookupResourcesForAnyRequest<'a> {
+    /// Sets the consistency mode, or `None` for the server default.
+    ///
+    /// Each lookup is evaluated at the revision its consistency mode
+    /// selects; pin a revision with [`Consistency::AtExactSnapshot`] for a
+    /// single consistent view.
+    pub fn consistency(mut self, c: impl Into<Option<Consistency>>) -> Self {
+        self.lookup = self.lookup.consistency(c);
+        self
+    }
+
+    /// Sets the caveat evaluation context, shared by every lookup.
+    pub fn context(mut self, ctx: HashMap<String, ContextValue>) -> Self {
+        self.lookup = self.lookup.context(ctx);
+        self
+    }
+
+    /// Sets how many lookups may be outstanding at once.
+    ///
+    /// Defaults to 4. Sending fails with `Err(InvalidArgument)` if `n` is 0.
+    pub fn max_in_flight(mut self, n: usize) -> Self {
+        self.max_in_flight = n;
+        self
+    }
+
+    /// Sends the lookups and returns a stream of the distinct resources any
+    /// subject can access.
+    ///
+    /// Each resource ID is yielded once. An `Allowed` result is yielded as
+    /// soon as it arrives; a `Conditional` one is held until every lookup
+    /// has finished and yielded only if no subject has the permission
+    /// outright. The `looked_up_at` of a result is that of the lookup that
+    /// produced it: tokens are opaque, so the client cannot tell which is
+    /// freshest. The stream ends after the first error.
+    pub async fn send(
+        self,
+    ) -> Result<impl Stream<Item = Result<LookupResourceResult, Error>> + Unpin + 'a, Error> {
+        if self.max_in_flight == 0 {
+            return Err(Error::InvalidArgument(
+                "max_in_flight must be at least 1".into(),
+            ));
+        }
+
+        let lookup = self.lookup;
+        let pending = self.subjects.into_iter().map(move |subject| {
+            let request = LookupResourcesRequest {
+                subject,
+                ..lookup.clone()
+            };
+            let stream: LookupStream<'a> = Box::pin(PendingLookup::Sending(Box::pin(async move {
+                let results: LookupStream<'a> = Box::pin(request.send().await?);
+                Ok(results)
+            })));
+            stream
+        });
+        Ok(merge_lookups(pending, self.max_in_flight))
+    }
+}
+
+/// One subject's lookup in [`LookupResourcesForAnyRequest::send`]: sends
+/// the request when first polled, then yields its results.
+enum PendingLookup<'a> {
+    Sending(Pin<Box<dyn Future<Output = Result<LookupStream<'a>, Error>> + Send + 'a>>),
+    Streaming(LookupStream<'a>),
+    Done,
+}
+
+impl Stream for PendingLookup<'_> {
+    type Item = Result<LookupResourceResult, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match &mut *self {
+                PendingLookup::Sending(send) => match ready!(send.as_mut().poll(cx)) {
+                    Ok(results) => *self = PendingLookup::Streaming(results),
+                    Err(e) => {
+                        *self = PendingLookup::Done;
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                },
+                PendingLookup::Streaming(results) => return results.as_mut().poll_next(cx),
+                PendingLookup::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+/// Merges `lookups`, polling at most `max_in_flight` at once, into one
+/// stream of distinct resource IDs (see [`LookupResourcesForAnyRequest::send`]).
+fn merge_lookups<'a, I>(lookups: I, max_in_flight: usize) -> MergedLookups<'a, I>
+where
+    I: Iterator<Item = LookupStream<'a>> + Unpin,
+{
+    MergedLookups {
+        lookups,
+        max_in_flight,
+        in_flight: Vec::new(),
+        next_slot: 0,
+        yielded: HashSet::new(),
+        held: VecDeque::new(),
+        held_ids: HashSet::new(),
+        done: false,
+    }
+}
+
+/// The stream returned by [`merge_lookups`].
+struct MergedLookups<'a, I> {
+    lookups: I,
+    max_in_flight: usize,
+    in_flight: Vec<LookupStream<'a>>,
+    /// The slot polled first next time, rotated so that a lookup that is
+    /// always ready cannot starve the others.
+    next_slot: usize,
+    /// IDs yielded as `Allowed`.
+    yielded: HashSet<String>,
+    /// Non-`Allowed` results, yielded once every lookup has finished.
+    held: VecDeque<LookupResourceResult>,
+    held_ids: HashSet<String>,
+    done: bool,
+}
+
+impl<'a, I> Stream for MergedLookups<'a, I>
+where
+    I: Iterator<Item = LookupStream<'a>> + Unpin,
+{
+    type Item = Result<LookupResourceResult, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        if this.done {
+            return Poll::Ready(None);
+        }
+        loop {
+            while this.in_flight.len() < this.max_in_flight {
+                match this.lookups.next() {
+                    Some(stream) => this.in_flight.push(stream),
+                    None => break,
+                }
+            }
+            if this.in_flight.is_empty() {
+                while let Some(result) = this.held.pop_front() {
+                    if !this.yielded.contains(&result.resource_id) {
+                        return Poll::Ready(Some(Ok(result)));
+                    }
+                }
+                this.done = true;
+                return Poll::Ready(None);
+            }
+
+            let count = this.in_flight.len();
+            let ready = (0..count).find_map(|offset| {
+                let slot = (this.next_slot + offset) % count;
+                match this.in_flight[slot].as_mut().poll_next(cx) {
+                    Poll::Ready(item) => Some((slot, item)),
+                    Poll::Pending => None,
+                }
+            });
+            let Some((slot, item)) = ready else {
+                return Poll::Pending;
+            };
+            this.next_slot = slot + 1;
+            match item {
+                None => drop(this.in_flight.swap_remove(slot)),
+                Some(Err(e)) => {
+                    this.done = true;
+                    this.in_flight.clear();
+                    return Poll::Ready(Some(Err(e)));
+                }
+                Some(Ok(result)) => {
+                    if result.permission == PermissionResult::Allowed {
+                        if this.yielded.insert(result.resource_id.clone()) {
+                            return Poll::Ready(Some(Ok(result)));
+                        }
+                    } else if !this.yielded.contains(&result.resource_id)
+                        && this.held_ids.insert(result.resource_id.clone())
+                    {
+                        this.held.push_back(result);
+                    }
+                }
+            }
+        }
+    }
+}
+
 // ── LookupSubjects ──────────────────────────────────────────────
 
 /// Builder for a LookupSubjects streaming request.
+#[derive(Clone)]
 pub struct LookupSubjectsRequest<'a> {
     client: &'a Client,
     resource: proto::ObjectReference,
@@ -233,12 +1110,20 @@ pub struct LookupSubjectsRequest<'a> {
     optional_subject_relation: String,
     consistency: Option<proto::Consistency>,
     context: Option<prost_types::Struct>,
+    correlation_id: CorrelationId,
 }
 
 impl<'a> LookupSubjectsRequest<'a> {
-    /// Sets the consistency mode.
-    pub fn consistency(mut self, c: Consistency) -> Self {
-        self.consistency = Some((&c).into());
+    /// Tags the request with a correlation ID, as for
+    /// [`CheckPermissionRequest::correlation_id`].
+    pub fn correlation_id(mut self, id: impl Into<String>) -> Self {
+        self.correlation_id = CorrelationId::new(id.into());
+        self
+    }
+
+    /// Sets the consistency mode, or `None` for the server default.
+    pub fn consistency(mut self, c: impl Into<Option<Consistency>>) -> Self {
+        self.consistency = c.into().as_ref().map(Into::into);
         self
     }
 
@@ -248,10 +1133,25 @@ impl<'a> LookupSubjectsRequest<'a> {
         self
     }
 
+    /// Looks up subject sets with this relation (e.g. `member` to find
+    /// `group:eng#member`) instead of plain subjects.
+    ///
+    /// Each result's [`subject_relation`](LookupSubjectResult::subject_relation)
+    /// is set to this relation.
+    pub fn subject_relation(mut self, relation: impl Into<String>) -> Self {
+        self.optional_subject_relation = relation.into();
+        self
+    }
+
     /// Sends the request and returns a stream of results.
     pub async fn send(
         self,
     ) -> Result<impl Stream<Item = Result<LookupSubjectResult, Error>>, Error> {
+        self.correlation_id.validate()?;
+        let subject_type = self.subject_type.clone();
+        let subject_relation =
+            Some(self.optional_subject_relation.clone()).filter(|r| !r.is_empty());
+        let exact = exact_snapshot(self.consistency.as_ref());
         let req = proto::LookupSubjectsRequest {
             consistency: self.consistency,
             resource: Some(self.resource),
@@ -266,15 +1166,27 @@ impl<'a> LookupSubjectsRequest<'a> {
 
         let response = self
             .client
-            .permissions
-            .clone()
-            .lookup_subjects(req)
+            .authenticated(
+                "PermissionsService.LookupSubjects",
+                &self.client.permissions,
+                self.client.type_prefix.apply(req),
+                |mut s, r| {
+                    let request = self.correlation_id.request(r);
+                    async move { s.lookup_subjects(request).await }
+                },
+            )
+            .instrument(self.correlation_id.span())
             .await
-            .map_err(Error::from_status)?;
+            .map_err(|status| Error::from_status(status).with_snapshot(exact.as_ref()))?;
 
-        Ok(response.into_inner().map(|r| match r {
-            Ok(proto) => LookupSubjectResult::from_proto(proto),
-            Err(status) => Err(Error::from_status(status)),
+        let items = self.client.stream_monitor().observe(response.into_inner());
+        Ok(items.map(move |r| match r {
+            Ok(proto) => LookupSubjectResult::from_proto(
+                proto,
+                subject_type.clone(),
+                subject_relation.clone(),
+            ),
+            Err(status) => Err(Error::from_status(status).with_snapshot(exact.as_ref())),
         }))
     }
 }
@@ -282,16 +1194,97 @@ impl<'a> LookupSubjectsRequest<'a> {
 // ── ReadRelationships ──────────────────────────────────────────────
 
 /// Builder for a ReadRelationships streaming request.
+#[derive(Clone)]
 pub struct ReadRelationshipsRequest<'a> {
     client: &'a Client,
     filter: proto::RelationshipFilter,
     consistency: Option<proto::Consistency>,
+    limit: u32,
+    subject_id_prefix: Option<String>,
+    without_caveat_context: bool,
+    caveat_filter: Option<CaveatFilter>,
+    correlation_id: CorrelationId,
+}
+
+/// Which caveats a [`ReadRelationshipsRequest`] keeps, applied client-side.
+#[derive(Clone)]
+enum CaveatFilter {
+    /// Any caveat.
+    Any,
+    /// Only the caveat with this name.
+    Named(String),
+}
+
+impl CaveatFilter {
+    fn matches(&self, caveat: Option<&Caveat>) -> bool {
+        match (self, caveat) {
+            (_, None) => false,
+            (CaveatFilter::Any, Some(_)) => true,
+            (CaveatFilter::Named(name), Some(caveat)) => caveat.name == *name,
+        }
+    }
 }
 
 impl<'a> ReadRelationshipsRequest<'a> {
-    /// Sets the consistency mode.
-    pub fn consistency(mut self, c: Consistency) -> Self {
-        self.consistency = Some((&c).into());
+    /// Tags the request with a correlation ID, as for
+    /// [`CheckPermissionRequest::correlation_id`].
+    pub fn correlation_id(mut self, id: impl Into<String>) -> Self {
+        self.correlation_id = CorrelationId::new(id.into());
+        self
+    }
+
+    /// Sets the consistency mode, or `None` for the server default.
+    pub fn consistency(mut self, c: impl Into<Option<Consistency>>) -> Self {
+        self.consistency = c.into().as_ref().map(Into::into);
+        self
+    }
+
+    /// Only yields relationships whose subject object ID starts with `prefix`.
+    ///
+    /// SpiceDB's `SubjectFilter` has no prefix field, so this is applied
+    /// client-side after each relationship is fetched: non-matching
+    /// relationships are still streamed from the server and then dropped.
+    /// Narrow the [`RelationshipFilter`] as much as possible to keep the
+    /// fetched set small.
+    pub fn subject_id_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.subject_id_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Drops the caveat context from each relationship read, keeping only
+    /// the caveat name.
+    ///
+    /// SpiceDB always returns the context, so this does not reduce the
+    /// bytes on the wire, and prost still decodes it into a protobuf
+    /// `Struct`. What it saves is converting that `Struct` into
+    /// [`ContextValue`](crate::ContextValue)s and holding them, which adds
+    /// up for large result sets.
+    /// Relationships read this way have an empty
+    /// [`Caveat::context`](crate::Caveat::context); do not write them back
+    /// if the context matters.
+    pub fn without_caveat_context(mut self, without: bool) -> Self {
+        self.without_caveat_context = without;
+        self
+    }
+
+    /// Only yields relationships that have a caveat, for auditing
+    /// conditional grants.
+    ///
+    /// Like [`subject_id_prefix`](Self::subject_id_prefix), this is applied
+    /// client-side: SpiceDB's filter cannot select on caveats, so
+    /// uncaveated relationships are still streamed from the server and then
+    /// dropped. Replaces any [`with_caveat_named`](Self::with_caveat_named).
+    pub fn with_caveat_only(mut self) -> Self {
+        self.caveat_filter = Some(CaveatFilter::Any);
+        self
+    }
+
+    /// Only yields relationships whose caveat is named `name`.
+    ///
+    /// Applied client-side after each relationship is fetched, like
+    /// [`with_caveat_only`](Self::with_caveat_only), which it replaces.
+    pub fn with_caveat_named(mut self, name: impl Into<String>) -> Self {
+        self.caveat_filter = Some(CaveatFilter::Named(name.into()));
         self
     }
 
@@ -299,31 +1292,206 @@ impl<'a> ReadRelationshipsRequest<'a> {
     pub async fn send(
         self,
     ) -> Result<impl Stream<Item = Result<ReadRelationshipResult, Error>>, Error> {
+        self.correlation_id.validate()?;
+        let exact = exact_snapshot(self.consistency.as_ref());
         let req = proto::ReadRelationshipsRequest {
             consistency: self.consistency,
             relationship_filter: Some(self.filter),
-            optional_limit: 0,
+            optional_limit: self.limit,
             optional_cursor: None,
         };
 
         let response = self
             .client
-            .permissions
-            .clone()
-            .read_relationships(req)
+            .authenticated(
+                "PermissionsService.ReadRelationships",
+                &self.client.permissions,
+                self.client.type_prefix.apply(req),
+                |mut s, r| {
+                    let request = self.correlation_id.request(r);
+                    async move { s.read_relationships(request).await }
+                },
+            )
+            .instrument(self.correlation_id.span())
             .await
-            .map_err(Error::from_status)?;
+            .map_err(|status| Error::from_status(status).with_snapshot(exact.as_ref()))?;
 
-        Ok(response.into_inner().map(|r| match r {
-            Ok(proto) => ReadRelationshipResult::from_proto(proto),
-            Err(status) => Err(Error::from_status(status)),
-        }))
+        let type_prefix = self.client.type_prefix.clone();
+        let prefix = self.subject_id_prefix;
+        let without_caveat_context = self.without_caveat_context;
+        let caveat_filter = self.caveat_filter;
+        Ok(self
+            .client
+            .stream_monitor()
+            .observe(response.into_inner())
+            .map(move |r| match r {
+                Ok(mut proto) => {
+                    if without_caveat_context {
+                        if let Some(caveat) = proto
+                            .relationship
+                            .as_mut()
+                            .and_then(|r| r.optional_caveat.as_mut())
+                        {
+                            caveat.context = None;
+                        }
+                    }
+                    ReadRelationshipResult::from_proto(type_prefix.strip(proto))
+                }
+                Err(status) => Err(Error::from_status(status).with_snapshot(exact.as_ref())),
+            })
+            .filter(move |r| match r {
+                Ok(item) => {
+                    let relationship = &item.relationship;
+                    prefix.as_ref().is_none_or(|prefix| {
+                        relationship
+                            .subject
+                            .object()
+                            .object_id()
+                            .starts_with(prefix.as_str())
+                    }) && caveat_filter
+                        .as_ref()
+                        .is_none_or(|filter| filter.matches(relationship.optional_caveat.as_ref()))
+                }
+                Err(_) => true,
+            }))
+    }
+}
+
+/// Builder for [`Client::read_graph`].
+#[derive(Clone)]
+pub struct ReadGraphRequest<'a> {
+    read: ReadRelationshipsRequest<'a>,
+}
+
+impl<'a> ReadGraphRequest<'a> {
+    /// Sets the consistency mode, or `None` for the server default.
+    pub fn consistency(mut self, c: impl Into<Option<Consistency>>) -> Self {
+        self.read = self.read.consistency(c);
+        self
+    }
+}
+
+impl<'a> std::future::IntoFuture for ReadGraphRequest<'a> {
+    type Output = Result<RelationshipGraph, Error>;
+    type IntoFuture =
+        std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            let stream = self.read.send().await?;
+            let mut stream = std::pin::pin!(stream);
+            let mut graph = RelationshipGraph::new();
+            while let Some(result) = stream.next().await {
+                graph.insert(result?.relationship);
+            }
+            Ok(graph)
+        })
+    }
+}
+
+/// Whether `wait_for_token` should keep polling after `err`.
+fn token_not_yet_readable(err: &Error) -> bool {
+    err.is_retryable()
+        || matches!(
+            err.code(),
+            Some(tonic::Code::FailedPrecondition | tonic::Code::OutOfRange)
+        )
+}
+
+// ── CountRelationships ──────────────────────────────────────────────
+
+/// Builder for counting relationships via a full ReadRelationships scan.
+#[derive(Clone)]
+pub struct CountRelationshipsRequest<'a> {
+    read: ReadRelationshipsRequest<'a>,
+    max: Option<u64>,
+}
+
+impl<'a> CountRelationshipsRequest<'a> {
+    /// Sets the consistency mode. Defaults to `FullyConsistent`; pass `None`
+    /// for the server default.
+    pub fn consistency(mut self, c: impl Into<Option<Consistency>>) -> Self {
+        self.read = self.read.consistency(c);
+        self
+    }
+
+    /// Stops counting once `max` relationships have been seen.
+    ///
+    /// A result equal to `max` means "at least `max`".
+    pub fn max(mut self, max: u64) -> Self {
+        self.max = Some(max);
+        self
+    }
+}
+
+impl<'a> std::future::IntoFuture for CountRelationshipsRequest<'a> {
+    type Output = Result<u64, Error>;
+    type IntoFuture =
+        std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            let mut count = 0;
+            if self.max == Some(0) {
+                return Ok(count);
+            }
+
+            let stream = self.read.send().await?;
+            let mut stream = std::pin::pin!(stream);
+            while let Some(result) = stream.next().await {
+                result?;
+                count += 1;
+                if self.max == Some(count) {
+                    break;
+                }
+            }
+            Ok(count)
+        })
+    }
+}
+
+// ── RelationshipExists ──────────────────────────────────────────────
+
+/// Builder for checking whether a single relationship exists.
+#[derive(Clone)]
+pub struct RelationshipExistsRequest<'a> {
+    read: ReadRelationshipsRequest<'a>,
+}
+
+impl<'a> RelationshipExistsRequest<'a> {
+    /// Sets the consistency mode, or `None` for the server default.
+    pub fn consistency(mut self, c: impl Into<Option<Consistency>>) -> Self {
+        self.read = self.read.consistency(c);
+        self
+    }
+}
+
+impl<'a> std::future::IntoFuture for RelationshipExistsRequest<'a> {
+    type Output = Result<bool, Error>;
+    type IntoFuture =
+        std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            let stream = self.read.send().await?;
+            let mut stream = std::pin::pin!(stream);
+            match stream.next().await {
+                Some(result) => result.map(|_| true),
+                None => Ok(false),
+            }
+        })
     }
 }
 
 // ── ExpandPermissionTree ──────────────────────────────────────────────
 
 /// Builder for an ExpandPermissionTree request.
+///
+/// ExpandPermissionTree has no depth parameter: SpiceDB recurses up to its
+/// server-wide dispatch depth limit and fails with
+/// [`Error::MaximumDepthExceeded`] beyond it. To keep trees small, expand a
+/// narrower relation or permission instead.
+#[derive(Clone)]
 pub struct ExpandPermissionTreeRequest<'a> {
     client: &'a Client,
     resource: proto::ObjectReference,
@@ -332,39 +1500,110 @@ pub struct ExpandPermissionTreeRequest<'a> {
 }
 
 impl<'a> ExpandPermissionTreeRequest<'a> {
-    /// Sets the consistency mode.
-    pub fn consistency(mut self, c: Consistency) -> Self {
-        self.consistency = Some((&c).into());
+    /// Sets the consistency mode, or `None` for the server default.
+    pub fn consistency(mut self, c: impl Into<Option<Consistency>>) -> Self {
+        self.consistency = c.into().as_ref().map(Into::into);
         self
     }
 }
 
+impl<'a> ExpandPermissionTreeRequest<'a> {
+    /// Sends the request, returning the tree and the token it was expanded at.
+    async fn expand(self) -> Result<(PermissionTree, ZedToken), Error> {
+        let (tree, expanded_at) = self.expand_proto().await?;
+        Ok((PermissionTree::from_proto(tree)?, expanded_at))
+    }
+
+    /// Like `expand`, but keeps the proto tree, whose nodes each carry the
+    /// object and relation they expand.
+    pub(super) async fn expand_proto(
+        self,
+    ) -> Result<(proto::PermissionRelationshipTree, ZedToken), Error> {
+        let exact = exact_snapshot(self.consistency.as_ref());
+        let req = proto::ExpandPermissionTreeRequest {
+            consistency: self.consistency,
+            resource: Some(self.resource),
+            permission: self.permission,
+        };
+
+        let response = self
+            .client
+            .authenticated(
+                "PermissionsService.ExpandPermissionTree",
+                &self.client.permissions,
+                self.client.type_prefix.apply(req),
+                |mut s, r| async move { s.expand_permission_tree(r).await },
+            )
+            .await
+            .map_err(|status| Error::from_status(status).with_snapshot(exact.as_ref()))?;
+
+        let inner = self.client.type_prefix.strip(response.into_inner());
+        let expanded_at = inner
+            .expanded_at
+            .ok_or_else(|| Error::Serialization("missing expanded_at token".into()))?
+            .try_into()?;
+        let tree = inner
+            .tree_root
+            .ok_or_else(|| Error::Serialization("missing tree_root".into()))?;
+        Ok((tree, expanded_at))
+    }
+}
+
 impl<'a> std::future::IntoFuture for ExpandPermissionTreeRequest<'a> {
     type Output = Result<PermissionTree, Error>;
     type IntoFuture =
         std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'a>>;
 
     fn into_future(self) -> Self::IntoFuture {
-        Box::pin(async move {
-            let req = proto::ExpandPermissionTreeRequest {
-                consistency: self.consistency,
-                resource: Some(self.resource),
-                permission: self.permission,
-            };
+        Box::pin(async move { self.expand().await.map(|(tree, _)| tree) })
+    }
+}
 
-            let response = self
-                .client
-                .permissions
-                .clone()
-                .expand_permission_tree(req)
-                .await
-                .map_err(Error::from_status)?;
+// ── CheckSubjectsViaExpand ────────────────────────────────────────────
 
-            let inner = response.into_inner();
-            let tree = inner
-                .tree_root
-                .ok_or_else(|| Error::Serialization("missing tree_root".into()))?;
-            PermissionTree::from_proto(tree)
+/// Builder for [`Client::check_subjects_via_expand`].
+#[derive(Clone)]
+pub struct CheckSubjectsViaExpandRequest<'a> {
+    expand: ExpandPermissionTreeRequest<'a>,
+    resource: ObjectReference,
+    permission: String,
+    subjects: Vec<SubjectReference>,
+}
+
+impl<'a> CheckSubjectsViaExpandRequest<'a> {
+    /// Sets the consistency of the expansion, or `None` for the server
+    /// default. Fallback checks always run at the expansion's snapshot.
+    pub fn consistency(mut self, c: impl Into<Option<Consistency>>) -> Self {
+        self.expand = self.expand.consistency(c);
+        self
+    }
+}
+
+impl<'a> std::future::IntoFuture for CheckSubjectsViaExpandRequest<'a> {
+    type Output = Result<Vec<PermissionResult>, Error>;
+    type IntoFuture =
+        std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output> + Send + 'a>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            let client = self.expand.client;
+            let (tree, expanded_at) = self.expand.expand().await?;
+
+            // The tree carries no caveat data, so membership it shows may be
+            // conditional: only a subject it rules out is answered locally.
+            let mut results = Vec::with_capacity(self.subjects.len());
+            for subject in &self.subjects {
+                let result = if tree.node.rules_out(subject) {
+                    PermissionResult::Denied
+                } else {
+                    client
+                        .check_permission(&self.resource, self.permission.clone(), subject)
+                        .consistency(Consistency::AtExactSnapshot(expanded_at.clone()))
+                        .await?
+                };
+                results.push(result);
+            }
+            Ok(results)
         })
     }
 }
@@ -376,6 +1615,16 @@ impl Client {
     ///
     /// Returns a [`PermissionResult`] with three possible states.
     /// Use `.consistency()` and `.context()` on the returned builder.
+    ///
+    /// # Cost per call
+    ///
+    /// The generated request message owns its strings, so the resource, the
+    /// subject and the permission are copied into it once. `permission` is
+    /// moved in without a copy when it is already a `String` (or an owned
+    /// `Cow<str>`); a `&str` costs one copy either way. The service handle
+    /// cloned for each call shares the underlying connection and token
+    /// rather than opening new ones, and the returned future is boxed. For
+    /// checks in a loop, [`Client::permission_checker`] avoids both.
     pub fn check_permission(
         &self,
         resource: &ObjectReference,
@@ -387,14 +1636,103 @@ impl Client {
             resource: resource.into(),
             permission: permission.into(),
             subject: subject.into(),
-            consistency: None,
+            consistency: self.default_consistency.get(RpcKind::Check),
             context: None,
+            correlation_id: CorrelationId::default(),
+        }
+    }
+
+    /// Checks a permission and fails unless it is granted, for enforcing
+    /// access in request handlers.
+    ///
+    /// Resolves to the `checked_at` token when the permission is allowed,
+    /// [`Error::PermissionDenied`] carrying the checked references when it
+    /// is denied, and [`Error::ConditionalPermission`] when a caveat could
+    /// not be evaluated for lack of context. Use `.consistency()` and
+    /// `.context()` on the returned builder.
+    ///
+    /// ```rust,no_run
+    /// # use prescience::{Client, Error, ObjectReference, SubjectReference};
+    /// # async fn example(client: &Client, doc: &ObjectReference, alice: &SubjectReference)
+    /// # -> Result<(), Error> {
+    /// match client.require_permission(doc, "edit", alice).await {
+    ///     Ok(_checked_at) => { /* proceed */ }
+    ///     Err(Error::PermissionDenied { .. }) => { /* respond 403 */ }
+    ///     Err(e) => return Err(e),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn require_permission(
+        &self,
+        resource: &ObjectReference,
+        permission: impl Into<String>,
+        subject: &SubjectReference,
+    ) -> RequirePermissionRequest<'_> {
+        RequirePermissionRequest {
+            check: self.check_permission(resource, permission, subject),
+            resource: resource.clone(),
+            subject: subject.clone(),
+        }
+    }
+
+    /// Checks `permission` on `resource` for many candidate subjects with a
+    /// single ExpandPermissionTree call, returning one result per subject in
+    /// input order.
+    ///
+    /// Expanded trees carry no caveat information, so the tree is only
+    /// trusted to rule candidates out: a candidate that appears in no branch
+    /// of it is [`PermissionResult::Denied`] without another call. Every
+    /// other candidate, including one the tree lists as a member (the
+    /// grant may be caveated), one reached through a nested subject set
+    /// (such as `group:eng#member`) or a wildcard, and one only excluded
+    /// (the exclusion may be caveated), falls back to an explicit
+    /// CheckPermission, pinned to the snapshot the tree was expanded at.
+    ///
+    /// One expand plus a check per possible member is cheaper than N checks
+    /// when most candidates have no access, but an expand of a permission
+    /// with many direct subjects returns all of them, and every candidate
+    /// that is not ruled out costs a sequential round-trip.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use prescience::{Client, ObjectReference, SubjectReference};
+    /// # async fn example(client: &Client) -> Result<(), prescience::Error> {
+    /// let doc = ObjectReference::new("document", "doc-1")?;
+    /// let candidates = vec![
+    ///     SubjectReference::parse("user:alice")?,
+    ///     SubjectReference::parse("user:bob")?,
+    /// ];
+    /// let results = client
+    ///     .check_subjects_via_expand(&doc, "view", candidates)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn check_subjects_via_expand(
+        &self,
+        resource: &ObjectReference,
+        permission: impl Into<String>,
+        subjects: Vec<SubjectReference>,
+    ) -> CheckSubjectsViaExpandRequest<'_> {
+        let permission = permission.into();
+        CheckSubjectsViaExpandRequest {
+            expand: self.expand_permission_tree(resource, permission.clone()),
+            resource: resource.clone(),
+            permission,
+            subjects,
         }
     }
 
     /// Writes a batch of relationship updates atomically.
     ///
-    /// Returns `Err(InvalidArgument)` if `updates` is empty.
+    /// Returns `Err(InvalidArgument)` without sending anything if `updates` is
+    /// empty, exceeds [`limits::MAX_WRITE_UPDATES`], or updates the same
+    /// relationship more than once (in any combination of operations, and
+    /// regardless of caveat). When the server rejects the write and its
+    /// error names a relationship or relation, the offending update is reported
+    /// in [`SpiceDbErrorDetails::failed_update`](crate::error::SpiceDbErrorDetails::failed_update).
     pub fn write_relationships(
         &self,
         updates: Vec<RelationshipUpdate>,
@@ -402,9 +1740,56 @@ impl Client {
         // FR-10.1: empty vec validation is checked in IntoFuture
         WriteRelationshipsRequest {
             client: self,
-            updates: updates.iter().map(Into::into).collect(),
+            updates,
             preconditions: vec![],
+            metadata: None,
+            reject_self_reference: false,
+            correlation_id: CorrelationId::default(),
+        }
+    }
+
+    /// Writes relationship updates from a stream in sequential chunks,
+    /// returning the token from the last write.
+    ///
+    /// Updates are accumulated into batches of `chunk_size` and each batch is
+    /// sent as its own atomic WriteRelationships call, so only one chunk is
+    /// held in memory at a time. This is the non-experimental counterpart to
+    /// bulk import for large ETL-style loads.
+    ///
+    /// Atomicity is **per chunk only**: if a later chunk fails, earlier chunks
+    /// remain written and the error is returned.
+    ///
+    /// Returns `Err(InvalidArgument)` if `chunk_size` is zero or exceeds
+    /// [`limits::MAX_WRITE_UPDATES`], or if the stream yields no updates.
+    pub async fn write_relationships_stream<S>(
+        &self,
+        stream: S,
+        chunk_size: usize,
+    ) -> Result<ZedToken, Error>
+    where
+        S: Stream<Item = RelationshipUpdate>,
+    {
+        if chunk_size == 0 || chunk_size > limits::MAX_WRITE_UPDATES {
+            return Err(Error::InvalidArgument(format!(
+                "chunk_size must be between 1 and {}",
+                limits::MAX_WRITE_UPDATES
+            )));
         }
+
+        let mut stream = std::pin::pin!(stream);
+        let mut chunk = Vec::with_capacity(chunk_size);
+        let mut token = None;
+        while let Some(update) = stream.next().await {
+            chunk.push(update);
+            if chunk.len() == chunk_size {
+                let batch = std::mem::replace(&mut chunk, Vec::with_capacity(chunk_size));
+                token = Some(self.write_relationships(batch).await?);
+            }
+        }
+        if !chunk.is_empty() {
+            token = Some(self.write_relationships(chunk).await?);
+        }
+        token.ok_or_else(|| Error::InvalidArgument("stream yielded no updates".into()))
     }
 
     /// Deletes all relationships matching the given filter.
@@ -416,12 +1801,39 @@ impl Client {
             client: self,
             filter: (&filter).into(),
             preconditions: vec![],
+            metadata: None,
+            correlation_id: CorrelationId::default(),
+        }
+    }
+
+    /// Deletes relationships matching each filter in turn, returning the
+    /// token from the last delete.
+    ///
+    /// SpiceDB has no multi-filter delete, so this issues one
+    /// DeleteRelationships call per filter, sequentially, and stops at the
+    /// first error. The deletes are **not atomic across filters**: if a later
+    /// filter fails, relationships removed by earlier filters stay deleted.
+    ///
+    /// Returns `Err(InvalidArgument)` if `filters` is empty.
+    pub async fn delete_relationships_many(
+        &self,
+        filters: Vec<RelationshipFilter>,
+    ) -> Result<ZedToken, Error> {
+        let mut token = None;
+        for filter in filters {
+            token = Some(self.delete_relationships(filter).await?);
         }
+        token.ok_or_else(|| Error::InvalidArgument("filters must not be empty".into()))
     }
 
     /// Looks up all resources of a given type that a subject can access.
     ///
     /// Returns a streaming builder. Call `.send().await?` to get the stream.
+    ///
+    /// SpiceDB's LookupResources has no tracing option and returns no
+    /// debug information. To find out why an expected resource is missing,
+    /// run [`Client::check_permission`] for that resource at the
+    /// `looked_up_at` token of the lookup.
     pub fn lookup_resources(
         &self,
         resource_type: impl Into<String>,
@@ -433,14 +1845,66 @@ impl Client {
             resource_type: resource_type.into(),
             permission: permission.into(),
             subject: subject.into(),
-            consistency: None,
+            consistency: self.default_consistency.get(RpcKind::Lookup),
             context: None,
+            correlation_id: CorrelationId::default(),
+        }
+    }
+
+    /// Looks up the resources of a given type that any of `subjects` can
+    /// access, e.g. everything visible to any of a user's groups.
+    ///
+    /// Runs one lookup per subject, up to 4 at once (see
+    /// `max_in_flight`), and merges them into a single stream with each
+    /// resource ID appearing once. Call `.send().await?` to get the stream.
+    ///
+    /// ```rust,no_run
+    /// # use prescience::{Client, SubjectReference};
+    /// # use tokio_stream::StreamExt;
+    /// # async fn example(client: &Client) -> Result<(), prescience::Error> {
+    /// let subjects = vec![
+    ///     SubjectReference::parse("user:alice")?,
+    ///     SubjectReference::parse("user:bob")?,
+    /// ];
+    /// let mut stream = client
+    ///     .lookup_resources_for_any("document", "view", subjects)
+    ///     .send()
+    ///     .await?;
+    /// while let Some(result) = stream.next().await {
+    ///     println!("{}", result?.resource_id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn lookup_resources_for_any(
+        &self,
+        resource_type: impl Into<String>,
+        permission: impl Into<String>,
+        subjects: Vec<SubjectReference>,
+    ) -> LookupResourcesForAnyRequest<'_> {
+        LookupResourcesForAnyRequest {
+            lookup: LookupResourcesRequest {
+                client: self,
+                resource_type: resource_type.into(),
+                permission: permission.into(),
+                subject: proto::SubjectReference::default(),
+                consistency: self.default_consistency.get(RpcKind::Lookup),
+                context: None,
+                correlation_id: CorrelationId::default(),
+            },
+            subjects: subjects.iter().map(Into::into).collect(),
+            max_in_flight: DEFAULT_LOOKUPS_IN_FLIGHT,
         }
     }
 
     /// Looks up all subjects of a given type that have access to a resource.
     ///
     /// Returns a streaming builder. Call `.send().await?` to get the stream.
+    ///
+    /// SpiceDB's LookupSubjects has no tracing option and returns no
+    /// debug information. To find out why an expected subject is missing,
+    /// run [`Client::check_permission`] for that subject at the
+    /// `looked_up_at` token of the lookup.
     pub fn lookup_subjects(
         &self,
         resource: &ObjectReference,
@@ -453,8 +1917,9 @@ impl Client {
             permission: permission.into(),
             subject_type: subject_type.into(),
             optional_subject_relation: String::new(),
-            consistency: None,
+            consistency: self.default_consistency.get(RpcKind::Lookup),
             context: None,
+            correlation_id: CorrelationId::default(),
         }
     }
 
@@ -465,7 +1930,114 @@ impl Client {
         ReadRelationshipsRequest {
             client: self,
             filter: (&filter).into(),
-            consistency: None,
+            consistency: self.default_consistency.get(RpcKind::Read),
+            limit: 0,
+            subject_id_prefix: None,
+            without_caveat_context: false,
+            caveat_filter: None,
+            correlation_id: CorrelationId::default(),
+        }
+    }
+
+    /// Reads all relationships matching `filter` into a [`RelationshipGraph`]
+    /// that can be walked from any object to its subjects or resources, or
+    /// rendered with [`RelationshipGraph::to_dot`].
+    ///
+    /// The whole result is collected in memory, so keep the filter narrow
+    /// (e.g. one resource) and use
+    /// [`read_relationships`](Self::read_relationships) to stream large
+    /// sets. Use `.consistency()` on the returned builder.
+    pub fn read_graph(&self, filter: RelationshipFilter) -> ReadGraphRequest<'_> {
+        ReadGraphRequest {
+            read: self.read_relationships(filter),
+        }
+    }
+
+    /// Checks whether the given relationship exists.
+    ///
+    /// This is a relationship lookup, not a permission check: it reads with a
+    /// filter matching the relationship's exact resource, relation, and
+    /// subject (limit 1). The relationship's caveat is not part of the match,
+    /// so a caveated tuple counts as existing regardless of its caveat.
+    pub fn relationship_exists(
+        &self,
+        relationship: &Relationship,
+    ) -> RelationshipExistsRequest<'_> {
+        let mut read = self.read_relationships(RelationshipFilter::matching(relationship));
+        read.limit = 1;
+        RelationshipExistsRequest { read }
+    }
+
+    /// Waits until `token` is readable, or `timeout` elapses.
+    ///
+    /// This is a polling convenience, not a server primitive: it repeatedly
+    /// issues a cheap read (an export limited to one relationship) at
+    /// `AtLeastAsFresh(token)` until one succeeds, backing off between
+    /// attempts. Use it after a write when a follower replica must reflect
+    /// the write before another reader — a test, or another service — runs
+    /// a `MinimizeLatency` read.
+    ///
+    /// Returns a `DEADLINE_EXCEEDED` status error if the token is still not
+    /// readable when `timeout` elapses. Errors that polling cannot fix, such
+    /// as `UNAUTHENTICATED`, are returned immediately.
+    pub async fn wait_for_token(&self, token: &ZedToken, timeout: Duration) -> Result<(), Error> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut delay = Duration::from_millis(10);
+        loop {
+            let attempt = self.read_one_at(token);
+            match tokio::time::timeout_at(deadline, attempt).await {
+                Ok(Ok(())) => return Ok(()),
+                Ok(Err(e)) if !token_not_yet_readable(&e) => return Err(e),
+                Ok(Err(_)) | Err(_) => {}
+            }
+            if tokio::time::Instant::now() + delay >= deadline {
+                return Err(Error::Status {
+                    code: tonic::Code::DeadlineExceeded,
+                    message: format!("token not readable within {:?}", timeout),
+                    details: None,
+                });
+            }
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(Duration::from_millis(500));
+        }
+    }
+
+    /// Reads at most one relationship at `AtLeastAsFresh(token)`.
+    async fn read_one_at(&self, token: &ZedToken) -> Result<(), Error> {
+        let req = proto::ExportBulkRelationshipsRequest {
+            consistency: Some((&Consistency::AtLeastAsFresh(token.clone())).into()),
+            optional_limit: 1,
+            optional_cursor: None,
+            optional_relationship_filter: None,
+        };
+        let mut stream = self
+            .authenticated(
+                "PermissionsService.ExportBulkRelationships",
+                &self.permissions,
+                req,
+                |mut s, r| async move { s.export_bulk_relationships(r).await },
+            )
+            .await
+            .map_err(Error::from_status)?
+            .into_inner();
+        match stream.next().await {
+            Some(Err(status)) => Err(Error::from_status(status)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Counts the relationships matching the given filter.
+    ///
+    /// SpiceDB has no count RPC, so this streams every matching relationship
+    /// via ReadRelationships and counts them client-side: it is a full scan,
+    /// O(n) in the number of matches. Use `.max()` to stop early when only a
+    /// bound is needed (e.g. for a progress bar).
+    pub fn count_relationships(&self, filter: RelationshipFilter) -> CountRelationshipsRequest<'_> {
+        CountRelationshipsRequest {
+            read: self
+                .read_relationships(filter)
+                .consistency(Consistency::FullyConsistent),
+            max: None,
         }
     }
 
@@ -479,7 +2051,242 @@ impl Client {
             client: self,
             resource: resource.into(),
             permission: permission.into(),
-            consistency: None,
+            consistency: self.default_consistency.get(RpcKind::Expand),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn update(make: fn(Relationship) -> RelationshipUpdate, s: &str) -> proto::RelationshipUpdate {
+        (&make(Relationship::parse(s).unwrap())).into()
+    }
+
+    #[test]
+    fn schema_problem_checks_types_relations_and_subjects() {
+        let schema = WriteSchema::from_parsed(
+            crate::schema::parse(
+                "definition user {}
+             definition group { relation member: user }
+             definition document {
+                 relation viewer: user | group#member | user:*
+                 relation auditor: user with ip_allowlist
+                 permission view = viewer
+             }",
+            )
+            .unwrap(),
+        );
+        let problem = |tuple: &str| {
+            let rel: proto::Relationship = (&Relationship::parse(tuple).unwrap()).into();
+            schema_problem(&schema, 0, &rel, &rel).map(|p| p.to_string())
+        };
+
+        assert_eq!(problem("document:1#viewer@user:alice"), None);
+        assert_eq!(problem("document:1#viewer@group:eng#member"), None);
+        assert_eq!(problem("document:1#viewer@user:*"), None);
+        assert_eq!(problem("document:1#auditor@user:bob[ip_allowlist]"), None);
+        assert_eq!(
+            problem("folder:1#viewer@user:alice").as_deref(),
+            Some("update 0: type folder is not defined")
+        );
+        assert_eq!(
+            problem("document:1#view@user:alice").as_deref(),
+            Some("update 0: document has no relation view")
+        );
+        assert_eq!(
+            problem("document:1#viewer@group:eng").as_deref(),
+            Some("update 0: relation viewer does not allow group")
+        );
+        assert_eq!(
+            problem("document:1#auditor@user:bob").as_deref(),
+            Some("update 0: relation auditor does not allow user")
+        );
+        assert_eq!(
+            problem("document:1#viewer@user:bob[ip_allowlist]").as_deref(),
+            Some("update 0: relation viewer does not allow user with ip_allowlist")
+        );
+    }
+
+    #[cfg(feature = "experimental")]
+    #[test]
+    fn schema_problem_uses_reflected_subject_types() {
+        use proto::exp_type_reference::Typeref;
+
+        let reference = |name: &str, typeref, caveat: &str| proto::ExpTypeReference {
+            subject_definition_name: name.into(),
+            optional_caveat_name: caveat.into(),
+            typeref: Some(typeref),
+        };
+        let schema = WriteSchema::from_reflected(vec![proto::ExpDefinition {
+            name: "document".into(),
+            relations: vec![proto::ExpRelation {
+                name: "viewer".into(),
+                subject_types: vec![
+                    reference("user", Typeref::IsTerminalSubject(true), ""),
+                    reference("group", Typeref::OptionalRelationName("member".into()), ""),
+                    reference("user", Typeref::IsPublicWildcard(true), "ip_allowlist"),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        }]);
+        let problem = |tuple: &str| {
+            let rel: proto::Relationship = (&Relationship::parse(tuple).unwrap()).into();
+            schema_problem(&schema, 0, &rel, &rel).map(|p| p.to_string())
+        };
+
+        assert_eq!(problem("document:1#viewer@user:alice"), None);
+        assert_eq!(problem("document:1#viewer@group:eng#member"), None);
+        assert_eq!(problem("document:1#viewer@user:*[ip_allowlist]"), None);
+        assert_eq!(
+            problem("document:1#viewer@user:*").as_deref(),
+            Some("update 0: relation viewer does not allow user:*")
+        );
+        assert_eq!(
+            problem("document:1#viewer@group:eng").as_deref(),
+            Some("update 0: relation viewer does not allow group")
+        );
+    }
+
+    fn lookup(results: &[(&str, PermissionResult)]) -> LookupStream<'static> {
+        let results: Vec<_> = results
+            .iter()
+            .map(|(id, permission)| {
+                Ok(LookupResourceResult {
+                    resource_type: "document".into(),
+                    resource_id: id.to_string(),
+                    permission: permission.clone(),
+                    looked_up_at: ZedToken::new("t").unwrap(),
+                })
+            })
+            .collect();
+        Box::pin(tokio_stream::iter(results))
+    }
+
+    #[tokio::test]
+    async fn merge_lookups_dedups_and_prefers_allowed() {
+        let conditional = PermissionResult::Conditional {
+            missing_fields: vec!["user_ip".into()],
+        };
+        let lookups = vec![
+            lookup(&[
+                ("a", PermissionResult::Allowed),
+                ("b", conditional.clone()),
+                ("c", conditional.clone()),
+            ]),
+            lookup(&[
+                ("b", PermissionResult::Allowed),
+                ("a", PermissionResult::Allowed),
+            ]),
+            lookup(&[("c", conditional.clone()), ("d", PermissionResult::Allowed)]),
+        ];
+
+        let merged: Vec<(String, PermissionResult)> = merge_lookups(lookups.into_iter(), 2)
+            .map(|r| {
+                let r = r.unwrap();
+                (r.resource_id, r.permission)
+            })
+            .collect()
+            .await;
+        assert_eq!(
+            merged,
+            [
+                ("a".to_string(), PermissionResult::Allowed),
+                ("b".to_string(), PermissionResult::Allowed),
+                ("d".to_string(), PermissionResult::Allowed),
+                ("c".to_string(), conditional),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn merge_lookups_rotates_between_ready_lookups() {
+        let busy: Vec<(String, PermissionResult)> = (0..10)
+            .map(|i| (format!("busy-{i}"), PermissionResult::Allowed))
+            .collect();
+        let busy: Vec<(&str, PermissionResult)> = busy
+            .iter()
+            .map(|(id, p)| (id.as_str(), p.clone()))
+            .collect();
+        let lookups = vec![
+            lookup(&busy),
+            lookup(&[("quiet", PermissionResult::Allowed)]),
+        ];
+
+        let first: Vec<String> = merge_lookups(lookups.into_iter(), 2)
+            .take(2)
+            .map(|r| r.unwrap().resource_id)
+            .collect()
+            .await;
+        assert_eq!(first, ["busy-0", "quiet"]);
+    }
+
+    #[test]
+    fn duplicate_updates_rejected_with_both_indices() {
+        let updates = vec![
+            update(RelationshipUpdate::create, "document:1#viewer@user:alice"),
+            update(RelationshipUpdate::create, "document:1#viewer@user:bob"),
+            update(
+                RelationshipUpdate::delete,
+                "document:1#viewer@user:alice[ip]",
+            ),
+        ];
+        let err = check_duplicate_updates(&updates).unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidArgument(ref m)
+                if m.contains("updates 0 (create) and 2 (delete)")
+                    && m.contains("document:1#viewer@user:alice")),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn distinct_updates_accepted() {
+        let updates = vec![
+            update(RelationshipUpdate::touch, "document:1#viewer@user:alice"),
+            update(
+                RelationshipUpdate::touch,
+                "document:1#viewer@group:eng#member",
+            ),
+            update(RelationshipUpdate::touch, "document:1#editor@user:alice"),
+        ];
+        assert!(check_duplicate_updates(&updates).is_ok());
+    }
+
+    #[test]
+    fn self_references_rejected_except_deletes() {
+        let updates = vec![
+            update(RelationshipUpdate::create, "document:1#parent@document:2"),
+            update(RelationshipUpdate::delete, "document:1#viewer@document:1"),
+            update(
+                RelationshipUpdate::touch,
+                "group:eng#admin@group:eng#member",
+            ),
+        ];
+        let err = check_self_references(&updates).unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidArgument(ref m)
+                if m.contains("update 2 relates group:eng to itself")
+                    && m.contains("group:eng#admin@group:eng#member")),
+            "{err}"
+        );
+        assert!(check_self_references(&updates[..2]).is_ok());
+        assert!(check_self_references(&[update(
+            RelationshipUpdate::create,
+            "document:1#viewer@user:1"
+        )])
+        .is_ok());
+    }
+
+    #[test]
+    fn caveat_filter_matches_presence_and_name() {
+        let ip = Caveat::new("ip_allowlist", HashMap::new());
+        assert!(CaveatFilter::Any.matches(Some(&ip)));
+        assert!(!CaveatFilter::Any.matches(None));
+        assert!(CaveatFilter::Named("ip_allowlist".into()).matches(Some(&ip)));
+        assert!(!CaveatFilter::Named("expiry".into()).matches(Some(&ip)));
+        assert!(!CaveatFilter::Named("ip_allowlist".into()).matches(None));
+    }
+}
@@ -0,0 +1,355 @@
+//! Object type prefixing for namespaced schemas (see [`ClientBuilder::type_prefix`]).
+//!
+//! Requests have the prefix prepended to every object and subject type just
+//! before they are sent; responses have it stripped just after they arrive.
+//! Working on the proto messages keeps the domain types unaware of prefixing.
+//!
+//! [`ClientBuilder::type_prefix`]: super::ClientBuilder::type_prefix
+
+use std::sync::Arc;
+
+use crate::proto;
+
+/// The object type prefix configured on a client, or none.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TypePrefix(Option<Arc<str>>);
+
+impl TypePrefix {
+    /// Creates a prefix, normalized to end in exactly one `/`.
+    ///
+    /// An empty prefix (or just `/`) means no prefixing.
+    pub(crate) fn new(prefix: &str) -> Self {
+        let prefix = prefix.trim_end_matches('/');
+        if prefix.is_empty() {
+            return Self(None);
+        }
+        Self(Some(format!("{}/", prefix).into()))
+    }
+
+    /// Returns the prefix including its trailing `/`, if set.
+    pub(crate) fn as_str(&self) -> Option<&str> {
+        self.0.as_deref()
+    }
+
+    /// Prepends the prefix to every non-empty object type in `message`.
+    pub(crate) fn apply<M: ObjectTypes>(&self, mut message: M) -> M {
+        if let Some(prefix) = self.as_str() {
+            message.visit_types(&mut |t| {
+                if !t.is_empty() {
+                    t.insert_str(0, prefix);
+                }
+            });
+        }
+        message
+    }
+
+    /// Removes the prefix from every object type in `message` that carries it.
+    ///
+    /// Types outside the prefix are left untouched.
+    pub(crate) fn strip<M: ObjectTypes>(&self, mut message: M) -> M {
+        if let Some(prefix) = self.as_str() {
+            message.visit_types(&mut |t| {
+                if t.starts_with(prefix) {
+                    t.drain(..prefix.len());
+                }
+            });
+        }
+        message
+    }
+}
+
+/// A proto message that carries object or subject type names.
+pub(crate) trait ObjectTypes {
+    /// Calls `f` on every object type field in the message.
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String));
+}
+
+impl<T: ObjectTypes> ObjectTypes for Option<T> {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        if let Some(inner) = self {
+            inner.visit_types(f);
+        }
+    }
+}
+
+impl<T: ObjectTypes> ObjectTypes for Vec<T> {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        for inner in self {
+            inner.visit_types(f);
+        }
+    }
+}
+
+impl ObjectTypes for String {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        f(self);
+    }
+}
+
+impl ObjectTypes for proto::ObjectReference {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        f(&mut self.object_type);
+    }
+}
+
+impl ObjectTypes for proto::SubjectReference {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.object.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::Relationship {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.resource.visit_types(f);
+        self.subject.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::RelationshipUpdate {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.relationship.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::SubjectFilter {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        f(&mut self.subject_type);
+    }
+}
+
+impl ObjectTypes for proto::RelationshipFilter {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        f(&mut self.resource_type);
+        self.optional_subject_filter.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::Precondition {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.filter.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::CheckDebugTrace {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        use proto::check_debug_trace::Resolution;
+        self.resource.visit_types(f);
+        self.subject.visit_types(f);
+        if let Some(Resolution::SubProblems(sub)) = &mut self.resolution {
+            sub.traces.visit_types(f);
+        }
+    }
+}
+
+impl ObjectTypes for proto::PermissionRelationshipTree {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        use proto::permission_relationship_tree::TreeType;
+        self.expanded_object.visit_types(f);
+        match &mut self.tree_type {
+            Some(TreeType::Intermediate(set)) => set.children.visit_types(f),
+            Some(TreeType::Leaf(set)) => set.subjects.visit_types(f),
+            None => {}
+        }
+    }
+}
+
+impl ObjectTypes for proto::CheckPermissionRequest {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.resource.visit_types(f);
+        self.subject.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::WriteRelationshipsRequest {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.updates.visit_types(f);
+        self.optional_preconditions.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::DeleteRelationshipsRequest {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.relationship_filter.visit_types(f);
+        self.optional_preconditions.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::LookupResourcesRequest {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        f(&mut self.resource_object_type);
+        self.subject.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::LookupSubjectsRequest {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.resource.visit_types(f);
+        f(&mut self.subject_object_type);
+    }
+}
+
+impl ObjectTypes for proto::ReadRelationshipsRequest {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.relationship_filter.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::ReadRelationshipsResponse {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.relationship.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::ExpandPermissionTreeRequest {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.resource.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::ExpandPermissionTreeResponse {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.tree_root.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::CheckBulkPermissionsRequestItem {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.resource.visit_types(f);
+        self.subject.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::ImportBulkRelationshipsRequest {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.relationships.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::ExportBulkRelationshipsRequest {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.optional_relationship_filter.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::ExportBulkRelationshipsResponse {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.relationships.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::WatchRequest {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.optional_object_types.visit_types(f);
+        self.optional_relationship_filters.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::WatchResponse {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.updates.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::ExperimentalRegisterRelationshipCounterRequest {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.relationship_filter.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::ExperimentalReflectSchemaResponse {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        self.definitions.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::ExpDefinition {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        f(&mut self.name);
+        self.relations.visit_types(f);
+        self.permissions.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::ExpRelation {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        f(&mut self.parent_definition_name);
+        self.subject_types.visit_types(f);
+    }
+}
+
+impl ObjectTypes for proto::ExpPermission {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        f(&mut self.parent_definition_name);
+    }
+}
+
+impl ObjectTypes for proto::ExpTypeReference {
+    fn visit_types(&mut self, f: &mut dyn FnMut(&mut String)) {
+        f(&mut self.subject_definition_name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(object_type: &str, object_id: &str) -> proto::ObjectReference {
+        proto::ObjectReference {
+            object_type: object_type.into(),
+            object_id: object_id.into(),
+        }
+    }
+
+    #[test]
+    fn prefix_normalized_with_single_slash() {
+        assert_eq!(TypePrefix::new("billing").as_str(), Some("billing/"));
+        assert_eq!(TypePrefix::new("billing/").as_str(), Some("billing/"));
+        assert_eq!(TypePrefix::new("").as_str(), None);
+    }
+
+    #[test]
+    fn apply_then_strip_round_trips() {
+        let prefix = TypePrefix::new("billing");
+        let rel = proto::Relationship {
+            resource: Some(object("invoice", "1")),
+            relation: "viewer".into(),
+            subject: Some(proto::SubjectReference {
+                object: Some(object("user", "alice")),
+                optional_relation: String::new(),
+            }),
+            optional_caveat: None,
+            optional_expires_at: None,
+        };
+
+        let sent = prefix.apply(rel.clone());
+        assert_eq!(
+            sent.resource.as_ref().unwrap().object_type,
+            "billing/invoice"
+        );
+        assert_eq!(
+            sent.subject
+                .as_ref()
+                .unwrap()
+                .object
+                .as_ref()
+                .unwrap()
+                .object_type,
+            "billing/user"
+        );
+        assert_eq!(prefix.strip(sent), rel);
+    }
+
+    #[test]
+    fn strip_leaves_foreign_types_alone() {
+        let prefix = TypePrefix::new("billing");
+        let stripped = prefix.strip(object("shipping/parcel", "1"));
+        assert_eq!(stripped.object_type, "shipping/parcel");
+    }
+
+    #[test]
+    fn empty_filter_type_not_prefixed() {
+        let prefix = TypePrefix::new("billing");
+        let filter = prefix.apply(proto::RelationshipFilter::default());
+        assert!(filter.resource_type.is_empty());
+    }
+}
@@ -0,0 +1,184 @@
+//! Schema reflection via ExperimentalService (behind `experimental` feature).
+
+use crate::error::Error;
+use crate::proto;
+use crate::types::explanation::{schema_rules, Explanation, ExplanationNode};
+use crate::types::{CaveatDefinition, ObjectReference};
+
+use super::Client;
+
+impl Client {
+    /// Reads every caveat definition in the server's schema.
+    ///
+    /// Each [`CaveatDefinition`] carries its parameters' types, ready for
+    /// client-side context validation. Uses SpiceDB's experimental
+    /// `ExperimentalReflectSchema` RPC.
+    pub async fn caveat_definitions(&self) -> Result<Vec<CaveatDefinition>, Error> {
+        let response = self.reflect_schema(vec![]).await?;
+        Ok(response
+            .caveats
+            .into_iter()
+            .map(CaveatDefinition::from)
+            .collect())
+    }
+
+    /// Reads a single caveat definition by name, or `None` if the schema
+    /// has no caveat with that name.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use std::collections::HashMap;
+    /// # use prescience::{Client, ContextValue, ObjectReference, SubjectReference};
+    /// # async fn example(client: &Client, doc: &ObjectReference, alice: &SubjectReference)
+    /// # -> Result<(), prescience::Error> {
+    /// let caveat = client.caveat_definition("ip_allowlist").await?.expect("caveat exists");
+    ///
+    /// let mut ctx = HashMap::new();
+    /// ctx.insert("user_ip".to_string(), ContextValue::String("10.0.0.1".into()));
+    /// let result = client
+    ///     .check_permission(doc, "view", alice)
+    ///     .context_checked(ctx, &caveat)?
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn caveat_definition(
+        &self,
+        name: impl Into<String>,
+    ) -> Result<Option<CaveatDefinition>, Error> {
+        let name = name.into();
+        if name.is_empty() {
+            return Err(Error::InvalidArgument(
+                "caveat name must not be empty".into(),
+            ));
+        }
+
+        // Reflection filters match by prefix, so pick the exact name out.
+        let response = self
+            .reflect_schema(vec![proto::ExpSchemaFilter {
+                optional_caveat_name_filter: name.clone(),
+                ..Default::default()
+            }])
+            .await?;
+        Ok(response
+            .caveats
+            .into_iter()
+            .find(|c| c.name == name)
+            .map(CaveatDefinition::from))
+    }
+
+    /// Checks that `schema` compiles, without writing it.
+    ///
+    /// SpiceDB has no dedicated validation RPC, so this asks the server to
+    /// diff `schema` against the current schema with the experimental
+    /// `ExperimentalDiffSchema` RPC, which parses and type-checks it first.
+    /// A schema that does not compile fails with an `INVALID_ARGUMENT`
+    /// [`Error::Status`] carrying the server's parse error. The server's
+    /// schema is left untouched.
+    ///
+    /// This does not catch everything [`write_schema`](Client::write_schema)
+    /// can reject: removing a relation that still has relationships written
+    /// is only detected on write.
+    ///
+    /// ```rust,no_run
+    /// # use prescience::Client;
+    /// # async fn example(client: &Client) -> Result<(), prescience::Error> {
+    /// let schema = std::fs::read_to_string("schema.zed").unwrap();
+    /// if let Err(err) = client.validate_schema(&schema).await {
+    ///     eprintln!("schema.zed: {}", err);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn validate_schema(&self, schema: impl Into<String>) -> Result<(), Error> {
+        let schema = schema.into();
+        if schema.is_empty() {
+            return Err(Error::InvalidArgument("schema must not be empty".into()));
+        }
+
+        self.authenticated(
+            "ExperimentalService.ExperimentalDiffSchema",
+            &self.experimental,
+            proto::ExperimentalDiffSchemaRequest {
+                consistency: None,
+                comparison_schema: schema,
+            },
+            |mut s, r| async move { s.experimental_diff_schema(r).await },
+        )
+        .await
+        .map_err(Error::from_status_for(
+            "ExperimentalService.ExperimentalDiffSchema",
+        ))?;
+        Ok(())
+    }
+
+    /// Explains how `permission` on `resource` is granted: the expanded
+    /// permission tree, with each node annotated with the relation or
+    /// permission in the schema that produced it.
+    ///
+    /// Combines [`expand_permission_tree`](Client::expand_permission_tree)
+    /// with `ExperimentalReflectSchema`, so it costs two round-trips. Both
+    /// read the server's latest revision; a schema change between them shows
+    /// up as nodes without a [`rule`](crate::ExplanationNode::rule).
+    /// Reflection does not expose permission expressions, so a permission
+    /// node is explained by its children rather than by its formula.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use prescience::{Client, ObjectReference};
+    /// # async fn example(client: &Client) -> Result<(), prescience::Error> {
+    /// let doc = ObjectReference::new("document", "doc-1")?;
+    /// let explanation = client.explain_permission(&doc, "view").await?;
+    /// println!("{}", explanation);
+    /// // document:doc-1#view = union of  (permission)
+    /// //   document:doc-1#viewer = {user:alice, group:eng#member}  (relation: user | group#member)
+    /// //   ...
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn explain_permission(
+        &self,
+        resource: &ObjectReference,
+        permission: impl Into<String>,
+    ) -> Result<Explanation, Error> {
+        let (tree, expanded_at) = self
+            .expand_permission_tree(resource, permission)
+            .expand_proto()
+            .await?;
+        let schema = self.type_prefix.strip(self.reflect_schema(vec![]).await?);
+        let rules = schema_rules(schema.definitions);
+        Ok(Explanation {
+            root: ExplanationNode::from_proto(tree, &rules)?,
+            expanded_at,
+        })
+    }
+
+    /// Reads the names of every definition in the schema via reflection.
+    pub(super) async fn reflected_object_types(&self) -> Result<Vec<String>, Error> {
+        let response = self.reflect_schema(vec![]).await?;
+        Ok(response.definitions.into_iter().map(|d| d.name).collect())
+    }
+
+    pub(super) async fn reflect_schema(
+        &self,
+        optional_filters: Vec<proto::ExpSchemaFilter>,
+    ) -> Result<proto::ExperimentalReflectSchemaResponse, Error> {
+        let response = self
+            .authenticated(
+                "ExperimentalService.ExperimentalReflectSchema",
+                &self.experimental,
+                proto::ExperimentalReflectSchemaRequest {
+                    consistency: None,
+                    optional_filters,
+                },
+                |mut s, r| async move { s.experimental_reflect_schema(r).await },
+            )
+            .await
+            .map_err(Error::from_status_for(
+                "ExperimentalService.ExperimentalReflectSchema",
+            ))?;
+        Ok(response.into_inner())
+    }
+}
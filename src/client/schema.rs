@@ -10,11 +10,35 @@ impl Client {
     /// Reads the current SpiceDB schema.
     ///
     /// Returns the schema text and the ZedToken at which it was read.
+    ///
+    /// SpiceDB's `ReadSchemaRequest` has no consistency field, so the schema
+    /// is always read at the server's latest revision; there is no way to
+    /// ask for the schema as of an earlier token. To read schema and
+    /// relationships without skew, read the schema first and pin the
+    /// relationship reads to the returned token with [`Client::at_snapshot`]:
+    ///
+    /// ```rust,no_run
+    /// # use prescience::{Client, RelationshipFilter};
+    /// # async fn example(client: &Client) -> Result<(), prescience::Error> {
+    /// let (schema, read_at) = client.read_schema().await?;
+    ///
+    /// // Relationships as of the same revision the schema was read at.
+    /// let snapshot = client.at_snapshot(read_at);
+    /// let relationships = snapshot
+    ///     .read_relationships(RelationshipFilter::new("document"))
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
     pub async fn read_schema(&self) -> Result<(String, ZedToken), Error> {
         let response = self
-            .schema
-            .clone()
-            .read_schema(proto::ReadSchemaRequest {})
+            .authenticated(
+                "SchemaService.ReadSchema",
+                &self.schema,
+                proto::ReadSchemaRequest {},
+                |mut s, r| async move { s.read_schema(r).await },
+            )
             .await
             .map_err(Error::from_status)?;
 
@@ -26,6 +50,38 @@ impl Client {
         Ok((inner.schema_text, token))
     }
 
+    /// Confirms the channel is connected and the token is accepted, for use
+    /// in readiness probes.
+    ///
+    /// This issues a single `SchemaService.ReadSchema` call and discards the
+    /// result. SpiceDB serves it from its schema cache without touching
+    /// relationship data, so the cost is one round-trip plus the size of the
+    /// schema text. A `NOT_FOUND` response (no schema written yet) still
+    /// proves the server is reachable and authenticated, so it counts as
+    /// success.
+    ///
+    /// ```rust,no_run
+    /// # use prescience::Client;
+    /// # async fn example(client: &Client) {
+    /// let ready = client.ping().await.is_ok();
+    /// # }
+    /// ```
+    pub async fn ping(&self) -> Result<(), Error> {
+        match self
+            .authenticated(
+                "SchemaService.ReadSchema",
+                &self.schema,
+                proto::ReadSchemaRequest {},
+                |mut s, r| async move { s.read_schema(r).await },
+            )
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(status) if status.code() == tonic::Code::NotFound => Ok(()),
+            Err(status) => Err(Error::from_status(status)),
+        }
+    }
+
     /// Writes (upserts) the SpiceDB schema.
     ///
     /// Returns `Err(InvalidArgument)` if the schema string is empty.
@@ -36,9 +92,12 @@ impl Client {
         }
 
         let response = self
-            .schema
-            .clone()
-            .write_schema(proto::WriteSchemaRequest { schema })
+            .authenticated(
+                "SchemaService.WriteSchema",
+                &self.schema,
+                proto::WriteSchemaRequest { schema },
+                |mut s, r| async move { s.write_schema(r).await },
+            )
             .await
             .map_err(Error::from_status)?;
 
@@ -48,4 +107,49 @@ impl Client {
             .ok_or_else(|| Error::Serialization("missing written_at token".into()))?
             .try_into()
     }
+
+    /// Lists the object types (definition names) in the current schema,
+    /// sorted and without duplicates.
+    ///
+    /// SpiceDB has no RPC for this. With the `experimental` feature the names
+    /// come from schema reflection; without it, or if the server has the
+    /// experimental API disabled, they are scanned from the
+    /// [`read_schema`](Client::read_schema) text. A server with no schema
+    /// written yet yields an empty list.
+    ///
+    /// With a [type prefix](super::ClientBuilder::type_prefix), only types
+    /// under the prefix are listed, with the prefix removed.
+    pub async fn list_object_types(&self) -> Result<Vec<String>, Error> {
+        #[cfg(feature = "experimental")]
+        let names = match self.reflected_object_types().await {
+            Err(Error::Unsupported { .. }) => self.scanned_object_types().await,
+            names => names,
+        };
+        #[cfg(not(feature = "experimental"))]
+        let names = self.scanned_object_types().await;
+
+        let mut names = match names {
+            Ok(names) => names,
+            Err(e) if e.code() == Some(tonic::Code::NotFound) => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        if let Some(prefix) = self.type_prefix.as_str() {
+            names = names
+                .into_iter()
+                .filter_map(|name| name.strip_prefix(prefix).map(str::to_string))
+                .collect();
+        }
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
+    async fn scanned_object_types(&self) -> Result<Vec<String>, Error> {
+        let (schema, _) = self.read_schema().await?;
+        Ok(crate::schema::parse(&schema)?
+            .definitions
+            .into_iter()
+            .map(|definition| definition.name)
+            .collect())
+    }
 }
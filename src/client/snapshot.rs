@@ -0,0 +1,125 @@
+//! Snapshot-pinned view over a [`Client`].
+
+use crate::types::*;
+
+use super::permissions::{
+    CheckPermissionRequest, ExpandPermissionTreeRequest, LookupResourcesRequest,
+    LookupSubjectsRequest, ReadRelationshipsRequest,
+};
+use super::Client;
+
+/// A view over a [`Client`] whose reads default to one exact snapshot.
+///
+/// Created by [`Client::at_snapshot`]. Every read issued through this view is
+/// pre-configured with [`Consistency::AtExactSnapshot`] for the pinned token,
+/// so a unit of work that performs several reads sees one consistent view of
+/// the data without repeating `.consistency(...)` on each call. Calling
+/// `.consistency(...)` on a returned builder still overrides the default.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// # use prescience::{Client, ObjectReference, SubjectReference, ZedToken};
+/// # async fn example(client: &Client, token: ZedToken) -> Result<(), prescience::Error> {
+/// let snap = client.at_snapshot(token);
+/// let doc = ObjectReference::new("document", "doc-123")?;
+/// let alice = SubjectReference::new(ObjectReference::new("user", "alice")?, None::<String>)?;
+///
+/// let can_view = snap.check_permission(&doc, "view", &alice).await?;
+/// let can_edit = snap.check_permission(&doc, "edit", &alice).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SnapshotClient<'a> {
+    client: &'a Client,
+    token: ZedToken,
+}
+
+impl<'a> SnapshotClient<'a> {
+    /// Returns the token all reads are pinned to.
+    pub fn token(&self) -> &ZedToken {
+        &self.token
+    }
+
+    fn consistency(&self) -> Consistency {
+        Consistency::AtExactSnapshot(self.token.clone())
+    }
+
+    /// Like [`Client::check_permission`], at the pinned snapshot.
+    pub fn check_permission(
+        &self,
+        resource: &ObjectReference,
+        permission: impl Into<String>,
+        subject: &SubjectReference,
+    ) -> CheckPermissionRequest<'a> {
+        self.client
+            .check_permission(resource, permission, subject)
+            .consistency(self.consistency())
+    }
+
+    /// Like [`Client::lookup_resources`], at the pinned snapshot.
+    pub fn lookup_resources(
+        &self,
+        resource_type: impl Into<String>,
+        permission: impl Into<String>,
+        subject: &SubjectReference,
+    ) -> LookupResourcesRequest<'a> {
+        self.client
+            .lookup_resources(resource_type, permission, subject)
+            .consistency(self.consistency())
+    }
+
+    /// Like [`Client::lookup_subjects`], at the pinned snapshot.
+    pub fn lookup_subjects(
+        &self,
+        resource: &ObjectReference,
+        permission: impl Into<String>,
+        subject_type: impl Into<String>,
+    ) -> LookupSubjectsRequest<'a> {
+        self.client
+            .lookup_subjects(resource, permission, subject_type)
+            .consistency(self.consistency())
+    }
+
+    /// Like [`Client::read_relationships`], at the pinned snapshot.
+    pub fn read_relationships(&self, filter: RelationshipFilter) -> ReadRelationshipsRequest<'a> {
+        self.client
+            .read_relationships(filter)
+            .consistency(self.consistency())
+    }
+
+    /// Like [`Client::expand_permission_tree`], at the pinned snapshot.
+    pub fn expand_permission_tree(
+        &self,
+        resource: &ObjectReference,
+        permission: impl Into<String>,
+    ) -> ExpandPermissionTreeRequest<'a> {
+        self.client
+            .expand_permission_tree(resource, permission)
+            .consistency(self.consistency())
+    }
+
+    /// Like [`Client::bulk_check_permissions`], at the pinned snapshot.
+    #[cfg(feature = "experimental")]
+    pub fn bulk_check_permissions(
+        &self,
+        items: Vec<super::experimental::BulkCheckItem>,
+    ) -> super::experimental::BulkCheckPermissionsRequest<'a> {
+        self.client
+            .bulk_check_permissions(items)
+            .consistency(self.consistency())
+    }
+}
+
+impl Client {
+    /// Returns a view whose reads default to `AtExactSnapshot(token)`.
+    ///
+    /// The view borrows this client; creating it is cheap.
+    pub fn at_snapshot(&self, token: ZedToken) -> SnapshotClient<'_> {
+        SnapshotClient {
+            client: self,
+            token,
+        }
+    }
+}
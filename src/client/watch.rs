@@ -1,19 +1,31 @@
 //! WatchService RPC implementation (behind `watch` feature).
 
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
 use futures_core::Stream;
+use tokio::time::Sleep;
 use tokio_stream::StreamExt;
 
 use crate::error::Error;
 use crate::proto;
-use crate::types::{WatchEvent, ZedToken};
+use crate::types::{RelationshipFilter, WatchEvent, ZedToken};
 
 use super::Client;
 
 /// Builder for a Watch streaming request.
+#[derive(Clone)]
 pub struct WatchRequest<'a> {
     client: &'a Client,
     object_types: Vec<String>,
+    relationship_filters: Vec<proto::RelationshipFilter>,
     start_cursor: Option<proto::ZedToken>,
+    initial_checkpoint: bool,
+    checkpoints: bool,
+    duration: Option<Duration>,
 }
 
 impl<'a> WatchRequest<'a> {
@@ -23,31 +35,186 @@ impl<'a> WatchRequest<'a> {
         self
     }
 
+    /// Makes the stream start with an event that has no updates, whose
+    /// checkpoint is the revision the watch starts from.
+    ///
+    /// Use it to snapshot-then-watch without a gap: read at
+    /// [`Consistency::AtExactSnapshot`](crate::Consistency::AtExactSnapshot)
+    /// of that checkpoint, then apply the events that follow, which contain
+    /// exactly the changes made after it. With
+    /// [`after_token`](Self::after_token), the checkpoint is that token.
+    /// Otherwise `send` first reads the schema to learn a recent revision
+    /// and starts the watch there, so a schema must have been written.
+    pub fn with_initial_checkpoint(mut self, enabled: bool) -> Self {
+        self.initial_checkpoint = enabled;
+        self
+    }
+
+    /// Asks SpiceDB to also send checkpoint-only events: events with no
+    /// updates whose checkpoint advances while nothing matching the watch
+    /// changes.
+    ///
+    /// Without this, the checkpoint only moves when a change arrives, so a
+    /// cursor persisted from [`WatchEvent::checkpoint`] falls behind during
+    /// quiet periods and resuming from it replays a long stretch of
+    /// history. Store the checkpoint of every event, including those where
+    /// [`WatchEvent::is_checkpoint_only`] is `true`.
+    pub fn with_checkpoints(mut self, enabled: bool) -> Self {
+        self.checkpoints = enabled;
+        self
+    }
+
+    /// Ends the stream cleanly (with `None`, not an error) once `duration`
+    /// has elapsed since [`send`](Self::send) returned.
+    ///
+    /// For tailing changes in fixed windows: resume the next window from
+    /// [`WatchStream::last_checkpoint`] so no change is missed or repeated.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use prescience::Client;
+    /// # async fn example(client: &Client) -> Result<(), prescience::Error> {
+    /// use std::time::Duration;
+    /// use tokio_stream::StreamExt;
+    ///
+    /// let mut resume_from = None;
+    /// loop {
+    ///     let mut request = client.watch(vec!["document"]).for_duration(Duration::from_secs(60));
+    ///     if let Some(token) = resume_from.take() {
+    ///         request = request.after_token(token);
+    ///     }
+    ///     let mut stream = request.send().await?;
+    ///     let mut updates = vec![];
+    ///     while let Some(event) = stream.next().await {
+    ///         updates.extend(event?.updates);
+    ///     }
+    ///     println!("{} updates in this window", updates.len());
+    ///     resume_from = stream.last_checkpoint().cloned();
+    /// }
+    /// # }
+    /// ```
+    pub fn for_duration(mut self, duration: Duration) -> Self {
+        self.duration = Some(duration);
+        self
+    }
+
     /// Sends the request and returns a long-lived stream of watch events.
     ///
     /// The stream does NOT auto-reconnect. On server disconnect, it yields
     /// `Err(Error::Status { code: UNAVAILABLE, .. })` then terminates.
     /// Use the checkpoint `ZedToken` from the last `WatchEvent` to resume.
-    pub async fn send(self) -> Result<impl Stream<Item = Result<WatchEvent, Error>>, Error> {
+    pub async fn send(self) -> Result<WatchStream, Error> {
+        let mut start_cursor = self.start_cursor;
+        let mut initial = None;
+        if self.initial_checkpoint {
+            let checkpoint = match &start_cursor {
+                Some(cursor) => cursor.clone().try_into()?,
+                None => {
+                    let (_, read_at) = self.client.read_schema().await?;
+                    start_cursor = Some((&read_at).into());
+                    read_at
+                }
+            };
+            initial = Some(Ok(WatchEvent {
+                updates: vec![],
+                checkpoint,
+                metadata: None,
+            }));
+        }
+
         let req = proto::WatchRequest {
             optional_object_types: self.object_types,
-            optional_start_cursor: self.start_cursor,
-            optional_relationship_filters: vec![],
-            optional_update_kinds: vec![],
+            optional_start_cursor: start_cursor,
+            optional_relationship_filters: self.relationship_filters,
+            optional_update_kinds: if self.checkpoints {
+                vec![
+                    proto::WatchKind::IncludeRelationshipUpdates as i32,
+                    proto::WatchKind::IncludeCheckpoints as i32,
+                ]
+            } else {
+                vec![]
+            },
         };
 
         let response = self
             .client
-            .watch
-            .clone()
-            .watch(req)
+            .authenticated(
+                "WatchService.Watch",
+                &self.client.watch,
+                self.client.type_prefix.apply(req),
+                |mut s, r| async move { s.watch(r).await },
+            )
             .await
-            .map_err(Error::from_status)?;
+            .map_err(Error::from_status_for("WatchService.Watch"))?;
+
+        let type_prefix = self.client.type_prefix.clone();
+        let events = self
+            .client
+            .stream_monitor()
+            .observe(response.into_inner())
+            .map(move |r| match r {
+                Ok(proto) => WatchEvent::from_proto(type_prefix.strip(proto)),
+                Err(status) => Err(Error::from_status(status)),
+            });
+        Ok(WatchStream {
+            inner: Box::pin(tokio_stream::iter(initial).chain(events)),
+            deadline: self.duration.map(|d| Box::pin(tokio::time::sleep(d))),
+            last_checkpoint: None,
+            done: false,
+        })
+    }
+}
 
-        Ok(response.into_inner().map(|r| match r {
-            Ok(proto) => WatchEvent::from_proto(proto),
-            Err(status) => Err(Error::from_status(status)),
-        }))
+/// The stream of events returned by sending a [`Client::watch`] request.
+///
+/// Besides yielding events, it remembers the checkpoint of the last one it
+/// delivered, so a watch that ended (by `for_duration` or an error) can be
+/// resumed with `after_token`.
+pub struct WatchStream {
+    inner: Pin<Box<dyn Stream<Item = Result<WatchEvent, Error>> + Send>>,
+    deadline: Option<Pin<Box<Sleep>>>,
+    last_checkpoint: Option<ZedToken>,
+    done: bool,
+}
+
+impl WatchStream {
+    /// The checkpoint of the last event this stream delivered, or `None` if
+    /// it has delivered none yet.
+    pub fn last_checkpoint(&self) -> Option<&ZedToken> {
+        self.last_checkpoint.as_ref()
+    }
+}
+
+impl fmt::Debug for WatchStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WatchStream")
+            .field("last_checkpoint", &self.last_checkpoint)
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Stream for WatchStream {
+    type Item = Result<WatchEvent, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+        if let Some(deadline) = &mut self.deadline {
+            if deadline.as_mut().poll(cx).is_ready() {
+                self.done = true;
+                return Poll::Ready(None);
+            }
+        }
+        let item = std::task::ready!(self.inner.as_mut().poll_next(cx));
+        match &item {
+            Some(Ok(event)) => self.last_checkpoint = Some(event.checkpoint.clone()),
+            Some(Err(_)) => {}
+            None => self.done = true,
+        }
+        Poll::Ready(item)
     }
 }
 
@@ -80,7 +247,104 @@ impl Client {
         WatchRequest {
             client: self,
             object_types: object_types.into_iter().map(Into::into).collect(),
+            relationship_filters: vec![],
+            start_cursor: None,
+            initial_checkpoint: false,
+            checkpoints: false,
+            duration: None,
+        }
+    }
+
+    /// Watches for relationship changes matching any of `filters`, so one
+    /// stream can follow different relations on different types.
+    ///
+    /// This is the filtered counterpart of [`watch`](Self::watch) and
+    /// replaces its object types: SpiceDB does not accept both in one
+    /// watch, so a filter with only a resource type
+    /// (`RelationshipFilter::new("document")`) stands in for an object type.
+    /// An empty vec watches all types. Returns the same streaming builder.
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// # use prescience::Client;
+    /// # async fn example(client: &Client) -> Result<(), prescience::Error> {
+    /// use prescience::RelationshipFilter;
+    ///
+    /// // Every change to documents, but only membership changes to groups.
+    /// let stream = client
+    ///     .watch_filtered(vec![
+    ///         RelationshipFilter::new("document"),
+    ///         RelationshipFilter::new("group").relation("member"),
+    ///     ])
+    ///     .send()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn watch_filtered(&self, filters: Vec<RelationshipFilter>) -> WatchRequest<'_> {
+        WatchRequest {
+            client: self,
+            object_types: vec![],
+            relationship_filters: filters.iter().map(Into::into).collect(),
             start_cursor: None,
+            initial_checkpoint: false,
+            checkpoints: false,
+            duration: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stream(events: Vec<Result<WatchEvent, Error>>, duration: Option<Duration>) -> WatchStream {
+        WatchStream {
+            inner: Box::pin(tokio_stream::iter(events).chain(tokio_stream::pending())),
+            deadline: duration.map(|d| Box::pin(tokio::time::sleep(d))),
+            last_checkpoint: None,
+            done: false,
         }
     }
+
+    fn event(token: &str) -> Result<WatchEvent, Error> {
+        Ok(WatchEvent {
+            updates: vec![],
+            checkpoint: ZedToken::new(token).unwrap(),
+            metadata: None,
+        })
+    }
+
+    #[tokio::test]
+    async fn for_duration_ends_cleanly_and_keeps_last_checkpoint() {
+        let mut stream = stream(
+            vec![event("t1"), event("t2")],
+            Some(Duration::from_millis(50)),
+        );
+        assert!(stream.last_checkpoint().is_none());
+
+        assert!(stream.next().await.unwrap().is_ok());
+        assert!(stream.next().await.unwrap().is_ok());
+        assert!(stream.next().await.is_none());
+        assert!(stream.next().await.is_none());
+        assert_eq!(
+            stream.last_checkpoint(),
+            Some(&ZedToken::new("t2").unwrap())
+        );
+    }
+
+    #[tokio::test]
+    async fn errors_do_not_replace_last_checkpoint() {
+        let mut stream = stream(
+            vec![event("t1"), Err(Error::Serialization("bad".into()))],
+            Some(Duration::from_millis(50)),
+        );
+        assert!(stream.next().await.unwrap().is_ok());
+        assert!(stream.next().await.unwrap().is_err());
+        assert_eq!(
+            stream.last_checkpoint(),
+            Some(&ZedToken::new("t1").unwrap())
+        );
+    }
 }
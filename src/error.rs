@@ -2,7 +2,7 @@
 //!
 //! The [`Error`] enum provides structured, matchable error variants covering
 //! transport failures, gRPC status errors, local validation, serialization,
-//! and conditional permission handling.
+//! and denied or conditional permission checks.
 //!
 //! ## gRPC Status Code Mapping
 //!
@@ -16,11 +16,31 @@
 //! | `ALREADY_EXISTS` | Relationship already exists (with Create) | No |
 //! | `UNAVAILABLE` | Server temporarily unavailable | Yes |
 //! | `DEADLINE_EXCEEDED` | Request timed out | Yes |
+//!
+//! `UNIMPLEMENTED` from a watch, bulk, or experimental RPC is surfaced as
+//! [`Error::Unsupported`] naming the RPC, since it means the server has that
+//! API disabled or predates it. A status whose SpiceDB reason is
+//! `ERROR_REASON_MAXIMUM_DEPTH_EXCEEDED`, from any RPC, is surfaced as
+//! [`Error::MaximumDepthExceeded`], and `UNAUTHENTICATED` as
+//! [`Error::Unauthenticated`]. A read at
+//! [`Consistency::AtExactSnapshot`](crate::Consistency::AtExactSnapshot) of a
+//! revision the server has garbage-collected is surfaced as
+//! [`Error::RevisionUnavailable`].
 
+use std::collections::BTreeMap;
 use std::time::Duration;
 
+use prost::Message;
+
+use crate::proto;
+use crate::types::{ObjectReference, SubjectReference, ZedToken};
+
 /// Details extracted from SpiceDB-specific gRPC error metadata.
+///
+/// Decoded from the `grpc-status-details-bin` trailer, which carries a
+/// `google.rpc.Status` with `ErrorInfo`, `DebugInfo`, and `RetryInfo` entries.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub struct SpiceDbErrorDetails {
     /// SpiceDB ErrorReason enum value, if present.
     pub error_reason: Option<String>,
@@ -28,8 +48,94 @@ pub struct SpiceDbErrorDetails {
     pub debug_message: Option<String>,
     /// Suggested retry delay, if the server provided one.
     pub retry_info: Option<Duration>,
+    /// Key-value metadata from the server's `ErrorInfo` (for example
+    /// `definition_name` or `relationship`).
+    pub metadata: BTreeMap<String, String>,
+    /// What a failed write or delete was attributed to. Boxed, and only
+    /// filled in on the error path, to keep `Error` small.
+    attribution: Option<Box<Attribution>>,
+}
+
+/// The parts of a failed request that a [`SpiceDbErrorDetails`] points at.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+struct Attribution {
+    failed_update: Option<FailedUpdate>,
+    failed_precondition: Option<usize>,
+}
+
+/// The update within a `write_relationships` call that caused it to fail.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FailedUpdate {
+    /// Index of the update in the list passed to the write.
+    pub index: usize,
+    /// The update's relationship as a tuple string, e.g.
+    /// `document:doc-1#viewer@user:alice`.
+    pub relationship: String,
+}
+
+impl SpiceDbErrorDetails {
+    /// For a failed write, the update the server's error points at, if it
+    /// could be identified.
+    pub fn failed_update(&self) -> Option<&FailedUpdate> {
+        self.attribution.as_ref()?.failed_update.as_ref()
+    }
+
+    /// For a write or delete that failed a precondition, the index of that
+    /// precondition in the list passed to the request, if it could be
+    /// identified.
+    pub fn failed_precondition(&self) -> Option<usize> {
+        self.attribution.as_ref()?.failed_precondition
+    }
+
+    fn attribution_mut(&mut self) -> &mut Attribution {
+        self.attribution.get_or_insert_with(Default::default)
+    }
+
+    /// Decodes the details carried in a status, or `None` if there are none.
+    fn from_status(status: &tonic::Status) -> Option<Self> {
+        let bytes = status.details();
+        if bytes.is_empty() {
+            return None;
+        }
+        let rpc_status = proto::google::rpc::Status::decode(bytes).ok()?;
+
+        let mut details = SpiceDbErrorDetails {
+            error_reason: None,
+            debug_message: None,
+            retry_info: None,
+            metadata: BTreeMap::new(),
+            attribution: None,
+        };
+        for any in rpc_status.details {
+            match any.type_url.rsplit('/').next() {
+                Some("google.rpc.ErrorInfo") => {
+                    if let Ok(info) = proto::google::rpc::ErrorInfo::decode(any.value.as_slice()) {
+                        details.error_reason = Some(info.reason).filter(|r| !r.is_empty());
+                        details.metadata = info.metadata;
+                    }
+                }
+                Some("google.rpc.DebugInfo") => {
+                    if let Ok(info) = proto::google::rpc::DebugInfo::decode(any.value.as_slice()) {
+                        details.debug_message = Some(info.detail).filter(|d| !d.is_empty());
+                    }
+                }
+                Some("google.rpc.RetryInfo") => {
+                    if let Ok(info) = proto::google::rpc::RetryInfo::decode(any.value.as_slice()) {
+                        details.retry_info =
+                            info.retry_delay.and_then(|d| Duration::try_from(d).ok());
+                    }
+                }
+                _ => {}
+            }
+        }
+        Some(details)
+    }
 }
 
+/// The gRPC codes [`Error::is_retryable`] treats as transient.
+pub const DEFAULT_RETRYABLE_CODES: &[tonic::Code] =
+    &[tonic::Code::Unavailable, tonic::Code::DeadlineExceeded];
+
 /// Errors returned by the Prescience SpiceDB client.
 ///
 /// All public methods return `Result<T, Error>`. Use pattern matching
@@ -44,7 +150,13 @@ pub enum Error {
 
     /// gRPC status errors returned by SpiceDB. Includes the status code,
     /// human-readable message, and optionally decoded SpiceDB-specific error details.
-    #[error("SpiceDB error ({code:?}): {message}")]
+    ///
+    /// `Display` appends the details' `debug_message`, when present, as
+    /// `(debug: ...)`.
+    #[error(
+        "SpiceDB error ({code:?}): {message}{}",
+        debug_suffix(message, details)
+    )]
     Status {
         /// The gRPC status code.
         code: tonic::Code,
@@ -54,6 +166,23 @@ pub enum Error {
         details: Option<SpiceDbErrorDetails>,
     },
 
+    /// SpiceDB rejected the credentials (`UNAUTHENTICATED`): the bearer
+    /// token is missing or does not match the server's preshared key.
+    ///
+    /// `Display` names the token as the likely cause and appends the
+    /// SpiceDB `error_reason`, when present, as `(reason: ...)`.
+    #[error(
+        "SpiceDB rejected the bearer token; check that it matches the server's \
+         preshared key: {message}{}",
+        reason_suffix(details)
+    )]
+    Unauthenticated {
+        /// Human-readable error message from the server.
+        message: String,
+        /// Decoded SpiceDB-specific error details, if available.
+        details: Option<Box<SpiceDbErrorDetails>>,
+    },
+
     /// Local validation failures before a request is sent.
     ///
     /// Examples: empty `object_type`, empty `object_id`, empty schema string,
@@ -73,37 +202,769 @@ pub enum Error {
         /// The context fields that were missing, preventing full caveat evaluation.
         missing_fields: Vec<String>,
     },
+
+    /// Returned by [`Client::require_permission`](crate::Client::require_permission)
+    /// when the check is definitively denied. Carries the checked references
+    /// so handlers can log them and map the error to a 403-style response.
+    ///
+    /// Not to be confused with a `Status` error with code
+    /// `PERMISSION_DENIED`, which means the token itself lacks access.
+    #[error("permission denied: {subject} does not have {permission} on {resource}")]
+    PermissionDenied {
+        /// The resource that was checked. Boxed to keep `Error` small.
+        resource: Box<ObjectReference>,
+        /// The permission that was checked.
+        permission: String,
+        /// The subject that was checked. Boxed to keep `Error` small.
+        subject: Box<SubjectReference>,
+    },
+
+    /// SpiceDB stopped resolving the request because the permission graph is
+    /// deeper than its dispatch depth limit (`--dispatch-max-depth`, 50 by
+    /// default), usually because of a recursive relation or a very deep
+    /// hierarchy. The limit is server-side; no request field lowers it.
+    #[error("SpiceDB error: maximum depth exceeded: {message}")]
+    MaximumDepthExceeded {
+        /// The gRPC status code the server sent with the error.
+        code: tonic::Code,
+        /// The server's depth limit, if it reported one.
+        max_depth: Option<u32>,
+        /// Human-readable error message from the server.
+        message: String,
+        /// Decoded SpiceDB-specific error details. Boxed to keep `Error`
+        /// small.
+        details: Box<SpiceDbErrorDetails>,
+    },
+
+    /// A read at [`Consistency::AtExactSnapshot`](crate::Consistency::AtExactSnapshot)
+    /// named a revision SpiceDB no longer has, usually because it is older
+    /// than the datastore's garbage-collection window (24 hours by default).
+    ///
+    /// Retrying with the same token cannot succeed. Fall back to a fresher
+    /// consistency such as `FullyConsistent`, or obtain a new token.
+    ///
+    /// SpiceDB reports this as `OUT_OF_RANGE` without a dedicated error
+    /// reason, so it is recognized by that code, together with a message
+    /// about the revision or zedtoken, on reads that requested an exact
+    /// snapshot.
+    #[error("SpiceDB revision unavailable: {message}")]
+    RevisionUnavailable {
+        /// The token whose snapshot was requested.
+        token: ZedToken,
+        /// Human-readable error message from the server.
+        message: String,
+    },
+
+    /// A relationship counter read with `Client::count` has been registered
+    /// but SpiceDB has not finished computing it yet.
+    ///
+    /// This is not a transport failure, so [`Error::is_retryable`] does not
+    /// report it; poll again after a delay.
+    #[error("relationship counter {name:?} is still calculating")]
+    CounterStillCalculating {
+        /// The counter's name.
+        name: String,
+    },
+
+    /// The server answered `UNIMPLEMENTED` for an RPC behind a feature flag
+    /// (watch, bulk, or experimental APIs): it is disabled on the server or
+    /// the server is too old to have it.
+    #[error("SpiceDB does not support {rpc}: {}", unsupported_hint(rpc))]
+    Unsupported {
+        /// The RPC the server rejected, e.g. `"WatchService.Watch"`.
+        rpc: &'static str,
+    },
+}
+
+/// Formats the server's debug message for `Display`, unless there is none or
+/// it just repeats `message`.
+fn debug_suffix(message: &str, details: &Option<SpiceDbErrorDetails>) -> String {
+    match details.as_ref().and_then(|d| d.debug_message.as_deref()) {
+        Some(debug) if debug != message => format!(" (debug: {})", debug),
+        _ => String::new(),
+    }
+}
+
+/// Formats the server's error reason for `Display`, if it sent one.
+fn reason_suffix(details: &Option<Box<SpiceDbErrorDetails>>) -> String {
+    match details.as_ref().and_then(|d| d.error_reason.as_deref()) {
+        Some(reason) => format!(" (reason: {})", reason),
+        None => String::new(),
+    }
+}
+
+fn unsupported_hint(rpc: &str) -> &'static str {
+    if rpc.starts_with("WatchService") {
+        "enable the WatchService in the SpiceDB config (the datastore must support watch)"
+    } else if rpc.starts_with("ExperimentalService") {
+        "the server may be too old or have experimental APIs disabled"
+    } else {
+        "the server may be too old for this RPC; upgrade SpiceDB"
+    }
 }
 
 impl Error {
     /// Returns `true` if this error is likely transient and the request may
     /// succeed if retried.
     ///
-    /// Currently considers `UNAVAILABLE` and `DEADLINE_EXCEEDED` as retryable.
+    /// Currently considers `UNAVAILABLE` and `DEADLINE_EXCEEDED` as retryable
+    /// (see [`DEFAULT_RETRYABLE_CODES`]).
+    ///
+    /// The client never retries on its own, so it has no retry budget or
+    /// circuit breaker either. If you retry on this, cap the aggregate retry
+    /// rate across the client (e.g. a token bucket shared by all callers)
+    /// and stop retrying for a cooldown after repeated `UNAVAILABLE`
+    /// errors; unbounded retries during an outage multiply the load on a
+    /// SpiceDB that is already struggling.
     pub fn is_retryable(&self) -> bool {
-        matches!(
-            self,
-            Error::Status {
-                code: tonic::Code::Unavailable | tonic::Code::DeadlineExceeded,
-                ..
-            }
-        )
+        self.is_retryable_with(DEFAULT_RETRYABLE_CODES)
     }
 
-    /// Returns the gRPC status code if this is a `Status` error.
+    /// Returns `true` if this is a `Status` error whose code is in `codes`.
+    ///
+    /// Use this when your deployment needs a different retryable set than
+    /// [`Error::is_retryable`], e.g. behind a proxy that surfaces transient
+    /// failures as `RESOURCE_EXHAUSTED` or `INTERNAL`:
+    ///
+    /// ```
+    /// use prescience::error::DEFAULT_RETRYABLE_CODES;
+    /// use prescience::Error;
+    ///
+    /// let codes = [DEFAULT_RETRYABLE_CODES, &[tonic::Code::ResourceExhausted]].concat();
+    /// let err = Error::Status {
+    ///     code: tonic::Code::ResourceExhausted,
+    ///     message: "rate limited".into(),
+    ///     details: None,
+    /// };
+    /// assert!(!err.is_retryable());
+    /// assert!(err.is_retryable_with(&codes));
+    /// ```
+    pub fn is_retryable_with(&self, codes: &[tonic::Code]) -> bool {
+        self.code().is_some_and(|code| codes.contains(&code))
+    }
+
+    /// Returns the gRPC status code if this is a `Status`,
+    /// `Unauthenticated`, or `MaximumDepthExceeded` error.
     pub fn code(&self) -> Option<tonic::Code> {
         match self {
-            Error::Status { code, .. } => Some(*code),
+            Error::Status { code, .. } | Error::MaximumDepthExceeded { code, .. } => Some(*code),
+            Error::Unauthenticated { .. } => Some(tonic::Code::Unauthenticated),
+            _ => None,
+        }
+    }
+
+    /// Returns the gRPC status code as a stable snake_case string (e.g.
+    /// `"unavailable"`, `"failed_precondition"`) if this is a `Status` error.
+    ///
+    /// Meant for structured logging, where `tonic::Code`'s `Debug` form is
+    /// not a good field value. Use [`Error::code`] for matching.
+    ///
+    /// ```
+    /// use prescience::Error;
+    ///
+    /// let err = Error::Status {
+    ///     code: tonic::Code::FailedPrecondition,
+    ///     message: "precondition failed".into(),
+    ///     details: None,
+    /// };
+    /// assert_eq!(err.code_str(), Some("failed_precondition"));
+    /// ```
+    pub fn code_str(&self) -> Option<&'static str> {
+        use tonic::Code;
+
+        self.code().map(|code| match code {
+            Code::Ok => "ok",
+            Code::Cancelled => "cancelled",
+            Code::Unknown => "unknown",
+            Code::InvalidArgument => "invalid_argument",
+            Code::DeadlineExceeded => "deadline_exceeded",
+            Code::NotFound => "not_found",
+            Code::AlreadyExists => "already_exists",
+            Code::PermissionDenied => "permission_denied",
+            Code::ResourceExhausted => "resource_exhausted",
+            Code::FailedPrecondition => "failed_precondition",
+            Code::Aborted => "aborted",
+            Code::OutOfRange => "out_of_range",
+            Code::Unimplemented => "unimplemented",
+            Code::Internal => "internal",
+            Code::Unavailable => "unavailable",
+            Code::DataLoss => "data_loss",
+            Code::Unauthenticated => "unauthenticated",
+        })
+    }
+
+    /// Returns the decoded SpiceDB error details if this is a `Status` or
+    /// `Unauthenticated` error that carried them, or a
+    /// `MaximumDepthExceeded` error.
+    pub fn details(&self) -> Option<&SpiceDbErrorDetails> {
+        match self {
+            Error::Status { details, .. } => details.as_ref(),
+            Error::Unauthenticated { details, .. } => details.as_deref(),
+            Error::MaximumDepthExceeded { details, .. } => Some(details),
             _ => None,
         }
     }
 
     pub(crate) fn from_status(status: tonic::Status) -> Self {
-        // TODO: decode SpiceDB-specific error details from status metadata
+        let details = match SpiceDbErrorDetails::from_status(&status) {
+            Some(details)
+                if details.error_reason.as_deref() == Some(MAXIMUM_DEPTH_EXCEEDED_REASON) =>
+            {
+                return Error::MaximumDepthExceeded {
+                    code: status.code(),
+                    max_depth: details
+                        .metadata
+                        .get("maximum_depth_allowed")
+                        .and_then(|d| d.parse().ok()),
+                    message: status.message().to_string(),
+                    details: Box::new(details),
+                };
+            }
+            details => details,
+        };
+        if status.code() == tonic::Code::Unauthenticated {
+            return Error::Unauthenticated {
+                message: status.message().to_string(),
+                details: details.map(Box::new),
+            };
+        }
         Error::Status {
             code: status.code(),
             message: status.message().to_string(),
-            details: None,
+            details,
+        }
+    }
+
+    /// Like [`Error::from_status`], but maps `UNIMPLEMENTED` to
+    /// [`Error::Unsupported`] naming `rpc`.
+    #[cfg_attr(
+        not(any(feature = "watch", feature = "experimental")),
+        allow(dead_code)
+    )]
+    pub(crate) fn from_status_for(rpc: &'static str) -> impl Fn(tonic::Status) -> Self {
+        move |status| {
+            if status.code() == tonic::Code::Unimplemented {
+                Error::Unsupported { rpc }
+            } else {
+                Error::from_status(status)
+            }
         }
     }
+
+    /// Turns a `Status` error for a read at the exact snapshot `exact` into
+    /// [`Error::RevisionUnavailable`] if the server no longer has that
+    /// revision. Errors for reads at other consistencies pass through.
+    pub(crate) fn with_snapshot(self, exact: Option<&ZedToken>) -> Self {
+        match (self, exact) {
+            (
+                Error::Status {
+                    code: tonic::Code::OutOfRange,
+                    message,
+                    ..
+                },
+                Some(token),
+            ) if names_revision(&message) => Error::RevisionUnavailable {
+                token: token.clone(),
+                message,
+            },
+            (err, _) => err,
+        }
+    }
+
+    /// Attributes a failed write to one of its `updates`, using the
+    /// relationship or definition named in the server's error metadata.
+    pub(crate) fn with_failed_update(mut self, updates: &[proto::RelationshipUpdate]) -> Self {
+        if let Error::Status {
+            details: Some(details),
+            ..
+        } = &mut self
+        {
+            if let Some(failed) = locate_failed_update(&details.metadata, updates) {
+                details.attribution_mut().failed_update = Some(failed);
+            }
+        }
+        self
+    }
+
+    /// Attributes a precondition failure to one of `preconditions`, using
+    /// the filter described in the server's error metadata.
+    pub(crate) fn with_failed_precondition(
+        mut self,
+        preconditions: &[proto::Precondition],
+    ) -> Self {
+        if let Error::Status {
+            details: Some(details),
+            ..
+        } = &mut self
+        {
+            if let Some(index) = locate_failed_precondition(details, preconditions) {
+                details.attribution_mut().failed_precondition = Some(index);
+            }
+        }
+        self
+    }
+}
+
+/// Whether an `OUT_OF_RANGE` message is about the requested revision,
+/// e.g. `invalid zedtoken: revision has expired`.
+fn names_revision(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    message.contains("revision") || message.contains("zedtoken")
+}
+
+/// The ErrorInfo reason SpiceDB sends when dispatch exceeds its depth limit.
+const MAXIMUM_DEPTH_EXCEEDED_REASON: &str = "ERROR_REASON_MAXIMUM_DEPTH_EXCEEDED";
+
+/// The ErrorInfo reason SpiceDB sends when a write or delete precondition
+/// is not met.
+const PRECONDITION_FAILURE_REASON: &str = "ERROR_REASON_WRITE_OR_DELETE_PRECONDITION_FAILURE";
+
+fn locate_failed_precondition(
+    details: &SpiceDbErrorDetails,
+    preconditions: &[proto::Precondition],
+) -> Option<usize> {
+    if details.error_reason.as_deref() != Some(PRECONDITION_FAILURE_REASON) {
+        return None;
+    }
+
+    // SpiceDB describes the failing precondition with `precondition_*` keys,
+    // omitting fields the filter left empty.
+    let field = |key: &str| details.metadata.get(key).map(String::as_str);
+    let matches = |p: &proto::Precondition| {
+        let operation = proto::precondition::Operation::try_from(p.operation)
+            .map(|op| op.as_str_name())
+            .ok();
+        if field("precondition_operation").is_some_and(|op| Some(op) != operation) {
+            return false;
+        }
+        let Some(filter) = &p.filter else {
+            return false;
+        };
+        let subject = filter.optional_subject_filter.as_ref();
+        let expected = [
+            (
+                "precondition_resource_type",
+                Some(filter.resource_type.as_str()),
+            ),
+            (
+                "precondition_resource_id",
+                Some(filter.optional_resource_id.as_str()),
+            ),
+            (
+                "precondition_resource_id_prefix",
+                Some(filter.optional_resource_id_prefix.as_str()),
+            ),
+            (
+                "precondition_relation",
+                Some(filter.optional_relation.as_str()),
+            ),
+            (
+                "precondition_subject_type",
+                subject.map(|s| s.subject_type.as_str()),
+            ),
+            (
+                "precondition_subject_id",
+                subject.map(|s| s.optional_subject_id.as_str()),
+            ),
+            (
+                "precondition_subject_relation",
+                subject
+                    .and_then(|s| s.optional_relation.as_ref())
+                    .map(|r| r.relation.as_str()),
+            ),
+        ];
+        expected
+            .iter()
+            .all(|(key, value)| field(key).is_none_or(|named| Some(named) == *value))
+    };
+
+    let has_description = details
+        .metadata
+        .keys()
+        .any(|k| k.starts_with("precondition_"));
+    if has_description {
+        preconditions.iter().position(matches)
+    } else if preconditions.len() == 1 {
+        Some(0)
+    } else {
+        None
+    }
+}
+
+fn locate_failed_update(
+    metadata: &BTreeMap<String, String>,
+    updates: &[proto::RelationshipUpdate],
+) -> Option<FailedUpdate> {
+    let tuples: Vec<Option<String>> = updates
+        .iter()
+        .map(|u| u.relationship.as_ref().map(tuple_string))
+        .collect();
+    let found = |index: usize| {
+        tuples[index].clone().map(|relationship| FailedUpdate {
+            index,
+            relationship,
+        })
+    };
+
+    // Most specific: the server names the exact relationship. Any caveat
+    // suffix (`[caveat]`) is ignored when comparing.
+    if let Some(named) = metadata.get("relationship") {
+        let named = named.split('[').next().unwrap_or(named);
+        if let Some(index) = tuples.iter().position(|t| t.as_deref() == Some(named)) {
+            return found(index);
+        }
+    }
+
+    // Otherwise fall back to the first update touching the named definition
+    // and relation, or using the named caveat.
+    let definition = metadata.get("definition_name");
+    let relation = [
+        "relation_or_permission_name",
+        "relation_name",
+        "permission_name",
+    ]
+    .iter()
+    .find_map(|k| metadata.get(*k));
+    if let (Some(definition), Some(relation)) = (definition, relation) {
+        let index = updates.iter().position(|u| {
+            u.relationship.as_ref().is_some_and(|r| {
+                &r.relation == relation
+                    && r.resource
+                        .as_ref()
+                        .is_some_and(|o| &o.object_type == definition)
+            })
+        });
+        if let Some(index) = index {
+            return found(index);
+        }
+    }
+    if let Some(caveat) = metadata.get("caveat_name") {
+        let index = updates.iter().position(|u| {
+            u.relationship
+                .as_ref()
+                .and_then(|r| r.optional_caveat.as_ref())
+                .is_some_and(|c| &c.caveat_name == caveat)
+        });
+        if let Some(index) = index {
+            return found(index);
+        }
+    }
+    None
+}
+
+/// Formats a relationship the way SpiceDB does in error messages:
+/// `type:id#relation@subject_type:subject_id[#subject_relation]`.
+pub(crate) fn tuple_string(r: &proto::Relationship) -> String {
+    let (resource_type, resource_id) = r
+        .resource
+        .as_ref()
+        .map(|o| (o.object_type.as_str(), o.object_id.as_str()))
+        .unwrap_or_default();
+    let mut s = format!("{}:{}#{}", resource_type, resource_id, r.relation);
+    if let Some(subject) = &r.subject {
+        if let Some(object) = &subject.object {
+            s.push_str(&format!("@{}:{}", object.object_type, object.object_id));
+        }
+        if !subject.optional_relation.is_empty() {
+            s.push_str(&format!("#{}", subject.optional_relation));
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status_with_error_info(reason: &str, metadata: &[(&str, &str)]) -> tonic::Status {
+        let info = proto::google::rpc::ErrorInfo {
+            reason: reason.into(),
+            domain: "authzed.com".into(),
+            metadata: metadata
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        };
+        let debug = proto::google::rpc::DebugInfo {
+            stack_entries: vec![],
+            detail: "some debug detail".into(),
+        };
+        let rpc_status = proto::google::rpc::Status {
+            code: tonic::Code::InvalidArgument as i32,
+            message: "bad write".into(),
+            details: vec![
+                prost_types::Any {
+                    type_url: "type.googleapis.com/google.rpc.ErrorInfo".into(),
+                    value: info.encode_to_vec(),
+                },
+                prost_types::Any {
+                    type_url: "type.googleapis.com/google.rpc.DebugInfo".into(),
+                    value: debug.encode_to_vec(),
+                },
+            ],
+        };
+        tonic::Status::with_details(
+            tonic::Code::InvalidArgument,
+            "bad write",
+            rpc_status.encode_to_vec().into(),
+        )
+    }
+
+    fn update(resource_id: &str, relation: &str) -> proto::RelationshipUpdate {
+        use crate::types::{ObjectReference, Relationship, RelationshipUpdate, SubjectReference};
+        let rel = Relationship::new(
+            ObjectReference::new("document", resource_id).unwrap(),
+            relation,
+            SubjectReference::new(
+                ObjectReference::new("user", "alice").unwrap(),
+                None::<String>,
+            )
+            .unwrap(),
+        );
+        (&RelationshipUpdate::touch(rel)).into()
+    }
+
+    #[test]
+    fn decodes_error_info_and_debug_info() {
+        let status = status_with_error_info(
+            "ERROR_REASON_UNKNOWN_RELATION_OR_PERMISSION",
+            &[("definition_name", "document")],
+        );
+        let err = Error::from_status(status);
+        let details = err.details().unwrap();
+        assert_eq!(
+            details.error_reason.as_deref(),
+            Some("ERROR_REASON_UNKNOWN_RELATION_OR_PERMISSION")
+        );
+        assert_eq!(details.debug_message.as_deref(), Some("some debug detail"));
+        assert_eq!(details.metadata["definition_name"], "document");
+    }
+
+    #[test]
+    fn retryable_codes_customizable() {
+        let err = Error::from_status(tonic::Status::internal("proxy hiccup"));
+        assert!(!err.is_retryable());
+        assert!(err.is_retryable_with(&[tonic::Code::Internal]));
+        assert!(!Error::InvalidArgument("x".into()).is_retryable_with(&[tonic::Code::Internal]));
+        assert!(Error::from_status(tonic::Status::unavailable("down")).is_retryable());
+    }
+
+    #[test]
+    fn display_includes_debug_message() {
+        let err = Error::from_status(status_with_error_info(
+            "ERROR_REASON_UNKNOWN_RELATION_OR_PERMISSION",
+            &[],
+        ));
+        assert_eq!(
+            err.to_string(),
+            "SpiceDB error (InvalidArgument): bad write (debug: some debug detail)"
+        );
+
+        let err = Error::from_status(tonic::Status::invalid_argument("bad write"));
+        assert_eq!(
+            err.to_string(),
+            "SpiceDB error (InvalidArgument): bad write"
+        );
+    }
+
+    #[test]
+    fn code_str_is_snake_case() {
+        let err = Error::from_status(tonic::Status::deadline_exceeded("slow"));
+        assert_eq!(err.code_str(), Some("deadline_exceeded"));
+        assert_eq!(
+            Error::from_status(tonic::Status::unauthenticated("")).code_str(),
+            Some("unauthenticated")
+        );
+        assert_eq!(Error::InvalidArgument("x".into()).code_str(), None);
+    }
+
+    #[test]
+    fn unimplemented_mapped_to_unsupported() {
+        let err = Error::from_status_for("WatchService.Watch")(tonic::Status::unimplemented(""));
+        assert!(matches!(
+            err,
+            Error::Unsupported {
+                rpc: "WatchService.Watch"
+            }
+        ));
+        assert!(err.to_string().contains("enable the WatchService"));
+
+        let err = Error::from_status_for("WatchService.Watch")(tonic::Status::unavailable(""));
+        assert_eq!(err.code(), Some(tonic::Code::Unavailable));
+    }
+
+    fn precondition(resource_id: &str, relation: &str) -> proto::Precondition {
+        use crate::types::{Precondition, RelationshipFilter};
+        let filter = RelationshipFilter::new("document")
+            .resource_id(resource_id)
+            .relation(relation);
+        (&Precondition::must_not_exist(filter)).into()
+    }
+
+    #[test]
+    fn failed_precondition_located_by_filter() {
+        let preconditions = vec![precondition("1", "owner"), precondition("2", "owner")];
+        let status = status_with_error_info(
+            PRECONDITION_FAILURE_REASON,
+            &[
+                ("precondition_operation", "OPERATION_MUST_NOT_MATCH"),
+                ("precondition_resource_type", "document"),
+                ("precondition_resource_id", "2"),
+                ("precondition_relation", "owner"),
+            ],
+        );
+        let err = Error::from_status(status).with_failed_precondition(&preconditions);
+        assert_eq!(err.details().unwrap().failed_precondition(), Some(1));
+
+        let status = status_with_error_info(
+            PRECONDITION_FAILURE_REASON,
+            &[
+                ("precondition_operation", "OPERATION_MUST_MATCH"),
+                ("precondition_resource_id", "2"),
+            ],
+        );
+        let err = Error::from_status(status).with_failed_precondition(&preconditions);
+        assert_eq!(err.details().unwrap().failed_precondition(), None);
+    }
+
+    #[test]
+    fn single_precondition_attributed_without_metadata() {
+        let status = status_with_error_info(PRECONDITION_FAILURE_REASON, &[]);
+        let err =
+            Error::from_status(status).with_failed_precondition(&[precondition("1", "owner")]);
+        assert_eq!(err.details().unwrap().failed_precondition(), Some(0));
+
+        let status = status_with_error_info("ERROR_REASON_UNKNOWN_RELATION_OR_PERMISSION", &[]);
+        let err =
+            Error::from_status(status).with_failed_precondition(&[precondition("1", "owner")]);
+        assert_eq!(err.details().unwrap().failed_precondition(), None);
+    }
+
+    #[test]
+    fn maximum_depth_exceeded_is_typed() {
+        let status = status_with_error_info(
+            MAXIMUM_DEPTH_EXCEEDED_REASON,
+            &[("maximum_depth_allowed", "50")],
+        );
+        let err = Error::from_status(status);
+        assert!(matches!(
+            err,
+            Error::MaximumDepthExceeded {
+                max_depth: Some(50),
+                ..
+            }
+        ));
+        assert_eq!(err.code_str(), Some("invalid_argument"));
+        assert!(err.is_retryable_with(&[tonic::Code::InvalidArgument]));
+        assert_eq!(
+            err.details().unwrap().error_reason.as_deref(),
+            Some(MAXIMUM_DEPTH_EXCEEDED_REASON)
+        );
+    }
+
+    #[test]
+    fn unauthenticated_is_typed() {
+        let err = Error::from_status(tonic::Status::unauthenticated("invalid preshared key"));
+        assert!(matches!(err, Error::Unauthenticated { .. }));
+        assert_eq!(err.code(), Some(tonic::Code::Unauthenticated));
+        assert_eq!(err.code_str(), Some("unauthenticated"));
+        assert!(!err.is_retryable());
+        assert_eq!(
+            err.to_string(),
+            "SpiceDB rejected the bearer token; check that it matches the server's \
+             preshared key: invalid preshared key"
+        );
+    }
+
+    #[test]
+    fn permission_denied_names_references() {
+        let err = Error::PermissionDenied {
+            resource: Box::new(ObjectReference::new("document", "doc-1").unwrap()),
+            permission: "edit".into(),
+            subject: Box::new(SubjectReference::parse("user:alice").unwrap()),
+        };
+        assert_eq!(err.code(), None);
+        assert_eq!(
+            err.to_string(),
+            "permission denied: user:alice does not have edit on document:doc-1"
+        );
+    }
+
+    #[test]
+    fn counter_still_calculating_is_not_a_status() {
+        let err = Error::CounterStillCalculating {
+            name: "members".into(),
+        };
+        assert_eq!(err.code(), None);
+        assert!(!err.is_retryable());
+        assert_eq!(
+            err.to_string(),
+            "relationship counter \"members\" is still calculating"
+        );
+    }
+
+    #[test]
+    fn expired_exact_snapshot_is_typed() {
+        let token = ZedToken::new("GhUKEzE2").unwrap();
+        let expired = || tonic::Status::out_of_range("invalid zedtoken: revision has expired");
+
+        let err = Error::from_status(expired()).with_snapshot(Some(&token));
+        assert!(matches!(
+            &err,
+            Error::RevisionUnavailable { token: t, .. } if *t == token
+        ));
+        assert!(!err.is_retryable());
+
+        // Without an exact snapshot, or for unrelated statuses, nothing changes.
+        let err = Error::from_status(expired()).with_snapshot(None);
+        assert_eq!(err.code(), Some(tonic::Code::OutOfRange));
+        let err = Error::from_status(tonic::Status::out_of_range("limit too large"))
+            .with_snapshot(Some(&token));
+        assert_eq!(err.code(), Some(tonic::Code::OutOfRange));
+    }
+
+    #[test]
+    fn status_without_details_has_none() {
+        let err = Error::from_status(tonic::Status::unavailable("down"));
+        assert!(err.details().is_none());
+    }
+
+    #[test]
+    fn failed_update_located_by_definition_and_relation() {
+        let updates = vec![
+            update("1", "viewer"),
+            update("2", "viewer"),
+            update("3", "veiwer"),
+        ];
+        let status = status_with_error_info(
+            "ERROR_REASON_UNKNOWN_RELATION_OR_PERMISSION",
+            &[
+                ("definition_name", "document"),
+                ("relation_or_permission_name", "veiwer"),
+            ],
+        );
+        let err = Error::from_status(status).with_failed_update(&updates);
+        let failed = err.details().unwrap().failed_update().cloned().unwrap();
+        assert_eq!(failed.index, 2);
+        assert_eq!(failed.relationship, "document:3#veiwer@user:alice");
+    }
+
+    #[test]
+    fn failed_update_located_by_relationship() {
+        let updates = vec![update("1", "viewer"), update("2", "viewer")];
+        let status = status_with_error_info(
+            "ERROR_REASON_UPDATES_ON_SAME_RELATIONSHIP",
+            &[("relationship", "document:2#viewer@user:alice")],
+        );
+        let err = Error::from_status(status).with_failed_update(&updates);
+        assert_eq!(err.details().unwrap().failed_update().unwrap().index, 1);
+    }
+
+    #[test]
+    fn failed_update_none_when_unidentifiable() {
+        let updates = vec![update("1", "viewer")];
+        let status = status_with_error_info("ERROR_REASON_UNSPECIFIED", &[]);
+        let err = Error::from_status(status).with_failed_update(&updates);
+        assert!(err.details().unwrap().failed_update().is_none());
+    }
 }
@@ -29,6 +29,8 @@
 //!     PermissionResult::Conditional { missing_fields } => {
 //!         println!("need caveat context: {:?}", missing_fields);
 //!     }
+//!     // `PermissionResult` is non-exhaustive; deny anything unrecognized.
+//!     _ => println!("access denied"),
 //! }
 //! # Ok(())
 //! # }
@@ -39,13 +41,15 @@
 //! | Feature | Default | Description |
 //! |---------|---------|-------------|
 //! | `watch` | No | Enables the WatchService for streaming relationship changes |
-//! | `experimental` | No | Enables experimental APIs (BulkCheckPermission, BulkImport/Export) |
+//! | `experimental` | No | Enables experimental APIs (BulkCheckPermission, BulkImport/Export, relationship counters) |
 //! | `serde` | No | Enables Serialize/Deserialize on ZedToken and domain types |
 //! | `tls-rustls` | No | Use rustls for TLS |
 //! | `tls-native` | No | Use native TLS |
 
 pub mod client;
 pub mod error;
+pub mod limits;
+pub mod schema;
 pub mod types;
 
 mod proto {
@@ -66,13 +70,49 @@ mod proto {
                 #[prost(message, repeated, tag = "3")]
                 pub details: ::prost::alloc::vec::Vec<::prost_types::Any>,
             }
+
+            /// google.rpc.ErrorInfo — SpiceDB puts its ErrorReason here.
+            #[derive(Clone, PartialEq, ::prost::Message)]
+            pub struct ErrorInfo {
+                #[prost(string, tag = "1")]
+                pub reason: ::prost::alloc::string::String,
+                #[prost(string, tag = "2")]
+                pub domain: ::prost::alloc::string::String,
+                #[prost(btree_map = "string, string", tag = "3")]
+                pub metadata: ::prost::alloc::collections::BTreeMap<
+                    ::prost::alloc::string::String,
+                    ::prost::alloc::string::String,
+                >,
+            }
+
+            /// google.rpc.DebugInfo
+            #[derive(Clone, PartialEq, ::prost::Message)]
+            pub struct DebugInfo {
+                #[prost(string, repeated, tag = "1")]
+                pub stack_entries: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+                #[prost(string, tag = "2")]
+                pub detail: ::prost::alloc::string::String,
+            }
+
+            /// google.rpc.RetryInfo
+            #[derive(Clone, PartialEq, ::prost::Message)]
+            pub struct RetryInfo {
+                #[prost(message, optional, tag = "1")]
+                pub retry_delay: ::core::option::Option<::prost_types::Duration>,
+            }
         }
     }
 }
 
 pub use client::Client;
+pub use client::ConnectionEvent;
 pub use error::Error;
 pub use types::*;
 
+#[cfg(feature = "watch")]
+pub use client::WatchStream;
+
 #[cfg(feature = "experimental")]
-pub use client::experimental::BulkCheckItem;
+pub use client::experimental::{
+    BulkCheckItem, BulkExportStream, ImportConflictStrategy, ImportSummary, RelationshipCount,
+};
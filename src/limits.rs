@@ -0,0 +1,28 @@
+//! Request size limits enforced by SpiceDB.
+//!
+//! These constants mirror SpiceDB's default server-side limits. Requests that
+//! exceed them are rejected locally with [`Error::InvalidArgument`](crate::Error::InvalidArgument)
+//! before any network call, rather than surfacing as a server `INVALID_ARGUMENT`.
+//!
+//! SpiceDB operators can raise or lower these limits with server flags; the
+//! flag controlling each limit is noted on the constant.
+
+/// Maximum number of updates in a single WriteRelationships call.
+///
+/// Server flag: `--write-relationships-max-updates-per-call`.
+pub const MAX_WRITE_UPDATES: usize = 1000;
+
+/// Maximum number of preconditions in a single WriteRelationships or
+/// DeleteRelationships call.
+///
+/// Server flag: `--update-relationships-max-preconditions-per-call`.
+pub const MAX_PRECONDITIONS: usize = 1000;
+
+/// Maximum number of items in a single CheckBulkPermissions call.
+pub const MAX_BULK_CHECK_ITEMS: usize = 10_000;
+
+/// Maximum length in bytes of an object ID.
+///
+/// Enforced by [`ObjectReference::new`](crate::ObjectReference::new); fixed by
+/// SpiceDB's object ID grammar rather than a server flag.
+pub const MAX_OBJECT_ID_LEN: usize = 1024;
@@ -0,0 +1,603 @@
+//! Shallow extraction of definitions, relations, and permissions from
+//! schema text.
+//!
+//! [`parse`] reads the text returned by
+//! [`Client::read_schema`](crate::Client::read_schema) into a
+//! [`ParsedSchema`] for basic introspection, such as listing the permissions
+//! of a type in a UI picker, without the `experimental` feature. It is not a
+//! compiler: caveats are skipped, and subject types and permission
+//! expressions are returned as text without checking what they refer to.
+//! To validate a schema, use `Client::validate_schema` (with the
+//! `experimental` feature).
+
+use crate::error::Error;
+
+/// The definitions of a schema, in the order they appear.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ParsedSchema {
+    /// The object type definitions.
+    pub definitions: Vec<ParsedDefinition>,
+}
+
+impl ParsedSchema {
+    /// Returns the definition named `name`, if any.
+    pub fn definition(&self, name: &str) -> Option<&ParsedDefinition> {
+        self.definitions.iter().find(|d| d.name == name)
+    }
+}
+
+/// An object type definition.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ParsedDefinition {
+    /// The type name, including any prefix (e.g. `tenant/document`).
+    pub name: String,
+    /// The relations, in the order they appear.
+    pub relations: Vec<ParsedRelation>,
+    /// The permissions, in the order they appear.
+    pub permissions: Vec<ParsedPermission>,
+}
+
+/// A relation of a definition.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParsedRelation {
+    /// The relation name.
+    pub name: String,
+    /// The allowed subject types as written, with whitespace normalized
+    /// (e.g. `user`, `group#member`, `user:*`, `user with ip_allowlist`).
+    pub subject_types: Vec<String>,
+    /// `subject_types`, split into their parts.
+    pub(crate) allowed: Vec<AllowedSubject>,
+}
+
+/// A subject type a relation allows, such as `group#member` or
+/// `user:* with ip_allowlist`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub(crate) struct AllowedSubject {
+    pub(crate) object_type: String,
+    /// The subject relation, as in `group#member`.
+    pub(crate) relation: Option<String>,
+    /// Whether this is the wildcard `type:*`.
+    pub(crate) wildcard: bool,
+    /// The caveat a relationship must carry, as in `user with ip_allowlist`.
+    pub(crate) caveat: Option<String>,
+}
+
+impl AllowedSubject {
+    /// Splits one `|` alternative of a relation's subject types. The
+    /// `expiration` trait is dropped: writing an expiration is optional.
+    fn from_tokens(tokens: &[Token<'_>]) -> Self {
+        let (base, traits) = match tokens.iter().position(|t| t.text == "with") {
+            Some(with) => (&tokens[..with], &tokens[with + 1..]),
+            None => (tokens, &[][..]),
+        };
+        let text = |i: usize| base.get(i).map(|t| t.text);
+        Self {
+            object_type: text(0).unwrap_or_default().to_string(),
+            relation: (text(1) == Some("#"))
+                .then(|| text(2).map(str::to_string))
+                .flatten(),
+            wildcard: text(1) == Some(":") && text(2) == Some("*"),
+            caveat: traits
+                .iter()
+                .map(|t| t.text)
+                .find(|t| !matches!(*t, "and" | "expiration"))
+                .map(str::to_string),
+        }
+    }
+}
+
+/// A permission of a definition.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ParsedPermission {
+    /// The permission name.
+    pub name: String,
+    /// The expression after `=`, with comments removed and whitespace
+    /// normalized (e.g. `viewer + editor`, `parent->view`).
+    pub expression: String,
+}
+
+/// Extracts the definitions, relations, and permissions of `schema`.
+///
+/// Comments, string literals, and caveat bodies are skipped, as are
+/// statements inside a definition other than relations and permissions. A
+/// statement ends at the end of its line unless the line break is inside
+/// parentheses or next to an operator, so expressions may be split across
+/// lines.
+///
+/// Returns `Err(InvalidArgument)` if the text is not shaped like a schema:
+/// a `definition` without a name or body, a relation without `:`, a
+/// permission without `=`, or an unclosed block.
+///
+/// ```
+/// let schema = prescience::schema::parse(
+///     r#"
+///     definition user {}
+///
+///     definition document {
+///         relation viewer: user | group#member // direct or via a group
+///         relation editor: user
+///         permission view = viewer +
+///             editor
+///     }
+///     "#,
+/// )
+/// .unwrap();
+///
+/// let document = schema.definition("document").unwrap();
+/// assert_eq!(document.relations[0].subject_types, ["user", "group#member"]);
+/// assert_eq!(document.permissions[0].name, "view");
+/// assert_eq!(document.permissions[0].expression, "viewer + editor");
+/// ```
+pub fn parse(schema: &str) -> Result<ParsedSchema, Error> {
+    let mut parser = Parser {
+        tokens: tokenize(schema),
+        pos: 0,
+    };
+    let mut definitions = Vec::new();
+    while let Some(token) = parser.next() {
+        match token.text {
+            "definition" => definitions.push(parser.definition()?),
+            "{" | "(" => parser.skip_block(token.text)?,
+            _ => {}
+        }
+    }
+    Ok(ParsedSchema { definitions })
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Token<'s> {
+    text: &'s str,
+    /// Whether a line break separates this token from the previous one.
+    line_start: bool,
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '/'
+}
+
+/// Splits `schema` into names, string literals, and punctuation, dropping
+/// whitespace and comments.
+fn tokenize(schema: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut line_start = true;
+    let mut chars = schema.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        let mut end = start + c.len_utf8();
+        match c {
+            '\n' => {
+                line_start = true;
+                continue;
+            }
+            c if c.is_whitespace() => continue,
+            '/' if chars.peek().is_some_and(|(_, n)| *n == '/') => {
+                for (_, c) in chars.by_ref() {
+                    if c == '\n' {
+                        line_start = true;
+                        break;
+                    }
+                }
+                continue;
+            }
+            '/' if chars.peek().is_some_and(|(_, n)| *n == '*') => {
+                chars.next();
+                let mut prev = ' ';
+                for (_, c) in chars.by_ref() {
+                    line_start |= c == '\n';
+                    if prev == '*' && c == '/' {
+                        break;
+                    }
+                    prev = c;
+                }
+                continue;
+            }
+            '"' | '\'' => {
+                let mut escaped = false;
+                for (i, n) in chars.by_ref() {
+                    end = i + n.len_utf8();
+                    match n {
+                        _ if escaped => escaped = false,
+                        '\\' => escaped = true,
+                        _ if n == c => break,
+                        _ => {}
+                    }
+                }
+            }
+            '-' if chars.peek().is_some_and(|(_, n)| *n == '>') => {
+                chars.next();
+                end += 1;
+            }
+            c if is_name_char(c) => {
+                while let Some((i, n)) = chars.next_if(|(_, n)| is_name_char(*n)) {
+                    end = i + n.len_utf8();
+                }
+            }
+            _ => {}
+        }
+        tokens.push(Token {
+            text: &schema[start..end],
+            line_start,
+        });
+        line_start = false;
+    }
+    tokens
+}
+
+/// Operators that continue an expression onto the next line.
+fn is_operator(text: &str) -> bool {
+    matches!(text, "+" | "-" | "&" | "|" | "->" | ".")
+}
+
+struct Parser<'s> {
+    tokens: Vec<Token<'s>>,
+    pos: usize,
+}
+
+impl<'s> Parser<'s> {
+    fn next(&mut self) -> Option<Token<'s>> {
+        let token = self.tokens.get(self.pos).copied();
+        self.pos += token.is_some() as usize;
+        token
+    }
+
+    fn peek(&self) -> Option<Token<'s>> {
+        self.tokens.get(self.pos).copied()
+    }
+
+    fn name(&mut self, after: &str) -> Result<&'s str, Error> {
+        match self.next() {
+            Some(t) if t.text.starts_with(is_name_char) => Ok(t.text),
+            _ => Err(invalid(format!("expected a name after `{}`", after))),
+        }
+    }
+
+    fn expect(&mut self, text: &str, context: &str) -> Result<(), Error> {
+        match self.next() {
+            Some(t) if t.text == text => Ok(()),
+            _ => Err(invalid(format!("expected `{}` {}", text, context))),
+        }
+    }
+
+    /// Skips to the bracket closing `open`, which was just consumed.
+    fn skip_block(&mut self, open: &str) -> Result<(), Error> {
+        let close = if open == "{" { "}" } else { ")" };
+        let mut depth = 1;
+        while let Some(token) = self.next() {
+            if token.text == open {
+                depth += 1;
+            } else if token.text == close {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(());
+                }
+            }
+        }
+        Err(invalid(format!("unclosed `{}`", open)))
+    }
+
+    fn definition(&mut self) -> Result<ParsedDefinition, Error> {
+        let name = self.name("definition")?;
+        self.expect("{", &format!("after `definition {}`", name))?;
+
+        let mut definition = ParsedDefinition {
+            name: name.to_string(),
+            ..Default::default()
+        };
+        loop {
+            let Some(token) = self.next() else {
+                return Err(invalid(format!("unclosed definition `{}`", name)));
+            };
+            match token.text {
+                "}" => return Ok(definition),
+                "relation" if self.at_name() => {
+                    let relation = self.name("relation")?;
+                    self.expect(":", &format!("after `relation {}`", relation))?;
+                    let statement = self.statement();
+                    let alternatives: Vec<_> = statement
+                        .split(|t| t.text == "|")
+                        .filter(|alternative| !alternative.is_empty())
+                        .collect();
+                    definition.relations.push(ParsedRelation {
+                        name: relation.to_string(),
+                        subject_types: alternatives.iter().map(|a| join(a)).collect(),
+                        allowed: alternatives
+                            .iter()
+                            .map(|a| AllowedSubject::from_tokens(a))
+                            .collect(),
+                    });
+                }
+                "permission" if self.at_name() => {
+                    let permission = self.name("permission")?;
+                    self.expect("=", &format!("after `permission {}`", permission))?;
+                    definition.permissions.push(ParsedPermission {
+                        name: permission.to_string(),
+                        expression: join(&self.statement()),
+                    });
+                }
+                "{" | "(" => self.skip_block(token.text)?,
+                _ => self.skip_line()?,
+            }
+        }
+    }
+
+    /// Whether the next token is a name.
+    fn at_name(&self) -> bool {
+        self.peek()
+            .is_some_and(|t| t.text.starts_with(is_name_char))
+    }
+
+    /// Skips the rest of a statement this parser does not know, up to the
+    /// end of its line or the `}` closing the definition, so that newer
+    /// syntax inside a definition does not fail the parse.
+    fn skip_line(&mut self) -> Result<(), Error> {
+        while let Some(token) = self.peek() {
+            if token.line_start || token.text == "}" {
+                break;
+            }
+            self.pos += 1;
+            if matches!(token.text, "{" | "(") {
+                self.skip_block(token.text)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Consumes the rest of a statement: up to the end of its line, the
+    /// next `relation` or `permission` on the same line, or the `}` closing
+    /// the definition.
+    fn statement(&mut self) -> Vec<Token<'s>> {
+        let mut tokens: Vec<Token<'s>> = Vec::new();
+        let mut depth = 0usize;
+        while let Some(token) = self.peek() {
+            if depth == 0 {
+                let after_operator = tokens.last().is_some_and(|t| is_operator(t.text));
+                let continues = after_operator || is_operator(token.text);
+                let next_statement = matches!(token.text, "relation" | "permission")
+                    && !after_operator
+                    && self
+                        .tokens
+                        .get(self.pos + 1)
+                        .is_some_and(|t| t.text.starts_with(is_name_char));
+                if token.text == "}"
+                    || (!tokens.is_empty() && (next_statement || (token.line_start && !continues)))
+                {
+                    break;
+                }
+            }
+            match token.text {
+                "(" => depth += 1,
+                ")" => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            tokens.push(token);
+            self.pos += 1;
+        }
+        tokens
+    }
+}
+
+/// Renders tokens as normalized text, without spaces inside `a->b`,
+/// `a.b(c)`, `group#member`, and `user:*`.
+fn join(tokens: &[Token<'_>]) -> String {
+    let mut out = String::new();
+    let mut prev: Option<&str> = None;
+    for token in tokens {
+        if let Some(prev) = prev {
+            let tight = matches!(prev, "->" | "." | "#" | ":" | "(")
+                || matches!(token.text, "->" | "." | "#" | ":" | "(" | ")" | ",");
+            if !tight {
+                out.push(' ');
+            }
+        }
+        out.push_str(token.text);
+        prev = Some(token.text);
+    }
+    out
+}
+
+fn invalid(message: String) -> Error {
+    Error::InvalidArgument(format!("invalid schema: {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_relations_and_permissions() {
+        let schema = parse(
+            r#"
+            use expiration
+
+            /** a user */
+            definition user {}
+
+            caveat ip_allowlist(user_ip ipaddress, cidr string) {
+                user_ip.in_cidr(cidr) && cidr != "definition fake {"
+            }
+
+            definition tenant/document {
+                relation parent: tenant/folder
+                relation viewer: user | user:* | group#member | user with ip_allowlist
+                relation editor: user with expiration // temporary access
+                permission view = viewer + editor + parent->view
+                permission edit = (editor &
+                    parent.any(edit)) - banned
+            }
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(schema.definitions.len(), 2);
+        assert_eq!(
+            schema.definitions[0],
+            ParsedDefinition {
+                name: "user".into(),
+                ..Default::default()
+            }
+        );
+
+        let document = schema.definition("tenant/document").unwrap();
+        let relations: Vec<_> = document
+            .relations
+            .iter()
+            .map(|r| (r.name.as_str(), r.subject_types.clone()))
+            .collect();
+        assert_eq!(
+            relations,
+            vec![
+                ("parent", vec!["tenant/folder".to_string()]),
+                (
+                    "viewer",
+                    vec![
+                        "user".into(),
+                        "user:*".into(),
+                        "group#member".into(),
+                        "user with ip_allowlist".into(),
+                    ]
+                ),
+                ("editor", vec!["user with expiration".into()]),
+            ]
+        );
+        let permissions: Vec<_> = document
+            .permissions
+            .iter()
+            .map(|p| (p.name.as_str(), p.expression.as_str()))
+            .collect();
+        assert_eq!(
+            permissions,
+            vec![
+                ("view", "viewer + editor + parent->view"),
+                ("edit", "(editor & parent.any(edit)) - banned"),
+            ]
+        );
+    }
+
+    #[test]
+    fn allowed_subjects_split_into_parts() {
+        let schema = parse(
+            "definition doc {
+                 relation viewer: user | group#member | user:* with ip_allowlist | user with ip_allowlist and expiration
+             }",
+        )
+        .unwrap();
+        let allowed = &schema.definitions[0].relations[0].allowed;
+        let subject =
+            |object_type: &str, relation: Option<&str>, wildcard, caveat: Option<&str>| {
+                AllowedSubject {
+                    object_type: object_type.into(),
+                    relation: relation.map(Into::into),
+                    wildcard,
+                    caveat: caveat.map(Into::into),
+                }
+            };
+        assert_eq!(
+            *allowed,
+            [
+                subject("user", None, false, None),
+                subject("group", Some("member"), false, None),
+                subject("user", None, true, Some("ip_allowlist")),
+                subject("user", None, false, Some("ip_allowlist")),
+            ]
+        );
+    }
+
+    #[test]
+    fn keywords_as_names() {
+        let schema = parse(
+            "definition document {\n\
+                 relation definition: user\n\
+                 permission relation = definition\n\
+             }",
+        )
+        .unwrap();
+        let document = &schema.definitions[0];
+        assert_eq!(document.relations[0].name, "definition");
+        assert_eq!(document.permissions[0].name, "relation");
+        assert_eq!(document.permissions[0].expression, "definition");
+    }
+
+    #[test]
+    fn single_line_definition() {
+        let schema = parse(
+            "definition doc { relation viewer: user | group#member permission view = viewer }",
+        )
+        .unwrap();
+        let document = &schema.definitions[0];
+        assert_eq!(
+            document.relations[0].subject_types,
+            ["user", "group#member"]
+        );
+        assert_eq!(document.permissions[0].expression, "viewer");
+    }
+
+    #[test]
+    fn malformed_schemas_rejected() {
+        for schema in [
+            "definition {}",
+            "definition user",
+            "definition user {",
+            "definition doc {\n relation viewer user\n}",
+            "definition doc {\n permission view viewer\n}",
+            "caveat c(a int) { a > 1",
+        ] {
+            assert!(
+                matches!(parse(schema), Err(Error::InvalidArgument(_))),
+                "{:?} should be rejected",
+                schema
+            );
+        }
+    }
+
+    #[test]
+    fn definition_names_only_from_top_level() {
+        let schema = parse(
+            r#"
+            // definition commented_out {}
+            /* definition also_commented {} */
+            caveat ip_allowlist(user_ip ipaddress, cidr string, definition int) {
+                user_ip.in_cidr(cidr) && cidr != "definition fake {}"
+            }
+
+            definition user {}
+
+            definition tenant/document {
+                relation definition: user
+                permission view = definition
+            }
+            "#,
+        )
+        .unwrap();
+        let names: Vec<_> = schema.definitions.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["user", "tenant/document"]);
+        assert!(parse("caveat only(a int) { a > 1 }")
+            .unwrap()
+            .definitions
+            .is_empty());
+    }
+
+    #[test]
+    fn unknown_statements_in_definitions_skipped() {
+        let schema = parse(
+            r#"
+            definition document {
+                relation viewer: user
+                metadata relation = "audited" permission: { nested }
+                ...shared_permissions
+                permission view = viewer
+            }
+            "#,
+        )
+        .unwrap();
+        let document = &schema.definitions[0];
+        assert_eq!(document.relations.len(), 1);
+        assert_eq!(document.permissions[0].name, "view");
+        assert_eq!(document.permissions[0].expression, "viewer");
+    }
+
+    #[test]
+    fn empty_schema() {
+        assert_eq!(parse("").unwrap(), ParsedSchema::default());
+        assert_eq!(parse("// nothing here").unwrap(), ParsedSchema::default());
+    }
+}
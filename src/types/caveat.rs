@@ -0,0 +1,370 @@
+//! Caveat definitions for client-side context validation.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::error::Error;
+use crate::types::ContextValue;
+
+/// The CEL type of a caveat parameter.
+///
+/// Parsed from SpiceDB's type names (`int`, `uint`, `double`, `bool`,
+/// `string`, `bytes`, `duration`, `timestamp`, `ipaddress`, `list<T>`,
+/// `map<T>`, `any`). Types this library does not know yet are kept as
+/// [`CaveatParamType::Other`] and accept any value during validation.
+///
+/// # Examples
+///
+/// ```
+/// use prescience::CaveatParamType;
+///
+/// let t = CaveatParamType::from("list<string>");
+/// assert_eq!(t, CaveatParamType::List(Box::new(CaveatParamType::String)));
+/// assert_eq!(t.to_string(), "list<string>");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum CaveatParamType {
+    /// `any`: accepts every value.
+    Any,
+    /// `int`: a whole number.
+    Int,
+    /// `uint`: a non-negative whole number.
+    Uint,
+    /// `double`: any number.
+    Double,
+    /// `bool`.
+    Bool,
+    /// `string`.
+    String,
+    /// `bytes`, passed as a string.
+    Bytes,
+    /// `duration`, passed as a string such as `"1h30m"`.
+    Duration,
+    /// `timestamp`, passed as an RFC 3339 string.
+    Timestamp,
+    /// `ipaddress`, passed as a string.
+    IpAddress,
+    /// `list<T>`.
+    List(Box<CaveatParamType>),
+    /// `map<T>`, keyed by string.
+    Map(Box<CaveatParamType>),
+    /// A type name this library does not recognize.
+    Other(String),
+}
+
+impl CaveatParamType {
+    /// Returns `true` if `value` is acceptable for a parameter of this type.
+    ///
+    /// Numbers are checked for integrality for `int` and `uint`; string-encoded
+    /// types (`duration`, `ipaddress`, ...) only check that a string was given.
+    pub fn accepts(&self, value: &ContextValue) -> bool {
+        match (self, value) {
+            (CaveatParamType::Any | CaveatParamType::Other(_), _) => true,
+            (CaveatParamType::Int, ContextValue::Number(n)) => n.fract() == 0.0,
+            (CaveatParamType::Uint, ContextValue::Number(n)) => n.fract() == 0.0 && *n >= 0.0,
+            (CaveatParamType::Double, ContextValue::Number(_)) => true,
+            (CaveatParamType::Bool, ContextValue::Bool(_)) => true,
+            (
+                CaveatParamType::String
+                | CaveatParamType::Bytes
+                | CaveatParamType::Duration
+                | CaveatParamType::Timestamp
+                | CaveatParamType::IpAddress,
+                ContextValue::String(_),
+            ) => true,
+            (CaveatParamType::List(inner), ContextValue::List(items)) => {
+                items.iter().all(|v| inner.accepts(v))
+            }
+            (CaveatParamType::Map(inner), ContextValue::Struct(fields)) => {
+                fields.values().all(|v| inner.accepts(v))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl From<&str> for CaveatParamType {
+    fn from(name: &str) -> Self {
+        let name = name.trim();
+        let generic = |prefix: &str| {
+            name.strip_prefix(prefix)
+                .and_then(|rest| rest.strip_suffix('>'))
+                .map(|inner| Box::new(CaveatParamType::from(inner)))
+        };
+        if let Some(inner) = generic("list<") {
+            return CaveatParamType::List(inner);
+        }
+        if let Some(inner) = generic("map<") {
+            return CaveatParamType::Map(inner);
+        }
+        match name {
+            "any" => CaveatParamType::Any,
+            "int" => CaveatParamType::Int,
+            "uint" => CaveatParamType::Uint,
+            "double" => CaveatParamType::Double,
+            "bool" => CaveatParamType::Bool,
+            "string" => CaveatParamType::String,
+            "bytes" => CaveatParamType::Bytes,
+            "duration" => CaveatParamType::Duration,
+            "timestamp" => CaveatParamType::Timestamp,
+            "ipaddress" => CaveatParamType::IpAddress,
+            other => CaveatParamType::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for CaveatParamType {
+    fn from(name: String) -> Self {
+        CaveatParamType::from(name.as_str())
+    }
+}
+
+/// Formats the type with its SpiceDB/CEL name, e.g. `list<string>`.
+impl fmt::Display for CaveatParamType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CaveatParamType::Any => f.write_str("any"),
+            CaveatParamType::Int => f.write_str("int"),
+            CaveatParamType::Uint => f.write_str("uint"),
+            CaveatParamType::Double => f.write_str("double"),
+            CaveatParamType::Bool => f.write_str("bool"),
+            CaveatParamType::String => f.write_str("string"),
+            CaveatParamType::Bytes => f.write_str("bytes"),
+            CaveatParamType::Duration => f.write_str("duration"),
+            CaveatParamType::Timestamp => f.write_str("timestamp"),
+            CaveatParamType::IpAddress => f.write_str("ipaddress"),
+            CaveatParamType::List(inner) => write!(f, "list<{}>", inner),
+            CaveatParamType::Map(inner) => write!(f, "map<{}>", inner),
+            CaveatParamType::Other(name) => f.write_str(name),
+        }
+    }
+}
+
+/// The declared shape of a caveat: its name and typed parameters.
+///
+/// Used with `CheckPermissionRequest::context_checked` to validate caveat
+/// context before a request is sent. Build one by hand, or (with the
+/// `experimental` feature) load it from the server's schema with
+/// `Client::caveat_definition`.
+///
+/// # Examples
+///
+/// ```
+/// use prescience::{CaveatDefinition, CaveatParamType};
+///
+/// let caveat = CaveatDefinition::new("ip_allowlist")
+///     .parameter("user_ip", "ipaddress")
+///     .parameter("allowed_ranges", "list<string>");
+/// assert_eq!(caveat.parameters().len(), 2);
+/// assert_eq!(caveat.parameters()["user_ip"], CaveatParamType::IpAddress);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaveatDefinition {
+    name: String,
+    parameters: HashMap<String, CaveatParamType>,
+}
+
+impl CaveatDefinition {
+    /// Creates a caveat definition with no parameters.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            parameters: HashMap::new(),
+        }
+    }
+
+    /// Declares a parameter with the given type, either a [`CaveatParamType`]
+    /// or its CEL type name.
+    pub fn parameter(
+        mut self,
+        name: impl Into<String>,
+        param_type: impl Into<CaveatParamType>,
+    ) -> Self {
+        self.parameters.insert(name.into(), param_type.into());
+        self
+    }
+
+    /// Returns the caveat name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the declared parameters, keyed by name, with their types.
+    pub fn parameters(&self) -> &HashMap<String, CaveatParamType> {
+        &self.parameters
+    }
+
+    /// Validates that every key in `context` is a declared parameter with a
+    /// value of a compatible type.
+    ///
+    /// Parameters missing from `context` are allowed: SpiceDB merges the
+    /// request context with the context stored on the relationship (see
+    /// [`Caveat::merge_context`](crate::Caveat::merge_context)), so a request
+    /// usually supplies only some of them. Use
+    /// [`validate_complete_context`](Self::validate_complete_context) to
+    /// require all of them.
+    ///
+    /// Returns `Err(InvalidArgument)` naming the first offending parameter
+    /// in name order.
+    pub fn validate_context(&self, context: &HashMap<String, ContextValue>) -> Result<(), Error> {
+        let mut keys: Vec<&String> = context.keys().collect();
+        keys.sort();
+        for key in keys {
+            let param_type = self.parameters.get(key).ok_or_else(|| {
+                Error::InvalidArgument(format!("caveat '{}' has no parameter '{}'", self.name, key))
+            })?;
+            let value = &context[key];
+            if !param_type.accepts(value) {
+                return Err(Error::InvalidArgument(format!(
+                    "caveat '{}' parameter '{}' expects {}, got {:?}",
+                    self.name, key, param_type, value
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Like [`validate_context`](Self::validate_context), but also requires
+    /// `context` to supply every declared parameter.
+    ///
+    /// Use it when the relationships carry no context of their own, or on
+    /// the result of [`Caveat::merge_context`](crate::Caveat::merge_context).
+    pub fn validate_complete_context(
+        &self,
+        context: &HashMap<String, ContextValue>,
+    ) -> Result<(), Error> {
+        self.validate_context(context)?;
+        let mut params: Vec<(&String, &CaveatParamType)> = self.parameters.iter().collect();
+        params.sort_by_key(|(name, _)| *name);
+        for (param, param_type) in params {
+            if !context.contains_key(param) {
+                return Err(Error::InvalidArgument(format!(
+                    "caveat '{}' requires parameter '{}' ({})",
+                    self.name, param, param_type
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl From<crate::proto::ExpCaveat> for CaveatDefinition {
+    fn from(proto: crate::proto::ExpCaveat) -> Self {
+        Self {
+            name: proto.name,
+            parameters: proto
+                .parameters
+                .into_iter()
+                .map(|p| (p.name, CaveatParamType::from(p.r#type)))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn caveat() -> CaveatDefinition {
+        CaveatDefinition::new("ip_check")
+            .parameter("user_ip", "ipaddress")
+            .parameter("attempts", "uint")
+            .parameter("tags", "list<string>")
+    }
+
+    fn context() -> HashMap<String, ContextValue> {
+        let mut ctx = HashMap::new();
+        ctx.insert("user_ip".into(), ContextValue::String("10.0.0.1".into()));
+        ctx.insert("attempts".into(), ContextValue::Number(3.0));
+        ctx.insert("tags".into(), ContextValue::List(vec![]));
+        ctx
+    }
+
+    #[test]
+    fn valid_context_accepted() {
+        caveat().validate_context(&context()).unwrap();
+    }
+
+    #[test]
+    fn missing_parameter_rejected() {
+        let mut ctx = context();
+        ctx.remove("user_ip");
+        let err = caveat().validate_complete_context(&ctx).unwrap_err();
+        assert!(matches!(err, Error::InvalidArgument(msg) if msg.contains("user_ip")));
+    }
+
+    #[test]
+    fn partial_context_accepted() {
+        let mut ctx = context();
+        ctx.remove("user_ip");
+        ctx.remove("tags");
+        caveat().validate_context(&ctx).unwrap();
+        // The first missing parameter in name order is reported.
+        let err = caveat().validate_complete_context(&ctx).unwrap_err();
+        assert!(matches!(err, Error::InvalidArgument(msg) if msg.contains("'tags'")));
+    }
+
+    #[test]
+    fn unknown_parameter_rejected() {
+        let mut ctx = context();
+        ctx.insert("extra".into(), ContextValue::Bool(true));
+        let err = caveat().validate_context(&ctx).unwrap_err();
+        assert!(matches!(err, Error::InvalidArgument(msg) if msg.contains("extra")));
+    }
+
+    #[test]
+    fn param_types_parse_and_display() {
+        for name in ["int", "ipaddress", "list<string>", "map<list<int>>", "any"] {
+            assert_eq!(CaveatParamType::from(name).to_string(), name);
+        }
+        assert_eq!(
+            CaveatParamType::from("map<uint>"),
+            CaveatParamType::Map(Box::new(CaveatParamType::Uint))
+        );
+        assert_eq!(
+            CaveatParamType::from("vector<int>"),
+            CaveatParamType::Other("vector<int>".into())
+        );
+    }
+
+    #[test]
+    fn list_elements_checked() {
+        let t = CaveatParamType::from("list<string>");
+        assert!(t.accepts(&ContextValue::List(vec![ContextValue::String("a".into())])));
+        assert!(!t.accepts(&ContextValue::List(vec![ContextValue::Number(1.0)])));
+    }
+
+    #[test]
+    fn from_reflected_caveat() {
+        let proto = crate::proto::ExpCaveat {
+            name: "ip_check".into(),
+            comment: String::new(),
+            parameters: vec![crate::proto::ExpCaveatParameter {
+                name: "allowed".into(),
+                r#type: "list<string>".into(),
+                parent_caveat_name: "ip_check".into(),
+            }],
+            expression: String::new(),
+        };
+        let caveat = CaveatDefinition::from(proto);
+        assert_eq!(caveat.name(), "ip_check");
+        assert_eq!(
+            caveat.parameters()["allowed"],
+            CaveatParamType::List(Box::new(CaveatParamType::String))
+        );
+    }
+
+    #[test]
+    fn unknown_type_accepts_anything() {
+        let t = CaveatParamType::Other("future_type".into());
+        assert!(t.accepts(&ContextValue::Bool(true)));
+    }
+
+    #[test]
+    fn wrong_type_rejected() {
+        let mut ctx = context();
+        ctx.insert("attempts".into(), ContextValue::Number(1.5));
+        let err = caveat().validate_context(&ctx).unwrap_err();
+        assert!(matches!(err, Error::InvalidArgument(msg) if msg.contains("attempts")));
+    }
+}
@@ -1,11 +1,16 @@
 //! Consistency modes for SpiceDB reads.
 
+use crate::error::Error;
 use crate::types::ZedToken;
 
 /// Controls the consistency guarantees for read operations.
 ///
 /// When no consistency is specified, the library sends no preference to the
-/// server, which defaults to `MinimizeLatency`.
+/// server, which defaults to `MinimizeLatency`. The [`Default`] impl matches
+/// that server-side behavior, so `Consistency::default()` can stand in for
+/// "no preference" in structs deriving `Default` and in generic code.
+///
+/// New consistency modes may be added, so the enum is `#[non_exhaustive]`.
 ///
 /// # Examples
 ///
@@ -18,10 +23,18 @@ use crate::types::ZedToken;
 /// // Read at least as fresh as a previous write
 /// let token = ZedToken::new("some-token").unwrap();
 /// let c = Consistency::AtLeastAsFresh(token);
+///
+/// // The same, from a token string (e.g. out of a cookie or header)
+/// let c = Consistency::at_least_as_fresh("some-token").unwrap();
+///
+/// // The default matches SpiceDB's server-side default
+/// assert_eq!(Consistency::default(), Consistency::MinimizeLatency);
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum Consistency {
     /// Server picks the fastest available snapshot. Lowest latency, weakest consistency.
+    #[default]
     MinimizeLatency,
     /// All data must be at least as fresh as the given token.
     AtLeastAsFresh(ZedToken),
@@ -31,6 +44,22 @@ pub enum Consistency {
     FullyConsistent,
 }
 
+impl Consistency {
+    /// Reads at least as fresh as the serialized token `token`.
+    ///
+    /// Returns `Err(InvalidArgument)` if `token` is empty.
+    pub fn at_least_as_fresh(token: impl Into<String>) -> Result<Self, Error> {
+        ZedToken::new(token).map(Self::AtLeastAsFresh)
+    }
+
+    /// Reads at exactly the snapshot of the serialized token `token`.
+    ///
+    /// Returns `Err(InvalidArgument)` if `token` is empty.
+    pub fn at_exact_snapshot(token: impl Into<String>) -> Result<Self, Error> {
+        ZedToken::new(token).map(Self::AtExactSnapshot)
+    }
+}
+
 impl From<&Consistency> for crate::proto::Consistency {
     fn from(c: &Consistency) -> Self {
         use crate::proto::consistency::Requirement;
@@ -44,3 +73,43 @@ impl From<&Consistency> for crate::proto::Consistency {
         }
     }
 }
+
+/// The token of an `AtExactSnapshot` requirement, if that is what
+/// `consistency` asks for.
+pub(crate) fn exact_snapshot(consistency: Option<&crate::proto::Consistency>) -> Option<ZedToken> {
+    use crate::proto::consistency::Requirement;
+    match consistency?.requirement.as_ref()? {
+        Requirement::AtExactSnapshot(token) => ZedToken::try_from(token.clone()).ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_token_strings() {
+        let token = ZedToken::new("tok").unwrap();
+        assert_eq!(
+            Consistency::at_least_as_fresh("tok").unwrap(),
+            Consistency::AtLeastAsFresh(token.clone())
+        );
+        assert_eq!(
+            Consistency::at_exact_snapshot(String::from("tok")).unwrap(),
+            Consistency::AtExactSnapshot(token)
+        );
+    }
+
+    #[test]
+    fn empty_token_strings_rejected() {
+        assert!(matches!(
+            Consistency::at_least_as_fresh(""),
+            Err(Error::InvalidArgument(_))
+        ));
+        assert!(matches!(
+            Consistency::at_exact_snapshot(""),
+            Err(Error::InvalidArgument(_))
+        ));
+    }
+}
@@ -1,6 +1,9 @@
 //! Caveat context value types.
 
 use std::collections::HashMap;
+use std::fmt;
+
+use crate::error::Error;
 
 /// A typed value for caveat context evaluation.
 ///
@@ -35,6 +38,342 @@ pub enum ContextValue {
     Struct(HashMap<String, ContextValue>),
 }
 
+/// Formats the value as compact JSON with object keys sorted, so equal
+/// values always render identically (e.g. `{"a":1,"b":[true,null]}`).
+///
+/// Whole numbers render without a fractional part; non-finite numbers,
+/// which JSON cannot represent, render as `null`.
+impl fmt::Display for ContextValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContextValue::Null => f.write_str("null"),
+            ContextValue::Bool(b) => write!(f, "{}", b),
+            ContextValue::Number(n) if !n.is_finite() => f.write_str("null"),
+            ContextValue::Number(n) => write!(f, "{}", n),
+            ContextValue::String(s) => write_json_string(f, s),
+            ContextValue::List(items) => {
+                f.write_str("[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                f.write_str("]")
+            }
+            ContextValue::Struct(fields) => write_json_object(f, fields),
+        }
+    }
+}
+
+fn write_json_object(
+    f: &mut fmt::Formatter<'_>,
+    fields: &HashMap<String, ContextValue>,
+) -> fmt::Result {
+    let mut keys: Vec<&String> = fields.keys().collect();
+    keys.sort();
+    f.write_str("{")?;
+    for (i, key) in keys.into_iter().enumerate() {
+        if i > 0 {
+            f.write_str(",")?;
+        }
+        write_json_string(f, key)?;
+        write!(f, ":{}", fields[key])?;
+    }
+    f.write_str("}")
+}
+
+fn write_json_string(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_str("\"")?;
+    for c in s.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    f.write_str("\"")
+}
+
+/// Renders a caveat context as a compact JSON object with sorted keys.
+pub(crate) fn context_to_json(context: &HashMap<String, ContextValue>) -> String {
+    struct Json<'a>(&'a HashMap<String, ContextValue>);
+    impl fmt::Display for Json<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write_json_object(f, self.0)
+        }
+    }
+    Json(context).to_string()
+}
+
+/// Parses a JSON object into a caveat context map.
+///
+/// Accepts standard JSON (RFC 8259). Errors are `InvalidArgument` and give
+/// the byte offset of the problem. Objects and arrays may nest at most
+/// [`MAX_JSON_DEPTH`] levels deep.
+pub(crate) fn parse_context_json(input: &str) -> Result<HashMap<String, ContextValue>, Error> {
+    let mut parser = JsonParser {
+        input,
+        pos: 0,
+        depth: 0,
+    };
+    parser.skip_whitespace();
+    let value = parser.parse_value()?;
+    parser.skip_whitespace();
+    if parser.pos != input.len() {
+        return Err(parser.error("trailing characters after JSON value"));
+    }
+    match value {
+        ContextValue::Struct(fields) => Ok(fields),
+        _ => Err(Error::InvalidArgument(
+            "caveat context must be a JSON object".into(),
+        )),
+    }
+}
+
+/// Maximum nesting of objects and arrays accepted by [`parse_context_json`].
+const MAX_JSON_DEPTH: usize = 64;
+
+struct JsonParser<'a> {
+    input: &'a str,
+    pos: usize,
+    depth: usize,
+}
+
+impl JsonParser<'_> {
+    fn error(&self, message: &str) -> Error {
+        Error::InvalidArgument(format!("invalid JSON at byte {}: {}", self.pos, message))
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.as_bytes().get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), Error> {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected {:?}", byte as char)))
+        }
+    }
+
+    fn keyword(&mut self, word: &str, value: ContextValue) -> Result<ContextValue, Error> {
+        if self.input[self.pos..].starts_with(word) {
+            self.pos += word.len();
+            Ok(value)
+        } else {
+            Err(self.error("unexpected token"))
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<ContextValue, Error> {
+        match self.peek() {
+            Some(b'{') => self.nested(Self::parse_object),
+            Some(b'[') => self.nested(Self::parse_array),
+            Some(b'"') => self.parse_string().map(ContextValue::String),
+            Some(b't') => self.keyword("true", ContextValue::Bool(true)),
+            Some(b'f') => self.keyword("false", ContextValue::Bool(false)),
+            Some(b'n') => self.keyword("null", ContextValue::Null),
+            Some(b'-' | b'0'..=b'9') => self.parse_number(),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn nested(
+        &mut self,
+        parse: fn(&mut Self) -> Result<ContextValue, Error>,
+    ) -> Result<ContextValue, Error> {
+        if self.depth == MAX_JSON_DEPTH {
+            return Err(self.error(&format!("nesting deeper than {MAX_JSON_DEPTH} levels")));
+        }
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn parse_object(&mut self) -> Result<ContextValue, Error> {
+        self.expect(b'{')?;
+        let mut fields = HashMap::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.pos += 1;
+            return Ok(ContextValue::Struct(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            if self.peek() != Some(b'"') {
+                return Err(self.error("expected object key"));
+            }
+            let key = self.parse_string()?;
+            self.skip_whitespace();
+            self.expect(b':')?;
+            self.skip_whitespace();
+            let value = self.parse_value()?;
+            fields.insert(key, value);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b'}') => {
+                    self.pos += 1;
+                    return Ok(ContextValue::Struct(fields));
+                }
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<ContextValue, Error> {
+        self.expect(b'[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.pos += 1;
+            return Ok(ContextValue::List(items));
+        }
+        loop {
+            self.skip_whitespace();
+            items.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(b',') => self.pos += 1,
+                Some(b']') => {
+                    self.pos += 1;
+                    return Ok(ContextValue::List(items));
+                }
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<ContextValue, Error> {
+        let start = self.pos;
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+        }
+        match self.peek() {
+            Some(b'0') => self.pos += 1,
+            Some(b'1'..=b'9') => self.skip_digits(),
+            _ => return Err(self.error("expected digit")),
+        }
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.error("expected digit after '.'"));
+            }
+            self.skip_digits();
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            self.pos += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.error("expected digit in exponent"));
+            }
+            self.skip_digits();
+        }
+        match self.input[start..self.pos].parse::<f64>() {
+            Ok(n) if n.is_finite() => Ok(ContextValue::Number(n)),
+            Ok(_) => Err(self.error("number out of range")),
+            Err(_) => Err(self.error("invalid number")),
+        }
+    }
+
+    fn skip_digits(&mut self) {
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.pos += 1;
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, Error> {
+        self.expect(b'"')?;
+        let mut out = String::new();
+        loop {
+            let rest = &self.input[self.pos..];
+            let Some(c) = rest.chars().next() else {
+                return Err(self.error("unterminated string"));
+            };
+            match c {
+                '"' => {
+                    self.pos += 1;
+                    return Ok(out);
+                }
+                '\\' => {
+                    self.pos += 1;
+                    let escaped = match self.peek() {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => {
+                            self.pos += 1;
+                            out.push(self.parse_unicode_escape()?);
+                            continue;
+                        }
+                        _ => return Err(self.error("invalid escape sequence")),
+                    };
+                    self.pos += 1;
+                    out.push(escaped);
+                }
+                c if (c as u32) < 0x20 => {
+                    return Err(self.error("unescaped control character in string"));
+                }
+                c => {
+                    self.pos += c.len_utf8();
+                    out.push(c);
+                }
+            }
+        }
+    }
+
+    /// Parses the `XXXX` after `\u`, combining a following low surrogate
+    /// escape when the first is a high surrogate.
+    fn parse_unicode_escape(&mut self) -> Result<char, Error> {
+        let high = self.parse_hex4()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| self.error("invalid unicode escape"));
+        }
+        if !self.input[self.pos..].starts_with("\\u") {
+            return Err(self.error("unpaired surrogate in unicode escape"));
+        }
+        self.pos += 2;
+        let low = self.parse_hex4()?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(self.error("unpaired surrogate in unicode escape"));
+        }
+        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+            .ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, Error> {
+        let digits = self
+            .input
+            .get(self.pos..self.pos + 4)
+            .filter(|d| d.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error("expected four hex digits"))?;
+        let value = u32::from_str_radix(digits, 16).map_err(|_| self.error("invalid hex"))?;
+        self.pos += 4;
+        Ok(value)
+    }
+}
+
 impl From<&ContextValue> for prost_types::Value {
     fn from(cv: &ContextValue) -> Self {
         use prost_types::value::Kind;
@@ -80,6 +419,11 @@ pub(crate) fn context_to_struct(context: &HashMap<String, ContextValue>) -> pros
     }
 }
 
+/// Convert a prost_types::Struct to a HashMap of ContextValues.
+pub(crate) fn struct_to_context(s: prost_types::Struct) -> HashMap<String, ContextValue> {
+    s.fields.into_iter().map(|(k, v)| (k, v.into())).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -127,6 +471,67 @@ mod tests {
         assert_eq!(orig, back);
     }
 
+    #[test]
+    fn display_renders_sorted_json() {
+        let mut fields = HashMap::new();
+        fields.insert(
+            "b".into(),
+            ContextValue::List(vec![ContextValue::Bool(true)]),
+        );
+        fields.insert("a".into(), ContextValue::Number(1.0));
+        fields.insert("c".into(), ContextValue::String("say \"hi\"\n".into()));
+        fields.insert("d".into(), ContextValue::Number(2.5));
+        assert_eq!(
+            ContextValue::Struct(fields).to_string(),
+            r#"{"a":1,"b":[true],"c":"say \"hi\"\n","d":2.5}"#
+        );
+        assert_eq!(ContextValue::Number(f64::NAN).to_string(), "null");
+    }
+
+    #[test]
+    fn parse_json_round_trips_display() {
+        let json = r#"{"a":1,"b":[true,null,-2.5e1],"c":{"d":"x\"y\u00e9\ud83d\ude00"}}"#;
+        let parsed = parse_context_json(json).unwrap();
+        assert_eq!(parsed["a"], ContextValue::Number(1.0));
+        assert_eq!(
+            parsed["b"],
+            ContextValue::List(vec![
+                ContextValue::Bool(true),
+                ContextValue::Null,
+                ContextValue::Number(-25.0),
+            ])
+        );
+        let reparsed = parse_context_json(&context_to_json(&parsed)).unwrap();
+        assert_eq!(parsed, reparsed);
+    }
+
+    #[test]
+    fn parse_json_rejects_invalid() {
+        for bad in [
+            "",
+            "[1]",
+            "{",
+            r#"{"a":}"#,
+            r#"{"a":1,}"#,
+            r#"{"a":01}"#,
+            r#"{"a":1e400}"#,
+            "{} x",
+        ] {
+            let err = parse_context_json(bad).unwrap_err();
+            assert!(matches!(err, Error::InvalidArgument(_)), "{bad}");
+        }
+    }
+
+    #[test]
+    fn parse_json_rejects_deep_nesting() {
+        let deep = format!("{{\"a\":{}}}", "[".repeat(100_000));
+        let err = parse_context_json(&deep).unwrap_err();
+        assert!(matches!(err, Error::InvalidArgument(_)));
+
+        let nested = format!("{{\"a\":{}{}}}", "[".repeat(63), "]".repeat(63));
+        assert!(parse_context_json(&nested).is_ok());
+    }
+
     #[test]
     fn roundtrip_nested_struct() {
         let mut fields = HashMap::new();
@@ -136,4 +541,122 @@ mod tests {
         let back: ContextValue = proto.into();
         assert_eq!(orig, back);
     }
+
+    mod against_serde_json {
+        use proptest::prelude::*;
+
+        use super::*;
+
+        fn from_serde(value: serde_json::Value) -> ContextValue {
+            match value {
+                serde_json::Value::Null => ContextValue::Null,
+                serde_json::Value::Bool(b) => ContextValue::Bool(b),
+                serde_json::Value::Number(n) => ContextValue::Number(n.as_f64().unwrap()),
+                serde_json::Value::String(s) => ContextValue::String(s),
+                serde_json::Value::Array(items) => {
+                    ContextValue::List(items.into_iter().map(from_serde).collect())
+                }
+                serde_json::Value::Object(fields) => ContextValue::Struct(
+                    fields
+                        .into_iter()
+                        .map(|(k, v)| (k, from_serde(v)))
+                        .collect(),
+                ),
+            }
+        }
+
+        /// Parses `input` with both parsers and checks they agree on whether
+        /// it is a valid JSON object and, if so, on its value.
+        fn assert_agrees(input: &str) -> Result<(), TestCaseError> {
+            let ours = parse_context_json(input);
+            let theirs = serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(input);
+            match (ours, theirs) {
+                (Ok(ours), Ok(theirs)) => {
+                    prop_assert_eq!(ContextValue::Struct(ours), from_serde(theirs.into()))
+                }
+                (Err(Error::InvalidArgument(_)), Err(_)) => {}
+                (ours, theirs) => {
+                    prop_assert!(
+                        false,
+                        "{:?}: ours {:?}, serde_json {:?}",
+                        input,
+                        ours,
+                        theirs
+                    )
+                }
+            }
+            Ok(())
+        }
+
+        /// Rewrites every non-ASCII character as a `\u` escape, using a
+        /// surrogate pair outside the basic multilingual plane.
+        fn escape_non_ascii(json: &str) -> String {
+            let mut out = String::new();
+            for c in json.chars() {
+                if c.is_ascii() {
+                    out.push(c);
+                } else {
+                    let mut units = [0u16; 2];
+                    for unit in c.encode_utf16(&mut units) {
+                        out.push_str(&format!("\\u{:04X}", unit));
+                    }
+                }
+            }
+            out
+        }
+
+        fn json_value() -> impl Strategy<Value = serde_json::Value> {
+            let leaf = prop_oneof![
+                Just(serde_json::Value::Null),
+                any::<bool>().prop_map(serde_json::Value::Bool),
+                any::<i64>().prop_map(Into::into),
+                any::<u64>().prop_map(Into::into),
+                any::<f64>()
+                    .prop_filter("finite", |n| n.is_finite())
+                    .prop_map(Into::into),
+                any::<String>().prop_map(serde_json::Value::String),
+            ];
+            leaf.prop_recursive(4, 32, 4, |inner| {
+                prop_oneof![
+                    prop::collection::vec(inner.clone(), 0..4).prop_map(serde_json::Value::Array),
+                    prop::collection::hash_map(any::<String>(), inner, 0..4)
+                        .prop_map(|m| serde_json::Value::Object(m.into_iter().collect())),
+                ]
+            })
+        }
+
+        proptest! {
+            #[test]
+            fn documents_parse_the_same(
+                fields in prop::collection::hash_map(any::<String>(), json_value(), 0..4),
+            ) {
+                let object: serde_json::Map<_, _> = fields.into_iter().collect();
+                let json = serde_json::to_string(&object).unwrap();
+                assert_agrees(&json)?;
+                assert_agrees(&escape_non_ascii(&json))?;
+            }
+
+            #[test]
+            fn numbers_parse_the_same(
+                number in r"-?(0|[1-9][0-9]{0,24})(\.[0-9]{1,24})?([eE][+-]?[0-9]{1,4})?",
+            ) {
+                assert_agrees(&format!(r#"{{"n":{}}}"#, number))?;
+            }
+
+            #[test]
+            fn escapes_parse_the_same(
+                body in r#"([a-z ]|\\["\\/bfnrt]|\\u[0-9a-fA-F]{4}|\\u[dD][89abAB][0-9a-fA-F]{2}\\u[dD][c-fC-F][0-9a-fA-F]{2}|\\.)*"#,
+            ) {
+                assert_agrees(&format!(r#"{{"s":"{}"}}"#, body))?;
+            }
+
+            #[test]
+            fn arbitrary_text_parses_the_same(
+                body in r#"[ \t\n{}\[\]":,.0-9eE+\-\\/utrfalsn]{0,48}"#,
+            ) {
+                assert_agrees(&format!("{{{}", body))?;
+                assert_agrees(&body)?;
+            }
+        }
+    }
 }
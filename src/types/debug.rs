@@ -0,0 +1,239 @@
+//! Check debug traces (`with_tracing`).
+
+use std::fmt;
+use std::fmt::Write;
+use std::time::Duration;
+
+use crate::error::Error;
+use crate::proto;
+use crate::types::{ObjectReference, PermissionResult, SubjectReference};
+
+/// How SpiceDB resolved a check, step by step, as returned by
+/// `CheckPermissionRequest::send_with_debug`.
+///
+/// Each node is one sub-problem: whether `subject` has `permission` on
+/// `resource`, with the sub-problems it was computed from as `children`.
+/// [`render`](Self::render) (or `Display`) gives an indented text tree to
+/// paste into a ticket; the fields are there for programmatic use.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugTrace {
+    /// The resource checked at this step.
+    pub resource: ObjectReference,
+    /// The relation or permission checked at this step.
+    pub permission: String,
+    /// `true` if [`permission`](Self::permission) is a relation rather than
+    /// a permission.
+    pub is_relation: bool,
+    /// The subject checked.
+    pub subject: SubjectReference,
+    /// The result of this step.
+    pub result: PermissionResult,
+    /// The name of the caveat evaluated at this step, if any.
+    pub caveat: Option<String>,
+    /// How long this step took on the server, if reported.
+    pub duration: Option<Duration>,
+    /// `true` if the result came from SpiceDB's dispatch cache, in which
+    /// case there are no children.
+    pub cached: bool,
+    /// The sub-problems this step was computed from.
+    pub children: Vec<DebugTrace>,
+}
+
+impl DebugTrace {
+    pub(crate) fn from_proto(trace: proto::CheckDebugTrace) -> Result<Self, Error> {
+        use proto::check_debug_trace::{PermissionType, Resolution};
+
+        let resource = trace
+            .resource
+            .ok_or_else(|| Error::Serialization("missing trace resource".into()))?
+            .try_into()?;
+        let subject = trace
+            .subject
+            .ok_or_else(|| Error::Serialization("missing trace subject".into()))?
+            .try_into()?;
+        let caveat = trace.caveat_evaluation_info;
+        let result = PermissionResult::from_check_response(
+            trace.result,
+            caveat.as_ref().and_then(|c| c.partial_caveat_info.clone()),
+        )?;
+        let (cached, children) = match trace.resolution {
+            Some(Resolution::WasCachedResult(cached)) => (cached, Vec::new()),
+            Some(Resolution::SubProblems(sub)) => (
+                false,
+                sub.traces
+                    .into_iter()
+                    .map(DebugTrace::from_proto)
+                    .collect::<Result<_, _>>()?,
+            ),
+            None => (false, Vec::new()),
+        };
+
+        Ok(Self {
+            resource,
+            permission: trace.permission,
+            is_relation: trace.permission_type == PermissionType::Relation as i32,
+            subject,
+            result,
+            caveat: caveat
+                .map(|c| c.caveat_name)
+                .filter(|name| !name.is_empty()),
+            duration: trace.duration.and_then(|d| Duration::try_from(d).ok()),
+            cached,
+            children,
+        })
+    }
+
+    /// Renders the trace as an indented tree, one step per line.
+    ///
+    /// A chain of single sub-problems on the same resource is folded into
+    /// one line with `via`, and a relation step satisfied directly by a
+    /// relationship ends with `-> subject`:
+    ///
+    /// ```text
+    /// document:doc-1 view = ALLOWED via viewer -> user:alice
+    /// ```
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        self.render_into(&mut out, 0);
+        out
+    }
+
+    fn render_into(&self, out: &mut String, depth: usize) {
+        let _ = write!(
+            out,
+            "{:indent$}{} {} = {}",
+            "",
+            self.resource,
+            self.permission,
+            result_label(&self.result),
+            indent = depth * 2
+        );
+        let mut node = self;
+        while let [child] = node.children.as_slice() {
+            if child.resource != node.resource {
+                break;
+            }
+            let _ = write!(out, " via {}", child.permission);
+            node = child;
+        }
+        if let Some(caveat) = &node.caveat {
+            let _ = write!(out, " [{}]", caveat);
+        }
+        if node.cached {
+            out.push_str(" (cached)");
+        } else if node.is_relation && node.children.is_empty() && !node.result.is_denied() {
+            let _ = write!(out, " -> {}", node.subject);
+        }
+        for child in &node.children {
+            out.push('\n');
+            child.render_into(out, depth + 1);
+        }
+    }
+}
+
+fn result_label(result: &PermissionResult) -> &'static str {
+    match result {
+        PermissionResult::Allowed => "ALLOWED",
+        PermissionResult::Denied => "DENIED",
+        PermissionResult::Conditional { .. } => "CONDITIONAL",
+        PermissionResult::Unknown(_) => "UNKNOWN",
+    }
+}
+
+impl fmt::Display for DebugTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step(resource: &str, permission: &str, is_relation: bool, allowed: bool) -> DebugTrace {
+        DebugTrace {
+            resource: ObjectReference::parse(resource).unwrap(),
+            permission: permission.into(),
+            is_relation,
+            subject: SubjectReference::parse("user:alice").unwrap(),
+            result: if allowed {
+                PermissionResult::Allowed
+            } else {
+                PermissionResult::Denied
+            },
+            caveat: None,
+            duration: None,
+            cached: false,
+            children: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn single_chain_renders_on_one_line() {
+        let mut root = step("document:doc-1", "view", false, true);
+        root.children = vec![step("document:doc-1", "viewer", true, true)];
+        assert_eq!(
+            root.render(),
+            "document:doc-1 view = ALLOWED via viewer -> user:alice"
+        );
+    }
+
+    #[test]
+    fn branches_are_indented() {
+        let mut parent = step("folder:f1", "view", false, true);
+        parent.cached = true;
+        let mut root = step("document:doc-1", "view", false, true);
+        root.children = vec![step("document:doc-1", "viewer", true, false), parent];
+        assert_eq!(
+            root.to_string(),
+            "document:doc-1 view = ALLOWED\n  \
+             document:doc-1 viewer = DENIED\n  \
+             folder:f1 view = ALLOWED (cached)"
+        );
+    }
+
+    #[test]
+    fn decodes_proto_trace() {
+        use proto::check_debug_trace::{PermissionType, Resolution, SubProblems};
+
+        let reference = |t: &str, id: &str| proto::ObjectReference {
+            object_type: t.into(),
+            object_id: id.into(),
+        };
+        let subject = proto::SubjectReference {
+            object: Some(reference("user", "alice")),
+            optional_relation: String::new(),
+        };
+        let leaf = proto::CheckDebugTrace {
+            resource: Some(reference("document", "doc-1")),
+            permission: "viewer".into(),
+            permission_type: PermissionType::Relation as i32,
+            subject: Some(subject.clone()),
+            result: 2,
+            resolution: Some(Resolution::WasCachedResult(false)),
+            ..Default::default()
+        };
+        let root = proto::CheckDebugTrace {
+            resource: Some(reference("document", "doc-1")),
+            permission: "view".into(),
+            permission_type: PermissionType::Permission as i32,
+            subject: Some(subject),
+            result: 2,
+            duration: Some(prost_types::Duration {
+                seconds: 0,
+                nanos: 1_000_000,
+            }),
+            resolution: Some(Resolution::SubProblems(SubProblems { traces: vec![leaf] })),
+            ..Default::default()
+        };
+
+        let trace = DebugTrace::from_proto(root).unwrap();
+        assert_eq!(trace.duration, Some(Duration::from_millis(1)));
+        assert!(!trace.is_relation);
+        assert!(trace.children[0].is_relation);
+        assert_eq!(
+            trace.render(),
+            "document:doc-1 view = ALLOWED via viewer -> user:alice"
+        );
+    }
+}
@@ -0,0 +1,343 @@
+//! Permission explanations (behind `experimental` feature).
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::error::Error;
+use crate::proto;
+use crate::types::{ObjectReference, SubjectReference, ZedToken};
+
+/// An expanded permission tree annotated with the schema rules behind each
+/// node, as returned by [`Client::explain_permission`](crate::Client::explain_permission).
+///
+/// `Display` renders it as an indented tree for humans; the fields are there
+/// for UIs that want to render it themselves.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Explanation {
+    /// The root node: the permission that was explained.
+    pub root: ExplanationNode,
+    /// The ZedToken at which the permission was expanded.
+    pub expanded_at: ZedToken,
+}
+
+/// A node of an [`Explanation`]: the subjects of one relation or permission
+/// on one object, and how they were derived.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExplanationNode {
+    /// The object this node expands.
+    pub object: ObjectReference,
+    /// The relation or permission this node expands.
+    pub relation: String,
+    /// The schema rule `relation` refers to, or `None` if the schema has no
+    /// such relation or permission on the object's type (e.g. it changed
+    /// between the expansion and the reflection).
+    pub rule: Option<SchemaRule>,
+    /// How the node's subjects are derived.
+    pub kind: ExplanationKind,
+}
+
+/// What kind of schema rule an [`ExplanationNode`] expands.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SchemaRule {
+    /// A relation, with the subject types it allows in schema syntax
+    /// (e.g. `user`, `group#member`, `user:*`, `user with ip_allowlist`).
+    Relation {
+        /// The allowed subject types.
+        subject_types: Vec<String>,
+    },
+    /// A permission, computed from other relations and permissions.
+    Permission,
+}
+
+/// How an [`ExplanationNode`]'s subjects are derived, mirroring
+/// [`PermissionTreeNode`](crate::PermissionTreeNode).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ExplanationKind {
+    /// Subjects written directly on the relation.
+    Leaf {
+        /// The subjects at this leaf.
+        subjects: Vec<SubjectReference>,
+    },
+    /// Subjects in any child.
+    Union {
+        /// The child nodes.
+        children: Vec<ExplanationNode>,
+    },
+    /// Subjects in every child.
+    Intersection {
+        /// The child nodes.
+        children: Vec<ExplanationNode>,
+    },
+    /// Subjects in `base` but not in `excluded`.
+    Exclusion {
+        /// The base set.
+        base: Box<ExplanationNode>,
+        /// The set to exclude.
+        excluded: Box<ExplanationNode>,
+    },
+}
+
+/// The schema rules of a reflected schema, keyed by definition and name.
+pub(crate) type SchemaRules = HashMap<(String, String), SchemaRule>;
+
+/// Indexes the relations and permissions of reflected `definitions`.
+pub(crate) fn schema_rules(definitions: Vec<proto::ExpDefinition>) -> SchemaRules {
+    let mut rules = HashMap::new();
+    for definition in definitions {
+        for relation in definition.relations {
+            let subject_types = relation.subject_types.iter().map(type_reference).collect();
+            rules.insert(
+                (definition.name.clone(), relation.name),
+                SchemaRule::Relation { subject_types },
+            );
+        }
+        for permission in definition.permissions {
+            rules.insert(
+                (definition.name.clone(), permission.name),
+                SchemaRule::Permission,
+            );
+        }
+    }
+    rules
+}
+
+/// Renders an allowed subject type as it is written in a schema.
+fn type_reference(t: &proto::ExpTypeReference) -> String {
+    use proto::exp_type_reference::Typeref;
+
+    let mut s = t.subject_definition_name.clone();
+    match &t.typeref {
+        Some(Typeref::OptionalRelationName(relation)) if !relation.is_empty() => {
+            s.push('#');
+            s.push_str(relation);
+        }
+        Some(Typeref::IsPublicWildcard(true)) => s.push_str(":*"),
+        _ => {}
+    }
+    if !t.optional_caveat_name.is_empty() {
+        s.push_str(" with ");
+        s.push_str(&t.optional_caveat_name);
+    }
+    s
+}
+
+impl ExplanationNode {
+    pub(crate) fn from_proto(
+        tree: proto::PermissionRelationshipTree,
+        rules: &SchemaRules,
+    ) -> Result<Self, Error> {
+        use proto::algebraic_subject_set::Operation;
+        use proto::permission_relationship_tree::TreeType;
+
+        let object: ObjectReference = tree
+            .expanded_object
+            .ok_or_else(|| Error::Serialization("missing expanded_object".into()))?
+            .try_into()?;
+        let rule = rules
+            .get(&(
+                object.object_type().to_string(),
+                tree.expanded_relation.clone(),
+            ))
+            .cloned();
+
+        let kind = match tree.tree_type {
+            Some(TreeType::Leaf(leaf)) => ExplanationKind::Leaf {
+                subjects: leaf
+                    .subjects
+                    .into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?,
+            },
+            Some(TreeType::Intermediate(set)) => {
+                let children = set
+                    .children
+                    .into_iter()
+                    .map(|child| ExplanationNode::from_proto(child, rules))
+                    .collect::<Result<Vec<_>, _>>()?;
+                match Operation::try_from(set.operation) {
+                    Ok(Operation::Union) => ExplanationKind::Union { children },
+                    Ok(Operation::Intersection) => ExplanationKind::Intersection { children },
+                    Ok(Operation::Exclusion) => {
+                        let [base, excluded]: [ExplanationNode; 2] =
+                            children.try_into().map_err(|c: Vec<_>| {
+                                Error::Serialization(format!(
+                                    "exclusion requires exactly 2 children, got {}",
+                                    c.len()
+                                ))
+                            })?;
+                        ExplanationKind::Exclusion {
+                            base: Box::new(base),
+                            excluded: Box::new(excluded),
+                        }
+                    }
+                    Ok(Operation::Unspecified) | Err(_) => {
+                        return Err(Error::Serialization(format!(
+                            "unknown algebraic operation: {}",
+                            set.operation
+                        )))
+                    }
+                }
+            }
+            None => return Err(Error::Serialization("missing tree type".into())),
+        };
+
+        Ok(Self {
+            object,
+            relation: tree.expanded_relation,
+            rule,
+            kind,
+        })
+    }
+
+    fn write_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(
+            f,
+            "{:indent$}{}#{} = ",
+            "",
+            self.object,
+            self.relation,
+            indent = depth * 2
+        )?;
+        let children: Vec<&ExplanationNode> = match &self.kind {
+            ExplanationKind::Leaf { subjects } => {
+                f.write_str("{")?;
+                for (i, subject) in subjects.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", subject)?;
+                }
+                f.write_str("}")?;
+                vec![]
+            }
+            ExplanationKind::Union { children } => {
+                f.write_str("union of")?;
+                children.iter().collect()
+            }
+            ExplanationKind::Intersection { children } => {
+                f.write_str("intersection of")?;
+                children.iter().collect()
+            }
+            ExplanationKind::Exclusion { base, excluded } => {
+                f.write_str("first excluding second of")?;
+                vec![base, excluded]
+            }
+        };
+        match &self.rule {
+            Some(SchemaRule::Relation { subject_types }) => {
+                write!(f, "  (relation: {})", subject_types.join(" | "))?
+            }
+            Some(SchemaRule::Permission) => f.write_str("  (permission)")?,
+            None => f.write_str("  (not in schema)")?,
+        }
+        for child in children {
+            f.write_str("\n")?;
+            child.write_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ExplanationNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_indented(f, 0)
+    }
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.root.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn object(object_type: &str, object_id: &str) -> Option<proto::ObjectReference> {
+        Some(proto::ObjectReference {
+            object_type: object_type.into(),
+            object_id: object_id.into(),
+        })
+    }
+
+    fn leaf(relation: &str, subjects: &[(&str, &str, &str)]) -> proto::PermissionRelationshipTree {
+        use proto::permission_relationship_tree::TreeType;
+        proto::PermissionRelationshipTree {
+            expanded_object: object("document", "doc-1"),
+            expanded_relation: relation.into(),
+            tree_type: Some(TreeType::Leaf(proto::DirectSubjectSet {
+                subjects: subjects
+                    .iter()
+                    .map(|(t, id, rel)| proto::SubjectReference {
+                        object: object(t, id),
+                        optional_relation: rel.to_string(),
+                    })
+                    .collect(),
+            })),
+        }
+    }
+
+    fn rules() -> SchemaRules {
+        use proto::exp_type_reference::Typeref;
+        let type_ref = |name: &str, typeref| proto::ExpTypeReference {
+            subject_definition_name: name.into(),
+            optional_caveat_name: String::new(),
+            typeref: Some(typeref),
+        };
+        schema_rules(vec![proto::ExpDefinition {
+            name: "document".into(),
+            comment: String::new(),
+            relations: vec![proto::ExpRelation {
+                name: "viewer".into(),
+                comment: String::new(),
+                parent_definition_name: "document".into(),
+                subject_types: vec![
+                    type_ref("user", Typeref::IsTerminalSubject(true)),
+                    type_ref("group", Typeref::OptionalRelationName("member".into())),
+                    type_ref("user", Typeref::IsPublicWildcard(true)),
+                ],
+            }],
+            permissions: vec![proto::ExpPermission {
+                name: "view".into(),
+                comment: String::new(),
+                parent_definition_name: "document".into(),
+            }],
+        }])
+    }
+
+    #[test]
+    fn annotates_nodes_with_schema_rules() {
+        use proto::permission_relationship_tree::TreeType;
+        let tree = proto::PermissionRelationshipTree {
+            expanded_object: object("document", "doc-1"),
+            expanded_relation: "view".into(),
+            tree_type: Some(TreeType::Intermediate(proto::AlgebraicSubjectSet {
+                operation: proto::algebraic_subject_set::Operation::Union as i32,
+                children: vec![
+                    leaf(
+                        "viewer",
+                        &[("user", "alice", ""), ("group", "eng", "member")],
+                    ),
+                    leaf("editor", &[]),
+                ],
+            })),
+        };
+
+        let node = ExplanationNode::from_proto(tree, &rules()).unwrap();
+        assert_eq!(node.rule, Some(SchemaRule::Permission));
+        let ExplanationKind::Union { children } = &node.kind else {
+            panic!("expected union, got {:?}", node.kind);
+        };
+        assert_eq!(children[1].rule, None);
+        assert_eq!(
+            node.to_string(),
+            "document:doc-1#view = union of  (permission)\n\
+             \x20 document:doc-1#viewer = {user:alice, group:eng#member}  \
+             (relation: user | group#member | user:*)\n\
+             \x20 document:doc-1#editor = {}  (not in schema)"
+        );
+    }
+}
@@ -1,7 +1,7 @@
 //! Relationship filters and subject filters.
 
 use crate::error::Error;
-use crate::types::{Relationship, ZedToken};
+use crate::types::{Relationship, SubjectReference, ZedToken};
 
 /// A filter for selecting relationships by resource type, ID, relation, and/or subject.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -44,6 +44,49 @@ impl RelationshipFilter {
         self.optional_subject_filter = Some(filter);
         self
     }
+
+    /// Adds a subject filter for an exact subject type and ID.
+    ///
+    /// Shorthand for `.subject_filter(SubjectFilter::new(t).subject_id(id))`.
+    /// Subjects with any relation match; use [`RelationshipFilter::subject_filter`]
+    /// with [`SubjectFilter::relation`] to also pin the subject relation.
+    pub fn with_subject(
+        self,
+        subject_type: impl Into<String>,
+        subject_id: impl Into<String>,
+    ) -> Self {
+        self.subject_filter(SubjectFilter::new(subject_type).subject_id(subject_id))
+    }
+
+    /// Builds a filter matching exactly the given relationship's tuple:
+    /// resource type and ID, relation, and subject type, ID, and relation.
+    ///
+    /// A subject without a relation is matched as such (an empty relation
+    /// filter), so `user:alice` does not also match `user:alice#member`.
+    /// Use this to delete a single relationship without risking a filter
+    /// that is broader than intended.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use prescience::{ObjectReference, Relationship, RelationshipFilter, SubjectReference};
+    ///
+    /// let rel = Relationship::new(
+    ///     ObjectReference::new("document", "doc-1").unwrap(),
+    ///     "viewer",
+    ///     SubjectReference::new(ObjectReference::new("user", "alice").unwrap(), None::<String>)
+    ///         .unwrap(),
+    /// );
+    /// let filter = RelationshipFilter::matching(&rel);
+    /// assert_eq!(filter.optional_resource_id.as_deref(), Some("doc-1"));
+    /// assert_eq!(filter.optional_relation.as_deref(), Some("viewer"));
+    /// ```
+    pub fn matching(relationship: &Relationship) -> Self {
+        Self::new(relationship.resource.object_type())
+            .resource_id(relationship.resource.object_id())
+            .relation(relationship.relation.clone())
+            .subject_filter(SubjectFilter::from_subject(&relationship.subject))
+    }
 }
 
 impl From<&RelationshipFilter> for crate::proto::RelationshipFilter {
@@ -59,13 +102,27 @@ impl From<&RelationshipFilter> for crate::proto::RelationshipFilter {
 }
 
 /// A filter on the subject side of a relationship.
+///
+/// The subject relation filter has three states, each with its own wire
+/// encoding:
+///
+/// | State | Builder | `optional_relation` | Matches |
+/// |-------|---------|---------------------|---------|
+/// | Any relation | [`any_relation`](Self::any_relation) (the default) | `None` | `user:alice` and `group:eng#member` alike |
+/// | A specific relation | [`relation("member")`](Self::relation) | `Some("member")` | only `…#member` subject sets |
+/// | No relation | [`no_relation`](Self::no_relation) | `Some("")` | only plain subjects such as `user:alice` |
+///
+/// SpiceDB has no encoding for "some relation, but not none"; to read only
+/// subject sets of a type, filter on each relation in turn or filter the
+/// results locally.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SubjectFilter {
     /// The subject object type.
     pub subject_type: String,
     /// Optional subject object ID.
     pub optional_subject_id: Option<String>,
-    /// Optional relation on the subject.
+    /// Relation on the subject: `None` for any relation, `Some("")` for
+    /// none, or the relation name.
     pub optional_relation: Option<String>,
 }
 
@@ -79,6 +136,29 @@ impl SubjectFilter {
         }
     }
 
+    /// Builds a filter matching exactly `subject`: its type, ID, and
+    /// relation.
+    ///
+    /// A subject without a relation is matched as such
+    /// ([`no_relation`](Self::no_relation)), so `user:alice` does not also
+    /// match `user:alice#member`. Chain [`any_relation`](Self::any_relation)
+    /// to widen it.
+    ///
+    /// ```
+    /// use prescience::{SubjectFilter, SubjectReference};
+    ///
+    /// let subject = SubjectReference::parse("group:eng#member").unwrap();
+    /// assert_eq!(
+    ///     SubjectFilter::from_subject(&subject),
+    ///     SubjectFilter::new("group").subject_id("eng").relation("member")
+    /// );
+    /// ```
+    pub fn from_subject(subject: &SubjectReference) -> Self {
+        Self::new(subject.object().object_type())
+            .subject_id(subject.object().object_id())
+            .relation(subject.optional_relation().unwrap_or_default())
+    }
+
     /// Adds a subject ID filter.
     pub fn subject_id(mut self, id: impl Into<String>) -> Self {
         self.optional_subject_id = Some(id.into());
@@ -86,10 +166,26 @@ impl SubjectFilter {
     }
 
     /// Adds a relation filter on the subject.
+    ///
+    /// An empty `relation` is the same as [`no_relation`](Self::no_relation).
     pub fn relation(mut self, relation: impl Into<String>) -> Self {
         self.optional_relation = Some(relation.into());
         self
     }
+
+    /// Matches subjects with any relation, or none. This is the default;
+    /// use it to clear an earlier [`relation`](Self::relation) or
+    /// [`no_relation`](Self::no_relation).
+    pub fn any_relation(mut self) -> Self {
+        self.optional_relation = None;
+        self
+    }
+
+    /// Matches only subjects without a relation, such as `user:alice`,
+    /// excluding subject sets such as `user:alice#member`.
+    pub fn no_relation(self) -> Self {
+        self.relation("")
+    }
 }
 
 impl From<&SubjectFilter> for crate::proto::SubjectFilter {
@@ -133,3 +229,97 @@ impl ReadRelationshipResult {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn with_subject_sets_exact_subject() {
+        let f = RelationshipFilter::new("document").with_subject("user", "alice");
+        let proto: crate::proto::RelationshipFilter = (&f).into();
+        let subject = proto.optional_subject_filter.unwrap();
+        assert_eq!(subject.subject_type, "user");
+        assert_eq!(subject.optional_subject_id, "alice");
+        assert!(subject.optional_relation.is_none());
+    }
+
+    #[test]
+    fn matching_pins_every_field() {
+        use crate::types::{ObjectReference, SubjectReference};
+        let rel = Relationship::new(
+            ObjectReference::new("document", "doc-1").unwrap(),
+            "viewer",
+            SubjectReference::new(
+                ObjectReference::new("group", "eng").unwrap(),
+                Some("member"),
+            )
+            .unwrap(),
+        );
+        let f = RelationshipFilter::matching(&rel);
+        assert_eq!(f.resource_type, "document");
+        assert_eq!(f.optional_resource_id.as_deref(), Some("doc-1"));
+        assert_eq!(f.optional_relation.as_deref(), Some("viewer"));
+        assert_eq!(
+            f.optional_subject_filter,
+            Some(
+                SubjectFilter::new("group")
+                    .subject_id("eng")
+                    .relation("member")
+            )
+        );
+    }
+
+    #[test]
+    fn matching_subject_without_relation_requires_none() {
+        use crate::types::{ObjectReference, SubjectReference};
+        let rel = Relationship::new(
+            ObjectReference::new("document", "doc-1").unwrap(),
+            "viewer",
+            SubjectReference::new(
+                ObjectReference::new("user", "alice").unwrap(),
+                None::<String>,
+            )
+            .unwrap(),
+        );
+        let proto: crate::proto::RelationshipFilter = (&RelationshipFilter::matching(&rel)).into();
+        let relation = proto.optional_subject_filter.unwrap().optional_relation;
+        assert_eq!(relation.unwrap().relation, "");
+    }
+
+    #[test]
+    fn from_subject_keeps_relation_state() {
+        let plain = SubjectReference::parse("user:alice").unwrap();
+        assert_eq!(
+            SubjectFilter::from_subject(&plain),
+            SubjectFilter::new("user").subject_id("alice").no_relation()
+        );
+
+        let set = SubjectReference::parse("group:eng#member").unwrap();
+        let f = SubjectFilter::from_subject(&set);
+        assert_eq!(f.optional_subject_id.as_deref(), Some("eng"));
+        assert_eq!(f.optional_relation.as_deref(), Some("member"));
+    }
+
+    #[test]
+    fn subject_relation_states_map_to_proto() {
+        let encode = |f: SubjectFilter| crate::proto::SubjectFilter::from(&f).optional_relation;
+        let base = SubjectFilter::new("user");
+
+        assert!(encode(base.clone().relation("member").any_relation()).is_none());
+        assert_eq!(encode(base.clone().no_relation()).unwrap().relation, "");
+        assert_eq!(encode(base.relation("member")).unwrap().relation, "member");
+    }
+
+    #[test]
+    fn subject_relation_uses_nested_relation_filter() {
+        let f = RelationshipFilter::new("document").subject_filter(
+            SubjectFilter::new("group")
+                .subject_id("eng")
+                .relation("member"),
+        );
+        let proto: crate::proto::RelationshipFilter = (&f).into();
+        let relation = proto.optional_subject_filter.unwrap().optional_relation;
+        assert_eq!(relation.unwrap().relation, "member");
+    }
+}
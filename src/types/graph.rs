@@ -0,0 +1,221 @@
+//! An in-memory graph of relationships, for walking or rendering small
+//! subgraphs.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+use crate::types::{ObjectReference, Relationship};
+
+/// Relationships assembled into an adjacency structure keyed by object, as
+/// returned by [`Client::read_graph`](crate::Client::read_graph).
+///
+/// Each relationship is an edge from its resource to its subject's object.
+/// The whole graph is held in memory, so build it from bounded reads (e.g.
+/// one document's relationships); stream large sets with
+/// [`Client::read_relationships`](crate::Client::read_relationships)
+/// instead. It can also be collected from any iterator of relationships.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RelationshipGraph {
+    relationships: Vec<Relationship>,
+    /// Indexes into `relationships`, by resource.
+    outgoing: HashMap<ObjectReference, Vec<usize>>,
+    /// Indexes into `relationships`, by subject object.
+    incoming: HashMap<ObjectReference, Vec<usize>>,
+}
+
+impl RelationshipGraph {
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `relationship` as an edge.
+    pub fn insert(&mut self, relationship: Relationship) {
+        let index = self.relationships.len();
+        self.outgoing
+            .entry(relationship.resource.clone())
+            .or_default()
+            .push(index);
+        self.incoming
+            .entry(relationship.subject.object().clone())
+            .or_default()
+            .push(index);
+        self.relationships.push(relationship);
+    }
+
+    /// All relationships, in the order they were added.
+    pub fn relationships(&self) -> &[Relationship] {
+        &self.relationships
+    }
+
+    /// The number of relationships (edges).
+    pub fn len(&self) -> usize {
+        self.relationships.len()
+    }
+
+    /// Returns `true` if the graph has no relationships.
+    pub fn is_empty(&self) -> bool {
+        self.relationships.is_empty()
+    }
+
+    /// Every object that is a resource or a subject of some relationship,
+    /// in no particular order.
+    pub fn objects(&self) -> impl Iterator<Item = &ObjectReference> {
+        self.outgoing.keys().chain(
+            self.incoming
+                .keys()
+                .filter(|object| !self.outgoing.contains_key(*object)),
+        )
+    }
+
+    /// The relationships whose resource is `object`: the edges to its
+    /// subjects.
+    pub fn subjects_of<'a>(
+        &'a self,
+        object: &ObjectReference,
+    ) -> impl Iterator<Item = &'a Relationship> + 'a {
+        self.edges(&self.outgoing, object)
+    }
+
+    /// The relationships whose subject is `object` (with any subject
+    /// relation): the edges to the resources it relates to.
+    pub fn resources_of<'a>(
+        &'a self,
+        object: &ObjectReference,
+    ) -> impl Iterator<Item = &'a Relationship> + 'a {
+        self.edges(&self.incoming, object)
+    }
+
+    fn edges<'a>(
+        &'a self,
+        index: &'a HashMap<ObjectReference, Vec<usize>>,
+        object: &ObjectReference,
+    ) -> impl Iterator<Item = &'a Relationship> + 'a {
+        index
+            .get(object)
+            .into_iter()
+            .flatten()
+            .map(|&i| &self.relationships[i])
+    }
+
+    /// Renders the graph in Graphviz DOT format.
+    ///
+    /// Each relationship becomes an edge from resource to subject labelled
+    /// with the relation; a subject relation is shown as the edge's
+    /// `headlabel` and a caveat name is appended to the label in brackets.
+    ///
+    /// ```
+    /// use prescience::{Relationship, RelationshipGraph};
+    ///
+    /// let graph: RelationshipGraph = [Relationship::parse("document:1#viewer@user:alice").unwrap()]
+    ///     .into_iter()
+    ///     .collect();
+    /// assert_eq!(
+    ///     graph.to_dot(),
+    ///     "digraph {\n  \"document:1\" -> \"user:alice\" [label=\"viewer\"];\n}\n"
+    /// );
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n");
+        for relationship in &self.relationships {
+            let mut label = relationship.relation.clone();
+            if let Some(caveat) = &relationship.optional_caveat {
+                let _ = write!(label, " [{}]", caveat.name);
+            }
+            let _ = write!(
+                dot,
+                "  {} -> {} [label={}",
+                quoted(&relationship.resource.to_string()),
+                quoted(&relationship.subject.object().to_string()),
+                quoted(&label)
+            );
+            if let Some(subject_relation) = relationship.subject.optional_relation() {
+                let _ = write!(dot, ", headlabel={}", quoted(subject_relation));
+            }
+            dot.push_str("];\n");
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl FromIterator<Relationship> for RelationshipGraph {
+    fn from_iter<I: IntoIterator<Item = Relationship>>(iter: I) -> Self {
+        let mut graph = Self::new();
+        graph.extend(iter);
+        graph
+    }
+}
+
+impl Extend<Relationship> for RelationshipGraph {
+    fn extend<I: IntoIterator<Item = Relationship>>(&mut self, iter: I) {
+        for relationship in iter {
+            self.insert(relationship);
+        }
+    }
+}
+
+/// Quotes `s` as a DOT string.
+fn quoted(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(tuples: &[&str]) -> RelationshipGraph {
+        tuples
+            .iter()
+            .map(|t| Relationship::parse(t).unwrap())
+            .collect()
+    }
+
+    fn object(s: &str) -> ObjectReference {
+        ObjectReference::parse(s).unwrap()
+    }
+
+    #[test]
+    fn traverses_both_directions() {
+        let g = graph(&[
+            "document:1#viewer@user:alice",
+            "document:1#viewer@group:eng#member",
+            "group:eng#member@user:alice",
+        ]);
+        assert_eq!(g.len(), 3);
+        assert_eq!(g.objects().count(), 3);
+
+        let subjects: Vec<String> = g
+            .subjects_of(&object("document:1"))
+            .map(|r| r.subject.to_string())
+            .collect();
+        assert_eq!(subjects, ["user:alice", "group:eng#member"]);
+
+        let resources: Vec<String> = g
+            .resources_of(&object("user:alice"))
+            .map(|r| r.resource.to_string())
+            .collect();
+        assert_eq!(resources, ["document:1", "group:eng"]);
+        assert_eq!(g.resources_of(&object("document:1")).count(), 0);
+    }
+
+    #[test]
+    fn dot_shows_subject_relations_and_caveats() {
+        let g = graph(&[
+            "document:1#viewer@group:eng#member",
+            "document:1#viewer@user:bob[ip_allowlist]",
+        ]);
+        assert_eq!(
+            g.to_dot(),
+            "digraph {\n\
+             \x20 \"document:1\" -> \"group:eng\" [label=\"viewer\", headlabel=\"member\"];\n\
+             \x20 \"document:1\" -> \"user:bob\" [label=\"viewer [ip_allowlist]\"];\n\
+             }\n"
+        );
+    }
+
+    #[test]
+    fn dot_escapes_quotes() {
+        assert_eq!(quoted(r#"a"b\c"#), r#""a\"b\\c""#);
+    }
+}
@@ -3,9 +3,14 @@
 //! These are idiomatic Rust types wrapping the generated protobuf types.
 //! The proto types are internal implementation details and are never exposed.
 
+mod caveat;
 mod consistency;
 pub(crate) mod context;
+mod debug;
+#[cfg(feature = "experimental")]
+pub(crate) mod explanation;
 mod filter;
+mod graph;
 mod permission;
 mod reference;
 mod relationship;
@@ -13,13 +18,19 @@ mod token;
 #[cfg(feature = "watch")]
 mod watch;
 
+pub use caveat::{CaveatDefinition, CaveatParamType};
+pub(crate) use consistency::exact_snapshot;
 pub use consistency::Consistency;
 pub use context::ContextValue;
+pub use debug::DebugTrace;
+#[cfg(feature = "experimental")]
+pub use explanation::{Explanation, ExplanationKind, ExplanationNode, SchemaRule};
 pub use filter::{RelationshipFilter, SubjectFilter};
-pub use permission::{PermissionResult, PermissionTree, PermissionTreeNode};
+pub use graph::RelationshipGraph;
+pub use permission::{ConditionalPolicy, PermissionResult, PermissionTree, PermissionTreeNode};
 pub use reference::{ObjectReference, SubjectReference};
 pub use relationship::{
-    Caveat, Operation, Precondition, PreconditionOp, Relationship, RelationshipUpdate,
+    Caveat, Operation, Precondition, PreconditionOp, Relationship, RelationshipUpdate, WriteProblem,
 };
 pub use token::ZedToken;
 #[cfg(feature = "watch")]
@@ -27,5 +38,5 @@ pub use watch::WatchEvent;
 
 // Re-export streaming result types
 pub use filter::ReadRelationshipResult;
-pub use permission::CheckResult;
+pub use permission::{BulkCheckResults, CheckResult};
 pub use permission::{LookupResourceResult, LookupSubjectResult};
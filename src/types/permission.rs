@@ -9,8 +9,14 @@ use crate::types::{ObjectReference, SubjectReference, ZedToken};
 /// the permission, or the permission is conditional on unresolved caveat context.
 ///
 /// Use [`is_allowed()`](PermissionResult::is_allowed) for a convenience boolean,
-/// but note that it returns `Err` for `Conditional` to force explicit handling.
+/// but note that it returns `Err` for `Conditional` to force explicit handling;
+/// [`to_bool()`](PermissionResult::to_bool) takes that decision as a
+/// [`ConditionalPolicy`] instead.
+///
+/// The enum is `#[non_exhaustive]`: a `match` outside this crate needs a
+/// wildcard arm, which should deny.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum PermissionResult {
     /// The subject definitively has the permission.
     Allowed,
@@ -21,14 +27,29 @@ pub enum PermissionResult {
         /// Context fields needed to fully evaluate the caveat.
         missing_fields: Vec<String>,
     },
+    /// A permissionship value this client does not recognize, e.g. a state
+    /// added by a newer SpiceDB. Treat it as not allowed.
+    Unknown(i32),
+}
+
+/// How [`PermissionResult::to_bool`] treats a `Conditional` result.
+///
+/// There is deliberately no default: each call site states its policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConditionalPolicy {
+    /// A conditional result counts as allowed (fail open).
+    TreatAsAllowed,
+    /// A conditional result counts as denied (fail closed).
+    TreatAsDenied,
 }
 
 impl PermissionResult {
-    /// Returns `Ok(true)` for `Allowed`, `Ok(false)` for `Denied`, and
-    /// `Err(Error::ConditionalPermission)` for `Conditional`.
+    /// Returns `Ok(true)` for `Allowed`, `Ok(false)` for `Denied`,
+    /// `Err(Error::ConditionalPermission)` for `Conditional`, and
+    /// `Err(Error::Serialization)` for `Unknown`.
     ///
-    /// This forces callers to handle the conditional case explicitly rather
-    /// than silently dropping it.
+    /// This forces callers to handle the conditional and unknown cases
+    /// explicitly rather than silently dropping them.
     pub fn is_allowed(&self) -> Result<bool, Error> {
         match self {
             PermissionResult::Allowed => Ok(true),
@@ -36,6 +57,31 @@ impl PermissionResult {
             PermissionResult::Conditional { missing_fields } => Err(Error::ConditionalPermission {
                 missing_fields: missing_fields.clone(),
             }),
+            PermissionResult::Unknown(permissionship) => Err(Error::Serialization(format!(
+                "unknown permissionship: {}",
+                permissionship
+            ))),
+        }
+    }
+
+    /// Converts the result to a `bool`, resolving `Conditional` by `policy`.
+    ///
+    /// A total alternative to [`is_allowed`](Self::is_allowed) for call
+    /// sites that have decided up front how to treat caveats. `Unknown` is
+    /// always `false`, whatever the policy.
+    ///
+    /// ```
+    /// use prescience::{ConditionalPolicy, PermissionResult};
+    ///
+    /// let result = PermissionResult::Conditional { missing_fields: vec!["ip".into()] };
+    /// assert!(!result.to_bool(ConditionalPolicy::TreatAsDenied));
+    /// assert!(result.to_bool(ConditionalPolicy::TreatAsAllowed));
+    /// ```
+    pub fn to_bool(&self, policy: ConditionalPolicy) -> bool {
+        match self {
+            PermissionResult::Allowed => true,
+            PermissionResult::Denied | PermissionResult::Unknown(_) => false,
+            PermissionResult::Conditional { .. } => policy == ConditionalPolicy::TreatAsAllowed,
         }
     }
 
@@ -61,10 +107,7 @@ impl PermissionResult {
                     .map(|info| info.missing_required_context)
                     .unwrap_or_default(),
             }),
-            other => Err(Error::Serialization(format!(
-                "unknown permissionship: {}",
-                other
-            ))),
+            other => Ok(PermissionResult::unknown(other)),
         }
     }
 
@@ -79,17 +122,25 @@ impl PermissionResult {
                     .map(|info| info.missing_required_context)
                     .unwrap_or_default(),
             }),
-            other => Err(Error::Serialization(format!(
-                "unknown lookup permissionship: {}",
-                other
-            ))),
+            other => Ok(PermissionResult::unknown(other)),
         }
     }
+
+    fn unknown(permissionship: i32) -> Self {
+        tracing::warn!(
+            permissionship,
+            "unrecognized permissionship from SpiceDB; treating it as unknown"
+        );
+        PermissionResult::Unknown(permissionship)
+    }
 }
 
 /// A resource found by LookupResources, with its permission status and token.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LookupResourceResult {
+    /// The object type of the resource, as passed to
+    /// [`Client::lookup_resources`](crate::Client::lookup_resources).
+    pub resource_type: String,
     /// The resource object ID.
     pub resource_id: String,
     /// The permission status for this resource.
@@ -99,7 +150,20 @@ pub struct LookupResourceResult {
 }
 
 impl LookupResourceResult {
-    pub(crate) fn from_proto(proto: crate::proto::LookupResourcesResponse) -> Result<Self, Error> {
+    /// Builds the full [`ObjectReference`] for this result from
+    /// [`resource_type`](Self::resource_type) and
+    /// [`resource_id`](Self::resource_id), ready to pass to a follow-up
+    /// check or write.
+    ///
+    /// Returns `Err` if the ID is not valid for a reference.
+    pub fn resource_reference(&self) -> Result<ObjectReference, Error> {
+        ObjectReference::new(self.resource_type.as_str(), self.resource_id.as_str())
+    }
+
+    pub(crate) fn from_proto(
+        proto: crate::proto::LookupResourcesResponse,
+        resource_type: String,
+    ) -> Result<Self, Error> {
         let permission = PermissionResult::from_lookup_permissionship(
             proto.permissionship,
             proto.partial_caveat_info,
@@ -109,6 +173,7 @@ impl LookupResourceResult {
             .ok_or_else(|| Error::Serialization("missing looked_up_at".into()))?
             .try_into()?;
         Ok(Self {
+            resource_type,
             resource_id: proto.resource_object_id,
             permission,
             looked_up_at,
@@ -119,8 +184,18 @@ impl LookupResourceResult {
 /// A subject found by LookupSubjects, with its permission status and token.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct LookupSubjectResult {
+    /// The object type of the resolved subject, as passed to
+    /// [`Client::lookup_subjects`](crate::Client::lookup_subjects).
+    pub subject_type: String,
     /// The resolved subject.
     pub subject_id: String,
+    /// The relation of the resolved subject, when the lookup asked for
+    /// subject sets (e.g. `member` for `group:eng#member`).
+    ///
+    /// SpiceDB does not echo the relation back, so this is the relation the
+    /// request was built with via `.subject_relation(..)` on
+    /// [`Client::lookup_subjects`](crate::Client::lookup_subjects).
+    pub subject_relation: Option<String>,
     /// Subjects excluded from a wildcard match.
     pub excluded_subject_ids: Vec<String>,
     /// The permission status for this subject.
@@ -130,7 +205,39 @@ pub struct LookupSubjectResult {
 }
 
 impl LookupSubjectResult {
-    pub(crate) fn from_proto(proto: crate::proto::LookupSubjectsResponse) -> Result<Self, Error> {
+    /// Builds the full [`SubjectReference`] for this result from
+    /// [`subject_type`](Self::subject_type), [`subject_id`](Self::subject_id)
+    /// and, if set, [`subject_relation`](Self::subject_relation), ready to
+    /// pass to a follow-up check or write.
+    ///
+    /// Returns `Err` if the ID or relation is not valid for a reference.
+    pub fn subject_reference(&self) -> Result<SubjectReference, Error> {
+        SubjectReference::new(
+            ObjectReference::new(self.subject_type.as_str(), self.subject_id.as_str())?,
+            self.subject_relation.as_deref(),
+        )
+    }
+
+    /// Reassembles the subject as a [`SubjectReference`] of `subject_type`,
+    /// including [`subject_relation`](Self::subject_relation) if set.
+    ///
+    /// Returns `Err` if the ID or relation is not valid for a reference.
+    #[deprecated(note = "use `subject_reference`, which uses the recorded `subject_type`")]
+    pub fn as_subject_reference(
+        &self,
+        subject_type: impl Into<String>,
+    ) -> Result<SubjectReference, Error> {
+        SubjectReference::new(
+            ObjectReference::new(subject_type, self.subject_id.as_str())?,
+            self.subject_relation.as_deref(),
+        )
+    }
+
+    pub(crate) fn from_proto(
+        proto: crate::proto::LookupSubjectsResponse,
+        subject_type: String,
+        subject_relation: Option<String>,
+    ) -> Result<Self, Error> {
         let looked_up_at = proto
             .looked_up_at
             .ok_or_else(|| Error::Serialization("missing looked_up_at".into()))?
@@ -148,7 +255,9 @@ impl LookupSubjectResult {
                 .map(|s| s.subject_object_id)
                 .collect();
             Ok(Self {
+                subject_type,
                 subject_id: resolved.subject_object_id,
+                subject_relation,
                 excluded_subject_ids: excluded_ids,
                 permission,
                 looked_up_at,
@@ -162,7 +271,9 @@ impl LookupSubjectResult {
             )?;
             #[allow(deprecated)]
             Ok(Self {
+                subject_type,
                 subject_id: proto.subject_object_id,
+                subject_relation,
                 excluded_subject_ids: proto.excluded_subject_ids,
                 permission,
                 looked_up_at,
@@ -174,6 +285,96 @@ impl LookupSubjectResult {
 /// Per-item result from a bulk check operation.
 pub type CheckResult = Result<PermissionResult, Error>;
 
+/// The per-item results of a bulk check, in the same order as the request items.
+///
+/// Dereferences to `[CheckResult]`, so indexing, `len()`, and `iter()` work as
+/// on a slice. The helpers cover the common ways of consuming the results.
+///
+/// # Examples
+///
+/// ```
+/// use prescience::{BulkCheckResults, PermissionResult};
+///
+/// let docs = ["doc-1", "doc-2", "doc-3"];
+/// let results = BulkCheckResults::from(vec![
+///     Ok(PermissionResult::Allowed),
+///     Ok(PermissionResult::Denied),
+///     Ok(PermissionResult::Allowed),
+/// ]);
+///
+/// let viewable: Vec<_> = results.allowed_items(&docs).collect();
+/// assert_eq!(viewable, vec![&"doc-1", &"doc-3"]);
+/// assert!(results.first_error().is_none());
+/// ```
+#[derive(Debug)]
+pub struct BulkCheckResults(Vec<CheckResult>);
+
+impl BulkCheckResults {
+    /// Returns the indices of the items whose result is `Allowed`.
+    ///
+    /// `Conditional` results and per-item errors are excluded.
+    pub fn allowed_only(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|(_, r)| matches!(r, Ok(PermissionResult::Allowed)))
+            .map(|(i, _)| i)
+    }
+
+    /// Returns the entries of `items` whose result is `Allowed`.
+    ///
+    /// `items` is the list the check was built from (or anything parallel to
+    /// it, such as the resource IDs); entries beyond the results are ignored.
+    pub fn allowed_items<'s, 'i: 's, T>(
+        &'s self,
+        items: &'i [T],
+    ) -> impl Iterator<Item = &'i T> + 's {
+        self.allowed_only().filter_map(move |i| items.get(i))
+    }
+
+    /// Returns the first per-item error, if any.
+    pub fn first_error(&self) -> Option<&Error> {
+        self.0.iter().find_map(|r| r.as_ref().err())
+    }
+
+    /// Returns the underlying results.
+    pub fn into_inner(self) -> Vec<CheckResult> {
+        self.0
+    }
+}
+
+impl From<Vec<CheckResult>> for BulkCheckResults {
+    fn from(results: Vec<CheckResult>) -> Self {
+        Self(results)
+    }
+}
+
+impl std::ops::Deref for BulkCheckResults {
+    type Target = [CheckResult];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl IntoIterator for BulkCheckResults {
+    type Item = CheckResult;
+    type IntoIter = std::vec::IntoIter<CheckResult>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a BulkCheckResults {
+    type Item = &'a CheckResult;
+    type IntoIter = std::slice::Iter<'a, CheckResult>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
 /// A recursive permission tree returned by ExpandPermissionTree.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PermissionTree {
@@ -186,7 +387,11 @@ pub struct PermissionTree {
 }
 
 /// A node in a permission tree.
+///
+/// `#[non_exhaustive]` so that new set operations can be added without a
+/// breaking change; give `match` a wildcard arm.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum PermissionTreeNode {
     /// A leaf node containing direct subjects.
     Leaf {
@@ -230,6 +435,94 @@ impl PermissionTree {
 }
 
 impl PermissionTreeNode {
+    /// Evaluates whether `subject` is in the set this node describes, using
+    /// only what the expanded tree shows.
+    ///
+    /// Returns `Some(true)` or `Some(false)` when the tree decides the
+    /// answer, and `None` when it cannot: a leaf holds a subject set (such as
+    /// `group:eng#member`) that the expansion did not flatten, or a wildcard
+    /// of the subject's type. Expanded trees also carry no caveat
+    /// information, so a caveated relationship is reported as a plain member.
+    ///
+    /// ```
+    /// use prescience::{ObjectReference, PermissionTreeNode, SubjectReference};
+    ///
+    /// let user = |id| SubjectReference::new(ObjectReference::new("user", id).unwrap(), None::<String>).unwrap();
+    /// let node = PermissionTreeNode::Leaf { subjects: vec![user("alice")] };
+    /// assert_eq!(node.contains_subject(&user("alice")), Some(true));
+    /// assert_eq!(node.contains_subject(&user("bob")), Some(false));
+    /// ```
+    pub fn contains_subject(&self, subject: &SubjectReference) -> Option<bool> {
+        match self {
+            PermissionTreeNode::Leaf { subjects } => {
+                if subjects.contains(subject) {
+                    return Some(true);
+                }
+                let undecided = subjects.iter().any(|s| {
+                    s.optional_relation().is_some()
+                        || (s.object().object_id() == "*"
+                            && s.object().object_type() == subject.object().object_type())
+                });
+                if undecided {
+                    None
+                } else {
+                    Some(false)
+                }
+            }
+            PermissionTreeNode::Union { children } => {
+                let mut result = Some(false);
+                for child in children {
+                    match child.contains_subject(subject) {
+                        Some(true) => return Some(true),
+                        Some(false) => {}
+                        None => result = None,
+                    }
+                }
+                result
+            }
+            PermissionTreeNode::Intersection { children } => {
+                let mut result = Some(true);
+                for child in children {
+                    match child.contains_subject(subject) {
+                        Some(false) => return Some(false),
+                        Some(true) => {}
+                        None => result = None,
+                    }
+                }
+                result
+            }
+            PermissionTreeNode::Exclusion { base, excluded } => {
+                match (
+                    base.contains_subject(subject),
+                    excluded.contains_subject(subject),
+                ) {
+                    (Some(false), _) | (_, Some(true)) => Some(false),
+                    (Some(true), Some(false)) => Some(true),
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// Whether the tree shows that `subject` is outside this set even if
+    /// some of its relationships are caveated.
+    ///
+    /// Unlike [`contains_subject`](Self::contains_subject) returning
+    /// `Some(false)`, membership of an excluded set does not count: a
+    /// caveated exclusion may not apply, so it cannot rule a subject out.
+    pub(crate) fn rules_out(&self, subject: &SubjectReference) -> bool {
+        match self {
+            PermissionTreeNode::Leaf { .. } => self.contains_subject(subject) == Some(false),
+            PermissionTreeNode::Union { children } => {
+                children.iter().all(|child| child.rules_out(subject))
+            }
+            PermissionTreeNode::Intersection { children } => {
+                children.iter().any(|child| child.rules_out(subject))
+            }
+            PermissionTreeNode::Exclusion { base, .. } => base.rules_out(subject),
+        }
+    }
+
     fn from_proto_tree(
         tree_type: Option<crate::proto::permission_relationship_tree::TreeType>,
     ) -> Result<Self, Error> {
@@ -249,10 +542,13 @@ impl PermissionTreeNode {
                     .collect();
                 let children = children?;
 
-                match alg.operation {
-                    1 => Ok(PermissionTreeNode::Union { children }),
-                    2 => Ok(PermissionTreeNode::Intersection { children }),
-                    3 => {
+                use crate::proto::algebraic_subject_set::Operation;
+                match Operation::try_from(alg.operation) {
+                    Ok(Operation::Union) => Ok(PermissionTreeNode::Union { children }),
+                    Ok(Operation::Intersection) => {
+                        Ok(PermissionTreeNode::Intersection { children })
+                    }
+                    Ok(Operation::Exclusion) => {
                         // Exclusion: exactly 2 children — base and excluded
                         if children.len() != 2 {
                             return Err(Error::Serialization(format!(
@@ -265,9 +561,9 @@ impl PermissionTreeNode {
                         let excluded = Box::new(iter.next().unwrap());
                         Ok(PermissionTreeNode::Exclusion { base, excluded })
                     }
-                    other => Err(Error::Serialization(format!(
+                    Ok(Operation::Unspecified) | Err(_) => Err(Error::Serialization(format!(
                         "unknown algebraic operation: {}",
-                        other
+                        alg.operation
                     ))),
                 }
             }
@@ -280,6 +576,147 @@ impl PermissionTreeNode {
 mod tests {
     use super::*;
 
+    fn subject(s: &str) -> SubjectReference {
+        SubjectReference::parse(s).unwrap()
+    }
+
+    fn leaf(subjects: &[&str]) -> PermissionTreeNode {
+        PermissionTreeNode::Leaf {
+            subjects: subjects.iter().map(|s| subject(s)).collect(),
+        }
+    }
+
+    #[test]
+    fn contains_subject_evaluates_set_operations() {
+        let alice = subject("user:alice");
+        let union = PermissionTreeNode::Union {
+            children: vec![leaf(&["user:bob"]), leaf(&["user:alice"])],
+        };
+        assert_eq!(union.contains_subject(&alice), Some(true));
+
+        let intersection = PermissionTreeNode::Intersection {
+            children: vec![leaf(&["user:alice"]), leaf(&["user:bob"])],
+        };
+        assert_eq!(intersection.contains_subject(&alice), Some(false));
+
+        let exclusion = PermissionTreeNode::Exclusion {
+            base: Box::new(leaf(&["user:alice", "user:bob"])),
+            excluded: Box::new(leaf(&["user:bob"])),
+        };
+        assert_eq!(exclusion.contains_subject(&alice), Some(true));
+        assert_eq!(
+            exclusion.contains_subject(&subject("user:bob")),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn contains_subject_undecided_for_subject_sets_and_wildcards() {
+        let alice = subject("user:alice");
+        assert_eq!(leaf(&["group:eng#member"]).contains_subject(&alice), None);
+        assert_eq!(leaf(&["user:*"]).contains_subject(&alice), None);
+        assert_eq!(
+            leaf(&["user:*"]).contains_subject(&subject("team:x")),
+            Some(false)
+        );
+
+        // A decided member wins a union even next to an undecided branch.
+        let union = PermissionTreeNode::Union {
+            children: vec![leaf(&["group:eng#member"]), leaf(&["user:alice"])],
+        };
+        assert_eq!(union.contains_subject(&alice), Some(true));
+    }
+
+    #[test]
+    fn rules_out_ignores_exclusions() {
+        let alice = subject("user:alice");
+        assert!(leaf(&["user:bob"]).rules_out(&alice));
+        assert!(!leaf(&["user:alice"]).rules_out(&alice));
+        assert!(!leaf(&["group:eng#member"]).rules_out(&alice));
+
+        // alice's exclusion may be caveated, so it does not rule her out.
+        let exclusion = PermissionTreeNode::Exclusion {
+            base: Box::new(leaf(&["user:alice"])),
+            excluded: Box::new(leaf(&["user:alice"])),
+        };
+        assert_eq!(exclusion.contains_subject(&alice), Some(false));
+        assert!(!exclusion.rules_out(&alice));
+
+        let intersection = PermissionTreeNode::Intersection {
+            children: vec![leaf(&["user:alice"]), leaf(&["user:bob"])],
+        };
+        assert!(intersection.rules_out(&alice));
+        let union = PermissionTreeNode::Union {
+            children: vec![leaf(&["user:bob"]), exclusion],
+        };
+        assert!(!union.rules_out(&alice));
+    }
+
+    fn lookup_subject(ty: &str, id: &str, relation: Option<&str>) -> LookupSubjectResult {
+        LookupSubjectResult {
+            subject_type: ty.into(),
+            subject_id: id.into(),
+            subject_relation: relation.map(Into::into),
+            excluded_subject_ids: vec![],
+            permission: PermissionResult::Allowed,
+            looked_up_at: ZedToken::new("t").unwrap(),
+        }
+    }
+
+    #[test]
+    fn lookup_subject_reference() {
+        let set = lookup_subject("group", "eng", Some("member"))
+            .subject_reference()
+            .unwrap();
+        assert_eq!(set.to_string(), "group:eng#member");
+
+        let plain = lookup_subject("user", "alice", None)
+            .subject_reference()
+            .unwrap();
+        assert_eq!(plain.to_string(), "user:alice");
+
+        assert!(lookup_subject("user", "", None)
+            .subject_reference()
+            .is_err());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn lookup_subject_as_subject_reference() {
+        let set = lookup_subject("group", "eng", Some("member"))
+            .as_subject_reference("group")
+            .unwrap();
+        assert_eq!(set.to_string(), "group:eng#member");
+    }
+
+    #[test]
+    fn lookup_resource_reference() {
+        let result = |id: &str| LookupResourceResult {
+            resource_type: "document".into(),
+            resource_id: id.into(),
+            permission: PermissionResult::Allowed,
+            looked_up_at: ZedToken::new("t").unwrap(),
+        };
+        let reference = result("doc-1").resource_reference().unwrap();
+        assert_eq!(reference.to_string(), "document:doc-1");
+        assert!(result("").resource_reference().is_err());
+    }
+
+    #[test]
+    fn to_bool_applies_conditional_policy() {
+        use ConditionalPolicy::*;
+        let conditional = PermissionResult::Conditional {
+            missing_fields: vec![],
+        };
+        for policy in [TreatAsAllowed, TreatAsDenied] {
+            assert!(PermissionResult::Allowed.to_bool(policy));
+            assert!(!PermissionResult::Denied.to_bool(policy));
+            assert!(!PermissionResult::Unknown(9).to_bool(policy));
+        }
+        assert!(conditional.to_bool(TreatAsAllowed));
+        assert!(!conditional.to_bool(TreatAsDenied));
+    }
+
     #[test]
     fn permission_result_allowed() {
         let r = PermissionResult::Allowed;
@@ -332,4 +769,38 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn unrecognized_permissionship_is_unknown() {
+        let r = PermissionResult::from_check_response(7, None).unwrap();
+        assert_eq!(r, PermissionResult::Unknown(7));
+        assert!(matches!(r.is_allowed(), Err(Error::Serialization(_))));
+        assert!(!r.is_denied());
+
+        let r = PermissionResult::from_lookup_permissionship(0, None).unwrap();
+        assert_eq!(r, PermissionResult::Unknown(0));
+    }
+
+    #[test]
+    fn bulk_check_results_helpers() {
+        let results = BulkCheckResults::from(vec![
+            Ok(PermissionResult::Denied),
+            Ok(PermissionResult::Allowed),
+            Err(Error::InvalidArgument("bad item".into())),
+            Ok(PermissionResult::Conditional {
+                missing_fields: vec!["ip".into()],
+            }),
+            Ok(PermissionResult::Allowed),
+        ]);
+        assert_eq!(results.allowed_only().collect::<Vec<_>>(), vec![1, 4]);
+        let items = ["a", "b", "c", "d", "e"];
+        assert_eq!(
+            results.allowed_items(&items).collect::<Vec<_>>(),
+            vec![&"b", &"e"]
+        );
+        assert!(matches!(
+            results.first_error(),
+            Some(Error::InvalidArgument(m)) if m == "bad item"
+        ));
+    }
 }
@@ -1,6 +1,9 @@
 //! Object and subject references.
 
+use std::fmt;
+
 use crate::error::Error;
+use crate::limits;
 
 /// A reference to a specific object in the SpiceDB system.
 ///
@@ -22,10 +25,27 @@ pub struct ObjectReference {
     object_id: String,
 }
 
+/// Returns `true` if `c` may appear in a SpiceDB object ID.
+///
+/// Mirrors SpiceDB's object ID grammar: `[a-zA-Z0-9/_|\-=+]`.
+fn is_object_id_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '/' | '_' | '|' | '-' | '=' | '+')
+}
+
 impl ObjectReference {
     /// Creates a new `ObjectReference` with the given type and ID.
     ///
-    /// Returns `Err` if either `object_type` or `object_id` is empty.
+    /// Returns `Err` if either `object_type` or `object_id` is empty, or if
+    /// `object_id` does not match SpiceDB's object ID grammar: at most
+    /// [`MAX_OBJECT_ID_LEN`](crate::limits::MAX_OBJECT_ID_LEN) characters from `a-z A-Z 0-9 / _ | - = +`,
+    /// or the wildcard `*` on its own.
+    ///
+    /// ```
+    /// use prescience::ObjectReference;
+    ///
+    /// assert!(ObjectReference::new("document", "team/doc-1").is_ok());
+    /// assert!(ObjectReference::new("document", "has space").is_err());
+    /// ```
     pub fn new(
         object_type: impl Into<String>,
         object_id: impl Into<String>,
@@ -41,6 +61,21 @@ impl ObjectReference {
         if object_id.is_empty() {
             return Err(Error::InvalidArgument("object_id must not be empty".into()));
         }
+        if object_id != "*" {
+            if object_id.len() > limits::MAX_OBJECT_ID_LEN {
+                return Err(Error::InvalidArgument(format!(
+                    "object_id is {} bytes; the maximum is {}",
+                    object_id.len(),
+                    limits::MAX_OBJECT_ID_LEN
+                )));
+            }
+            if let Some(c) = object_id.chars().find(|c| !is_object_id_char(*c)) {
+                return Err(Error::InvalidArgument(format!(
+                    "object_id {:?} contains disallowed character {:?}",
+                    object_id, c
+                )));
+            }
+        }
 
         Ok(Self {
             object_type,
@@ -48,6 +83,40 @@ impl ObjectReference {
         })
     }
 
+    /// Creates a new `ObjectReference` without validating the type or ID.
+    ///
+    /// An escape hatch for IDs that a newer SpiceDB accepts but this
+    /// library's validation does not. Invalid values are rejected by the
+    /// server instead.
+    pub fn new_unchecked(object_type: impl Into<String>, object_id: impl Into<String>) -> Self {
+        Self {
+            object_type: object_type.into(),
+            object_id: object_id.into(),
+        }
+    }
+
+    /// Parses a reference from its `type:id` string form, as produced by
+    /// [`Display`](fmt::Display).
+    ///
+    /// The ID is validated as in [`new`](Self::new).
+    ///
+    /// ```
+    /// use prescience::ObjectReference;
+    ///
+    /// let obj = ObjectReference::parse("document:doc-1").unwrap();
+    /// assert_eq!(obj.object_id(), "doc-1");
+    /// assert!(ObjectReference::parse("document").is_err());
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let (object_type, object_id) = s.split_once(':').ok_or_else(|| {
+            Error::InvalidArgument(format!(
+                "object reference {:?} must have the form `type:id`",
+                s
+            ))
+        })?;
+        Self::new(object_type, object_id)
+    }
+
     /// Returns the object type.
     pub fn object_type(&self) -> &str {
         &self.object_type
@@ -57,6 +126,25 @@ impl ObjectReference {
     pub fn object_id(&self) -> &str {
         &self.object_id
     }
+
+    /// Consumes the reference, returning its owned `(object_type, object_id)`.
+    ///
+    /// ```
+    /// use prescience::ObjectReference;
+    ///
+    /// let (object_type, object_id) = ObjectReference::new("document", "doc-1").unwrap().into_parts();
+    /// assert_eq!((object_type.as_str(), object_id.as_str()), ("document", "doc-1"));
+    /// ```
+    pub fn into_parts(self) -> (String, String) {
+        (self.object_type, self.object_id)
+    }
+}
+
+/// Formats as `type:id`, e.g. `document:doc-123`.
+impl fmt::Display for ObjectReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.object_type, self.object_id)
+    }
 }
 
 impl From<&ObjectReference> for crate::proto::ObjectReference {
@@ -72,7 +160,17 @@ impl TryFrom<crate::proto::ObjectReference> for ObjectReference {
     type Error = Error;
 
     fn try_from(proto: crate::proto::ObjectReference) -> Result<Self, Error> {
-        ObjectReference::new(proto.object_type, proto.object_id)
+        // IDs from the server were already accepted by it; only reject
+        // structurally missing fields rather than re-applying the grammar.
+        if proto.object_type.is_empty() || proto.object_id.is_empty() {
+            return Err(Error::Serialization(
+                "object reference missing type or id".into(),
+            ));
+        }
+        Ok(ObjectReference::new_unchecked(
+            proto.object_type,
+            proto.object_id,
+        ))
     }
 }
 
@@ -127,6 +225,52 @@ impl SubjectReference {
         })
     }
 
+    /// Creates a subject set: every subject of `relation` on
+    /// `object_type:object_id`, such as `group:eng#member`.
+    ///
+    /// Use this to check or write a permission for a whole group rather than
+    /// for the group object itself. Returns `Err` if `relation` is empty or
+    /// the object is invalid.
+    ///
+    /// ```
+    /// use prescience::SubjectReference;
+    ///
+    /// let members = SubjectReference::set("group", "eng", "member").unwrap();
+    /// assert_eq!(members.to_string(), "group:eng#member");
+    /// ```
+    pub fn set(
+        object_type: impl Into<String>,
+        object_id: impl Into<String>,
+        relation: impl Into<String>,
+    ) -> Result<Self, Error> {
+        let relation = relation.into();
+        if relation.is_empty() {
+            return Err(Error::InvalidArgument(
+                "subject set relation must not be empty".into(),
+            ));
+        }
+        Self::new(
+            ObjectReference::new(object_type, object_id)?,
+            Some(relation),
+        )
+    }
+
+    /// Parses a subject from its `type:id` or `type:id#relation` string form,
+    /// as produced by [`Display`](fmt::Display).
+    ///
+    /// ```
+    /// use prescience::SubjectReference;
+    ///
+    /// let subject = SubjectReference::parse("group:eng#member").unwrap();
+    /// assert_eq!(subject.optional_relation(), Some("member"));
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        match s.split_once('#') {
+            Some((object, relation)) => Self::new(ObjectReference::parse(object)?, Some(relation)),
+            None => Self::new(ObjectReference::parse(s)?, None::<String>),
+        }
+    }
+
     /// Returns the subject's object reference.
     pub fn object(&self) -> &ObjectReference {
         &self.object
@@ -136,6 +280,22 @@ impl SubjectReference {
     pub fn optional_relation(&self) -> Option<&str> {
         self.optional_relation.as_deref()
     }
+
+    /// Consumes the subject, returning its owned object and relation.
+    pub fn into_parts(self) -> (ObjectReference, Option<String>) {
+        (self.object, self.optional_relation)
+    }
+}
+
+/// Formats as `type:id` or `type:id#relation`, e.g. `group:eng#member`.
+impl fmt::Display for SubjectReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.object)?;
+        if let Some(relation) = &self.optional_relation {
+            write!(f, "#{}", relation)?;
+        }
+        Ok(())
+    }
 }
 
 impl From<&SubjectReference> for crate::proto::SubjectReference {
@@ -187,6 +347,39 @@ mod tests {
         assert!(matches!(err, Error::InvalidArgument(_)));
     }
 
+    #[test]
+    fn object_reference_id_grammar_accepted() {
+        for id in ["doc-123", "team/doc_1", "a|b", "k=v+w", "*"] {
+            assert!(ObjectReference::new("document", id).is_ok(), "{id}");
+        }
+        let max = "a".repeat(limits::MAX_OBJECT_ID_LEN);
+        assert!(ObjectReference::new("document", max).is_ok());
+    }
+
+    #[test]
+    fn object_reference_id_too_long() {
+        let long = "a".repeat(limits::MAX_OBJECT_ID_LEN + 1);
+        let err = ObjectReference::new("document", long).unwrap_err();
+        assert!(matches!(err, Error::InvalidArgument(ref m) if m.contains("maximum")));
+    }
+
+    #[test]
+    fn object_reference_id_disallowed_character() {
+        for (id, bad) in [("has space", ' '), ("a.b", '.'), ("x@y", '@'), ("a*", '*')] {
+            let err = ObjectReference::new("document", id).unwrap_err();
+            assert!(
+                matches!(err, Error::InvalidArgument(ref m) if m.contains(&format!("{:?}", bad))),
+                "{id}"
+            );
+        }
+    }
+
+    #[test]
+    fn object_reference_new_unchecked_skips_validation() {
+        let obj = ObjectReference::new_unchecked("document", "has space");
+        assert_eq!(obj.object_id(), "has space");
+    }
+
     #[test]
     fn object_reference_equality_and_hash() {
         use std::collections::HashSet;
@@ -223,6 +416,53 @@ mod tests {
         assert!(matches!(err, Error::InvalidArgument(_)));
     }
 
+    #[test]
+    fn subject_set() {
+        let sub = SubjectReference::set("group", "eng", "member").unwrap();
+        assert_eq!(sub, SubjectReference::parse("group:eng#member").unwrap());
+
+        let err = SubjectReference::set("group", "eng", "").unwrap_err();
+        assert!(matches!(err, Error::InvalidArgument(_)));
+    }
+
+    #[test]
+    fn display_formats() {
+        let obj = ObjectReference::new("group", "eng").unwrap();
+        assert_eq!(obj.to_string(), "group:eng");
+        let sub = SubjectReference::new(obj.clone(), Some("member")).unwrap();
+        assert_eq!(sub.to_string(), "group:eng#member");
+        let sub = SubjectReference::new(obj, None::<String>).unwrap();
+        assert_eq!(sub.to_string(), "group:eng");
+    }
+
+    #[test]
+    fn parse_round_trips_display() {
+        for s in ["user:alice", "group:eng#member", "user:*"] {
+            assert_eq!(SubjectReference::parse(s).unwrap().to_string(), s);
+        }
+        assert_eq!(
+            ObjectReference::parse("document:a:b")
+                .unwrap_err()
+                .to_string(),
+            ObjectReference::new("document", "a:b")
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(SubjectReference::parse("group:eng#").is_err());
+        assert!(ObjectReference::parse(":alice").is_err());
+    }
+
+    #[test]
+    fn into_parts_returns_owned_fields() {
+        let sub = SubjectReference::parse("group:eng#member").unwrap();
+        let (object, relation) = sub.into_parts();
+        assert_eq!(relation.as_deref(), Some("member"));
+        assert_eq!(
+            object.into_parts(),
+            ("group".to_string(), "eng".to_string())
+        );
+    }
+
     #[test]
     fn proto_roundtrip_object_reference() {
         let orig = ObjectReference::new("document", "doc-123").unwrap();
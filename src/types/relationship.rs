@@ -1,8 +1,10 @@
 //! Relationship, RelationshipUpdate, Caveat, and Precondition types.
 
 use std::collections::HashMap;
+use std::fmt;
 
 use crate::error::Error;
+use crate::types::context::struct_to_context;
 use crate::types::{ContextValue, ObjectReference, SubjectReference};
 
 /// A caveat attached to a relationship, with optional context for evaluation.
@@ -22,6 +24,45 @@ impl Caveat {
             context,
         }
     }
+
+    /// Returns the context SpiceDB evaluates this caveat with when a check
+    /// supplies `request_context`.
+    ///
+    /// SpiceDB merges the two by top-level key, and the context stored on the
+    /// relationship takes precedence: a request cannot override a value that
+    /// was written with the relationship. Keys only in `request_context` fill
+    /// in the parameters the relationship left open. Nested
+    /// [`ContextValue::Struct`] values are replaced whole, not merged.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use prescience::{Caveat, ContextValue};
+    ///
+    /// let caveat = Caveat::new(
+    ///     "ip_allowlist",
+    ///     HashMap::from([("cidr".to_string(), ContextValue::String("10.0.0.0/8".into()))]),
+    /// );
+    /// let request = HashMap::from([
+    ///     ("cidr".to_string(), ContextValue::String("0.0.0.0/0".into())),
+    ///     ("user_ip".to_string(), ContextValue::String("10.1.2.3".into())),
+    /// ]);
+    ///
+    /// let merged = caveat.merge_context(&request);
+    /// assert_eq!(merged["cidr"], ContextValue::String("10.0.0.0/8".into()));
+    /// assert_eq!(merged["user_ip"], ContextValue::String("10.1.2.3".into()));
+    /// ```
+    pub fn merge_context(
+        &self,
+        request_context: &HashMap<String, ContextValue>,
+    ) -> HashMap<String, ContextValue> {
+        let mut merged = request_context.clone();
+        merged.extend(
+            self.context
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone())),
+        );
+        merged
+    }
 }
 
 /// A relationship between a resource and a subject via a relation.
@@ -57,6 +98,151 @@ impl Relationship {
         self.optional_caveat = Some(caveat);
         self
     }
+
+    /// Returns `true` if both relationships grant the same tuple: equal
+    /// resource, relation, subject, and caveat name, regardless of caveat
+    /// context.
+    ///
+    /// `==` still compares the context too; use this to dedup grants whose
+    /// context differs.
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use prescience::{Caveat, ContextValue, Relationship};
+    ///
+    /// let base = Relationship::parse("document:doc-1#viewer@user:alice").unwrap();
+    /// let mut ctx = HashMap::new();
+    /// ctx.insert("cidr".to_string(), ContextValue::String("10.0.0.0/8".into()));
+    /// let a = base.clone().with_caveat(Caveat::new("ip_allowlist", ctx));
+    /// let b = base.with_caveat(Caveat::new("ip_allowlist", HashMap::new()));
+    /// assert!(a.same_tuple(&b));
+    /// assert_ne!(a, b);
+    /// ```
+    pub fn same_tuple(&self, other: &Relationship) -> bool {
+        self.resource == other.resource
+            && self.relation == other.relation
+            && self.subject == other.subject
+            && self.optional_caveat.as_ref().map(|c| &c.name)
+                == other.optional_caveat.as_ref().map(|c| &c.name)
+    }
+
+    /// Formats the relationship the way the `zed` CLI does:
+    /// `resource_type:id#relation@subject_type:id[#subject_relation]`.
+    ///
+    /// A caveat is appended in brackets: `[caveat_name]` without context, or
+    /// `[caveat_name:{...}]` with the context as compact JSON. Context keys
+    /// are sorted and whole numbers render without a fraction, so the output
+    /// is stable across runs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use prescience::{Caveat, ContextValue, ObjectReference, Relationship, SubjectReference};
+    ///
+    /// let rel = Relationship::new(
+    ///     ObjectReference::new("document", "doc-1").unwrap(),
+    ///     "viewer",
+    ///     SubjectReference::new(ObjectReference::new("user", "alice").unwrap(), None::<String>)
+    ///         .unwrap(),
+    /// );
+    /// assert_eq!(rel.to_zed_string(), "document:doc-1#viewer@user:alice");
+    ///
+    /// let mut ctx = HashMap::new();
+    /// ctx.insert("day".to_string(), ContextValue::String("tuesday".into()));
+    /// let rel = rel.with_caveat(Caveat::new("only_on", ctx));
+    /// assert_eq!(
+    ///     rel.to_zed_string(),
+    ///     r#"document:doc-1#viewer@user:alice[only_on:{"day":"tuesday"}]"#
+    /// );
+    /// ```
+    ///
+    /// [`parse`](Self::parse) accepts this format back.
+    pub fn to_zed_string(&self) -> String {
+        let mut s = format!("{}#{}@{}", self.resource, self.relation, self.subject);
+        if let Some(caveat) = &self.optional_caveat {
+            if caveat.context.is_empty() {
+                s.push_str(&format!("[{}]", caveat.name));
+            } else {
+                s.push_str(&format!(
+                    "[{}:{}]",
+                    caveat.name,
+                    crate::types::context::context_to_json(&caveat.context)
+                ));
+            }
+        }
+        s
+    }
+
+    /// Parses a relationship from the `zed` CLI string form produced by
+    /// [`to_zed_string`](Self::to_zed_string), e.g.
+    /// `document:doc-1#viewer@group:eng#member[on_network:{"cidr":"10.0.0.0/8"}]`.
+    ///
+    /// Object IDs are validated as in [`ObjectReference::new`]. The caveat
+    /// context, if present, must be a JSON object. Errors are
+    /// [`Error::InvalidArgument`] naming the part that failed to parse.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use prescience::Relationship;
+    ///
+    /// let rel = Relationship::parse("document:doc-1#viewer@group:eng#member").unwrap();
+    /// assert_eq!(rel.relation, "viewer");
+    /// assert_eq!(rel.subject.optional_relation(), Some("member"));
+    /// assert!(Relationship::parse("document:doc-1@user:alice").is_err());
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, Error> {
+        let invalid =
+            |what: &str| Error::InvalidArgument(format!("relationship {:?}: {}", s, what));
+
+        let (body, optional_caveat) = match s.split_once('[') {
+            Some((body, caveat)) => {
+                let caveat = caveat
+                    .strip_suffix(']')
+                    .ok_or_else(|| invalid("caveat must end with `]`"))?;
+                (body, Some(parse_caveat(caveat).map_err(|e| invalid(&e))?))
+            }
+            None => (s, None),
+        };
+
+        let (resource, subject) = body
+            .split_once('@')
+            .ok_or_else(|| invalid("missing `@` between resource and subject"))?;
+        let (resource, relation) = resource
+            .split_once('#')
+            .ok_or_else(|| invalid("missing `#relation` after resource"))?;
+        if relation.is_empty() {
+            return Err(invalid("relation must not be empty"));
+        }
+        let resource =
+            ObjectReference::parse(resource).map_err(|e| invalid(&format!("resource: {}", e)))?;
+        let subject =
+            SubjectReference::parse(subject).map_err(|e| invalid(&format!("subject: {}", e)))?;
+
+        Ok(Self {
+            resource,
+            relation: relation.to_string(),
+            subject,
+            optional_caveat,
+        })
+    }
+}
+
+/// Parses the inside of a `[name]` or `[name:{json}]` caveat suffix.
+fn parse_caveat(s: &str) -> Result<Caveat, String> {
+    let (name, context) = match s.split_once(':') {
+        Some((name, json)) => (
+            name,
+            crate::types::context::parse_context_json(json)
+                .map_err(|e| format!("caveat context: {}", e))?,
+        ),
+        None => (s, HashMap::new()),
+    };
+    if name.is_empty() {
+        return Err("caveat name must not be empty".into());
+    }
+    Ok(Caveat::new(name, context))
 }
 
 impl TryFrom<crate::proto::Relationship> for Relationship {
@@ -71,13 +257,16 @@ impl TryFrom<crate::proto::Relationship> for Relationship {
             .subject
             .ok_or_else(|| Error::Serialization("missing subject".into()))?
             .try_into()?;
-        let optional_caveat = proto.optional_caveat.map(|c| Caveat {
-            name: c.caveat_name,
-            context: c
-                .context
-                .map(|s| s.fields.into_iter().map(|(k, v)| (k, v.into())).collect())
-                .unwrap_or_default(),
-        });
+        // A missing context and an empty one both read back as an empty map,
+        // mirroring the write side, which sends an empty map as no context.
+        // A caveat without a name is treated as no caveat at all.
+        let optional_caveat = proto
+            .optional_caveat
+            .filter(|c| !c.caveat_name.is_empty())
+            .map(|c| Caveat {
+                name: c.caveat_name,
+                context: c.context.map(struct_to_context).unwrap_or_default(),
+            });
         Ok(Relationship {
             resource,
             relation: proto.relation,
@@ -109,7 +298,10 @@ impl From<&Relationship> for crate::proto::Relationship {
 }
 
 /// The operation to perform on a relationship.
+///
+/// Marked `#[non_exhaustive]` in case SpiceDB adds operations.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum Operation {
     /// Create the relationship; error if it already exists.
     Create,
@@ -152,6 +344,80 @@ impl RelationshipUpdate {
             relationship,
         }
     }
+
+    /// Creates a CREATE update for each of the given relationships.
+    ///
+    /// ```
+    /// use prescience::{Relationship, RelationshipUpdate};
+    ///
+    /// let updates = RelationshipUpdate::create_all([
+    ///     Relationship::parse("document:1#viewer@user:alice").unwrap(),
+    ///     Relationship::parse("document:1#viewer@user:bob").unwrap(),
+    /// ]);
+    /// assert_eq!(updates.len(), 2);
+    /// ```
+    pub fn create_all(relationships: impl IntoIterator<Item = Relationship>) -> Vec<Self> {
+        relationships.into_iter().map(Self::create).collect()
+    }
+
+    /// Creates a TOUCH (upsert) update for each of the given relationships.
+    pub fn touch_all(relationships: impl IntoIterator<Item = Relationship>) -> Vec<Self> {
+        relationships.into_iter().map(Self::touch).collect()
+    }
+
+    /// Computes the updates that turn `actual` into `desired`.
+    ///
+    /// Relationships are compared with [`Relationship::same_tuple`], so
+    /// caveat context is ignored. A relationship only in `desired` becomes a
+    /// CREATE and one only in `actual` a DELETE. One in both whose caveat
+    /// name differs becomes a TOUCH of the desired relationship, since
+    /// SpiceDB stores one relationship per resource, relation and subject.
+    /// Creates and touches come first, in `desired` order, then deletes, in
+    /// `actual` order; duplicates are emitted once.
+    ///
+    /// ```
+    /// use prescience::{Operation, Relationship, RelationshipUpdate};
+    ///
+    /// let alice = Relationship::parse("document:1#viewer@user:alice").unwrap();
+    /// let bob = Relationship::parse("document:1#viewer@user:bob").unwrap();
+    /// let carol = Relationship::parse("document:1#viewer@user:carol").unwrap();
+    ///
+    /// let updates = RelationshipUpdate::diff(&[alice.clone(), bob], &[alice, carol]);
+    /// assert_eq!(updates.len(), 2);
+    /// assert_eq!(updates[0].operation, Operation::Create);
+    /// assert_eq!(updates[1].operation, Operation::Delete);
+    /// ```
+    pub fn diff(desired: &[Relationship], actual: &[Relationship]) -> Vec<Self> {
+        type Key<'r> = (&'r ObjectReference, &'r str, &'r SubjectReference);
+        fn key(r: &Relationship) -> Key<'_> {
+            (&r.resource, &r.relation, &r.subject)
+        }
+
+        let actual_by_key: HashMap<Key<'_>, &Relationship> =
+            actual.iter().map(|r| (key(r), r)).collect();
+        let mut desired_keys = std::collections::HashSet::new();
+        let mut updates = Vec::new();
+        for rel in desired {
+            if !desired_keys.insert(key(rel)) {
+                continue;
+            }
+            match actual_by_key.get(&key(rel)) {
+                None => updates.push(Self::create(rel.clone())),
+                Some(existing) if !existing.same_tuple(rel) => {
+                    updates.push(Self::touch(rel.clone()))
+                }
+                Some(_) => {}
+            }
+        }
+
+        let mut deleted = std::collections::HashSet::new();
+        for rel in actual {
+            if !desired_keys.contains(&key(rel)) && deleted.insert(key(rel)) {
+                updates.push(Self::delete(rel.clone()));
+            }
+        }
+        updates
+    }
 }
 
 impl From<&RelationshipUpdate> for crate::proto::RelationshipUpdate {
@@ -194,7 +460,10 @@ impl TryFrom<crate::proto::RelationshipUpdate> for RelationshipUpdate {
 }
 
 /// The operation for a precondition.
+///
+/// Marked `#[non_exhaustive]` in case SpiceDB adds precondition kinds.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum PreconditionOp {
     /// The filter must match at least one existing relationship.
     MustExist,
@@ -203,6 +472,19 @@ pub enum PreconditionOp {
 }
 
 /// A precondition on a write or delete operation.
+///
+/// # Examples
+///
+/// ```
+/// use prescience::{Precondition, RelationshipFilter};
+///
+/// // Only proceed if the document has no owner yet
+/// let p = Precondition::must_not_exist(
+///     RelationshipFilter::new("document")
+///         .resource_id("doc-123")
+///         .relation("owner"),
+/// );
+/// ```
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Precondition {
     /// The precondition operation.
@@ -245,10 +527,154 @@ impl From<&Precondition> for crate::proto::Precondition {
     }
 }
 
+/// A reason a write would fail, reported by a dry run of
+/// [`Client::write_relationships`](crate::Client::write_relationships).
+///
+/// `update` and `precondition` are indexes into the write's updates and
+/// preconditions. Type names are as passed, without any type prefix.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WriteProblem {
+    /// The resource type is not defined in the schema.
+    UnknownType {
+        /// The index of the update.
+        update: usize,
+        /// The undefined type.
+        object_type: String,
+    },
+    /// The resource type has no relation with this name. Permissions
+    /// cannot be written, so naming one is reported here too.
+    UnknownRelation {
+        /// The index of the update.
+        update: usize,
+        /// The resource type.
+        object_type: String,
+        /// The missing relation.
+        relation: String,
+    },
+    /// The relation does not allow the subject: its type, subject relation,
+    /// wildcard, or caveat does not match any allowed subject type.
+    SubjectNotAllowed {
+        /// The index of the update.
+        update: usize,
+        /// The relation written.
+        relation: String,
+        /// The subject as the schema would spell it, e.g. `user`,
+        /// `group#member`, `user:*`, or `user with ip_allowlist`.
+        subject_type: String,
+    },
+    /// A create names a relationship that already exists.
+    AlreadyExists {
+        /// The index of the update.
+        update: usize,
+    },
+    /// A precondition is not met.
+    PreconditionFailed {
+        /// The index of the precondition.
+        precondition: usize,
+    },
+}
+
+impl fmt::Display for WriteProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WriteProblem::UnknownType {
+                update,
+                object_type,
+            } => write!(f, "update {}: type {} is not defined", update, object_type),
+            WriteProblem::UnknownRelation {
+                update,
+                object_type,
+                relation,
+            } => write!(
+                f,
+                "update {}: {} has no relation {}",
+                update, object_type, relation
+            ),
+            WriteProblem::SubjectNotAllowed {
+                update,
+                relation,
+                subject_type,
+            } => write!(
+                f,
+                "update {}: relation {} does not allow {}",
+                update, relation, subject_type
+            ),
+            WriteProblem::AlreadyExists { update } => {
+                write!(f, "update {}: relationship already exists", update)
+            }
+            WriteProblem::PreconditionFailed { precondition } => {
+                write!(f, "precondition {} is not met", precondition)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn rel(s: &str) -> Relationship {
+        Relationship::parse(s).unwrap()
+    }
+
+    #[test]
+    fn diff_adds_and_removes() {
+        let desired = [rel("doc:1#viewer@user:alice"), rel("doc:1#viewer@user:bob")];
+        let actual = [rel("doc:1#viewer@user:bob"), rel("doc:1#viewer@user:carol")];
+        assert_eq!(
+            RelationshipUpdate::diff(&desired, &actual),
+            vec![
+                RelationshipUpdate::create(rel("doc:1#viewer@user:alice")),
+                RelationshipUpdate::delete(rel("doc:1#viewer@user:carol")),
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_of_equal_sets_is_empty() {
+        let rels = [rel("doc:1#viewer@user:alice"), rel("doc:2#editor@user:bob")];
+        let mut reordered = rels.clone();
+        reordered.reverse();
+        assert!(RelationshipUpdate::diff(&rels, &reordered).is_empty());
+        assert!(RelationshipUpdate::diff(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn diff_ignores_context_but_not_caveat_name() {
+        let base = rel("doc:1#viewer@user:alice");
+        let context = HashMap::from([("cidr".to_string(), ContextValue::String("10/8".into()))]);
+        let with_context = base
+            .clone()
+            .with_caveat(Caveat::new("ip_allowlist", context));
+        let without_context = base
+            .clone()
+            .with_caveat(Caveat::new("ip_allowlist", HashMap::new()));
+        assert!(RelationshipUpdate::diff(
+            std::slice::from_ref(&with_context),
+            std::slice::from_ref(&without_context)
+        )
+        .is_empty());
+
+        assert_eq!(
+            RelationshipUpdate::diff(std::slice::from_ref(&with_context), &[base]),
+            vec![RelationshipUpdate::touch(with_context)]
+        );
+    }
+
+    #[test]
+    fn diff_emits_duplicates_once() {
+        let alice = rel("doc:1#viewer@user:alice");
+        let bob = rel("doc:1#viewer@user:bob");
+        assert_eq!(
+            RelationshipUpdate::diff(&[alice.clone(), alice.clone()], &[bob.clone(), bob.clone()]),
+            vec![
+                RelationshipUpdate::create(alice),
+                RelationshipUpdate::delete(bob)
+            ]
+        );
+    }
+
     #[test]
     fn relationship_create_update() {
         let rel = Relationship::new(
@@ -264,6 +690,24 @@ mod tests {
         assert_eq!(update.operation, Operation::Create);
     }
 
+    #[test]
+    fn merge_context_prefers_relationship_values() {
+        let caveat = Caveat::new(
+            "limit",
+            HashMap::from([("max".to_string(), ContextValue::Number(5.0))]),
+        );
+        let request = HashMap::from([
+            ("max".to_string(), ContextValue::Number(500.0)),
+            ("used".to_string(), ContextValue::Number(3.0)),
+        ]);
+
+        let merged = caveat.merge_context(&request);
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged["max"], ContextValue::Number(5.0));
+        assert_eq!(merged["used"], ContextValue::Number(3.0));
+        assert_eq!(caveat.merge_context(&HashMap::new()), caveat.context);
+    }
+
     #[test]
     fn relationship_with_caveat() {
         let rel = Relationship::new(
@@ -280,6 +724,98 @@ mod tests {
         assert_eq!(rel.optional_caveat.unwrap().name, "ip_check");
     }
 
+    #[test]
+    fn parse_round_trips_zed_string() {
+        for s in [
+            "document:doc-1#viewer@user:alice",
+            "document:doc-1#viewer@group:eng#member",
+            "document:doc-1#viewer@user:*[only_on]",
+            r#"document:doc-1#viewer@user:alice[ip:{"cidr":"10.0.0.0/8","tags":["a]"]}]"#,
+        ] {
+            assert_eq!(Relationship::parse(s).unwrap().to_zed_string(), s);
+        }
+    }
+
+    #[test]
+    fn parse_reports_failing_part() {
+        let cases = [
+            ("document:doc-1#viewer", "missing `@`"),
+            ("document:doc-1@user:alice", "missing `#relation`"),
+            ("document:doc 1#viewer@user:alice", "resource:"),
+            ("document:doc-1#viewer@alice", "subject:"),
+            ("document:doc-1#viewer@user:alice[ip", "`]`"),
+            ("document:doc-1#viewer@user:alice[ip:{]", "caveat context"),
+        ];
+        for (input, expected) in cases {
+            let err = Relationship::parse(input).unwrap_err();
+            assert!(
+                matches!(err, Error::InvalidArgument(ref m) if m.contains(expected)),
+                "{input}: {err}"
+            );
+        }
+    }
+
+    fn caveated(context: HashMap<String, ContextValue>) -> Relationship {
+        Relationship::parse("document:doc-1#viewer@user:alice")
+            .unwrap()
+            .with_caveat(Caveat::new("ip_allowlist", context))
+    }
+
+    #[test]
+    fn caveat_context_survives_proto_round_trip() {
+        let mut context = HashMap::new();
+        context.insert(
+            "cidr".to_string(),
+            ContextValue::String("10.0.0.0/8".into()),
+        );
+        let rel = caveated(context);
+        let proto: crate::proto::Relationship = (&rel).into();
+        assert_eq!(Relationship::try_from(proto).unwrap(), rel);
+    }
+
+    #[test]
+    fn empty_caveat_context_is_symmetric() {
+        let rel = caveated(HashMap::new());
+        let proto: crate::proto::Relationship = (&rel).into();
+        assert!(proto.optional_caveat.as_ref().unwrap().context.is_none());
+        assert_eq!(Relationship::try_from(proto.clone()).unwrap(), rel);
+
+        // The server may also send an explicitly empty struct.
+        let mut with_empty_struct = proto;
+        with_empty_struct.optional_caveat.as_mut().unwrap().context =
+            Some(prost_types::Struct::default());
+        assert_eq!(Relationship::try_from(with_empty_struct).unwrap(), rel);
+    }
+
+    #[test]
+    fn unnamed_caveat_reads_as_none() {
+        let mut proto: crate::proto::Relationship = (&caveated(HashMap::new())).into();
+        proto.optional_caveat.as_mut().unwrap().caveat_name.clear();
+        let rel = Relationship::try_from(proto).unwrap();
+        assert!(rel.optional_caveat.is_none());
+    }
+
+    #[test]
+    fn same_tuple_compares_caveat_name_only() {
+        let mut context = HashMap::new();
+        context.insert(
+            "cidr".to_string(),
+            ContextValue::String("10.0.0.0/8".into()),
+        );
+        let with_context = caveated(context);
+        let without_context = caveated(HashMap::new());
+        assert!(with_context.same_tuple(&without_context));
+
+        let other_caveat = Relationship::parse("document:doc-1#viewer@user:alice[other]").unwrap();
+        assert!(!with_context.same_tuple(&other_caveat));
+
+        let uncaveated = Relationship::parse("document:doc-1#viewer@user:alice").unwrap();
+        assert!(!with_context.same_tuple(&uncaveated));
+        assert!(
+            !uncaveated.same_tuple(&Relationship::parse("document:doc-1#viewer@user:bob").unwrap())
+        );
+    }
+
     #[test]
     fn precondition_must_exist() {
         use crate::types::RelationshipFilter;
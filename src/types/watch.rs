@@ -1,20 +1,71 @@
 //! Watch event types (behind `watch` feature).
 
+use std::collections::HashMap;
+
 use crate::error::Error;
-use crate::types::{RelationshipUpdate, ZedToken};
+use crate::types::context::struct_to_context;
+use crate::types::{ContextValue, Operation, RelationshipUpdate, ZedToken};
 
 /// An event from the SpiceDB Watch stream.
 ///
 /// Contains relationship changes and a checkpoint token for resumption.
+///
+/// An event can also carry no updates and only advance the checkpoint: the
+/// first event of a watch started `with_initial_checkpoint(true)`, and the
+/// events SpiceDB sends during quiet periods when the watch is started
+/// `with_checkpoints(true)`. These are delivered like any other event; see
+/// [`is_checkpoint_only`](Self::is_checkpoint_only).
 #[derive(Debug, Clone, PartialEq)]
 pub struct WatchEvent {
     /// The relationship updates in this event.
     pub updates: Vec<RelationshipUpdate>,
     /// Checkpoint token for resuming the watch stream.
     pub checkpoint: ZedToken,
+    /// The transaction metadata the writer attached with `.metadata(..)` on
+    /// the write or delete that made these changes, if any.
+    pub metadata: Option<HashMap<String, ContextValue>>,
 }
 
 impl WatchEvent {
+    /// Returns `true` if this event carries no updates and only advances
+    /// the checkpoint. Persist its checkpoint all the same, so a resumed
+    /// watch starts from the latest revision seen.
+    pub fn is_checkpoint_only(&self) -> bool {
+        self.updates.is_empty()
+    }
+
+    /// Returns the updates that created a relationship.
+    pub fn creates(&self) -> impl Iterator<Item = &RelationshipUpdate> {
+        self.updates_of(Operation::Create)
+    }
+
+    /// Returns the updates that touched (upserted) a relationship.
+    pub fn touches(&self) -> impl Iterator<Item = &RelationshipUpdate> {
+        self.updates_of(Operation::Touch)
+    }
+
+    /// Returns the updates that deleted a relationship.
+    pub fn deletes(&self) -> impl Iterator<Item = &RelationshipUpdate> {
+        self.updates_of(Operation::Delete)
+    }
+
+    /// Returns the number of creates, touches, and deletes in this event.
+    pub fn counts(&self) -> (usize, usize, usize) {
+        self.updates
+            .iter()
+            .fold((0, 0, 0), |(c, t, d), update| match update.operation {
+                Operation::Create => (c + 1, t, d),
+                Operation::Touch => (c, t + 1, d),
+                Operation::Delete => (c, t, d + 1),
+            })
+    }
+
+    fn updates_of(&self, operation: Operation) -> impl Iterator<Item = &RelationshipUpdate> {
+        self.updates
+            .iter()
+            .filter(move |update| update.operation == operation)
+    }
+
     pub(crate) fn from_proto(proto: crate::proto::WatchResponse) -> Result<Self, Error> {
         let updates: Result<Vec<RelationshipUpdate>, Error> =
             proto.updates.into_iter().map(TryInto::try_into).collect();
@@ -25,6 +76,55 @@ impl WatchEvent {
         Ok(Self {
             updates: updates?,
             checkpoint,
+            metadata: proto
+                .optional_transaction_metadata
+                .filter(|m| !m.fields.is_empty())
+                .map(struct_to_context),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Relationship;
+
+    #[test]
+    fn updates_by_kind() {
+        let rel = |s: &str| Relationship::parse(s).unwrap();
+        let event = WatchEvent {
+            updates: vec![
+                RelationshipUpdate::create(rel("document:1#viewer@user:a")),
+                RelationshipUpdate::delete(rel("document:1#viewer@user:b")),
+                RelationshipUpdate::touch(rel("document:1#viewer@user:c")),
+                RelationshipUpdate::delete(rel("document:2#viewer@user:a")),
+            ],
+            checkpoint: ZedToken::new("t").unwrap(),
+            metadata: None,
+        };
+
+        assert_eq!(event.counts(), (1, 1, 2));
+        let deleted: Vec<String> = event
+            .deletes()
+            .map(|u| u.relationship.to_zed_string())
+            .collect();
+        assert_eq!(
+            deleted,
+            ["document:1#viewer@user:b", "document:2#viewer@user:a"]
+        );
+        assert_eq!(event.creates().count(), 1);
+        assert_eq!(event.touches().count(), 1);
+        assert!(!event.is_checkpoint_only());
+    }
+
+    #[test]
+    fn checkpoint_only_response_is_kept() {
+        let event = WatchEvent::from_proto(crate::proto::WatchResponse {
+            changes_through: Some(crate::proto::ZedToken { token: "t2".into() }),
+            ..Default::default()
         })
+        .unwrap();
+        assert!(event.is_checkpoint_only());
+        assert_eq!(event.checkpoint, ZedToken::new("t2").unwrap());
     }
 }
@@ -8,7 +8,7 @@ use std::sync::Arc;
 
 use prescience::{
     Client, Consistency, ObjectReference, PermissionResult, Relationship, RelationshipFilter,
-    RelationshipUpdate, SubjectReference,
+    RelationshipUpdate, SubjectFilter, SubjectReference,
 };
 use testcontainers::core::{IntoContainerPort, WaitFor};
 use testcontainers::runners::AsyncRunner;
@@ -151,10 +151,18 @@ async fn spicedb() -> Client {
 }
 
 const TEST_SCHEMA: &str = r#"
+caveat ip_allowlist(user_ip ipaddress, cidr string) {
+    user_ip.in_cidr(cidr)
+}
+
 definition user {}
 
+definition group {
+    relation member: user
+}
+
 definition document {
-    relation viewer: user
+    relation viewer: user | user with ip_allowlist | group#member
     relation editor: user
 
     permission view = viewer + editor
@@ -173,6 +181,65 @@ async fn write_and_read_schema() {
     assert!(!read_at.token().is_empty());
 }
 
+#[tokio::test]
+async fn ping() {
+    let c = spicedb().await;
+    c.ping().await.expect("ping failed");
+
+    let endpoint = c.endpoint().unwrap().to_string();
+    let bad = Client::new(&endpoint, "wrong-key").await.unwrap();
+    let err = bad.ping().await.unwrap_err();
+    assert_eq!(err.code(), Some(tonic::Code::Unauthenticated));
+    assert!(matches!(err, prescience::Error::Unauthenticated { .. }));
+}
+
+#[tokio::test]
+async fn connect_verified_fails_fast_on_bad_token() {
+    let c = spicedb().await;
+    let endpoint = c.endpoint().unwrap().to_string();
+
+    Client::connect_verified(&endpoint, SPICEDB_TOKEN)
+        .await
+        .expect("valid token rejected");
+    let err = Client::connect_verified(&endpoint, "wrong-key")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, prescience::Error::Unauthenticated { .. }));
+}
+
+#[tokio::test]
+async fn list_object_types() {
+    let c = spicedb().await;
+    let types = c
+        .list_object_types()
+        .await
+        .expect("list_object_types failed");
+    for expected in ["document", "group", "user"] {
+        assert!(types.iter().any(|t| t == expected), "missing {}", expected);
+    }
+    assert!(types.windows(2).all(|w| w[0] < w[1]));
+}
+
+#[tokio::test]
+async fn fallback_token_used_after_rejection() {
+    let c = spicedb().await;
+    let endpoint = c.endpoint().unwrap().to_string();
+
+    let rotated = Client::builder(&endpoint, "retired-key")
+        .fallback_tokens(vec![SPICEDB_TOKEN.to_string()])
+        .build()
+        .await
+        .unwrap();
+    rotated
+        .ping()
+        .await
+        .expect("fallback token should be accepted");
+    rotated
+        .read_schema()
+        .await
+        .expect("accepted token should stay active");
+}
+
 #[tokio::test]
 async fn write_schema_empty_rejected() {
     let c = spicedb().await;
@@ -189,6 +256,26 @@ async fn write_relationships_empty_rejected() {
     assert!(matches!(err, prescience::Error::InvalidArgument(_)));
 }
 
+#[tokio::test]
+async fn write_relationships_over_limit_rejected() {
+    let c = spicedb().await;
+    let updates: Vec<RelationshipUpdate> = (0..=prescience::limits::MAX_WRITE_UPDATES)
+        .map(|i| {
+            RelationshipUpdate::touch(Relationship::new(
+                ObjectReference::new("document", format!("limit-{i}")).unwrap(),
+                "viewer",
+                SubjectReference::new(
+                    ObjectReference::new("user", "limit").unwrap(),
+                    None::<String>,
+                )
+                .unwrap(),
+            ))
+        })
+        .collect();
+    let err = c.write_relationships(updates).await.unwrap_err();
+    assert!(matches!(err, prescience::Error::InvalidArgument(_)));
+}
+
 #[tokio::test]
 async fn write_and_check_permission() {
     let c = spicedb().await;
@@ -241,6 +328,77 @@ async fn write_and_check_permission() {
     assert_eq!(result, PermissionResult::Denied);
 }
 
+#[tokio::test]
+async fn default_check_consistency_applies_without_override() {
+    let c = spicedb().await;
+    let endpoint = c.endpoint().unwrap().to_string();
+    let consistent = Client::builder(&endpoint, SPICEDB_TOKEN)
+        .default_consistency_for(
+            prescience::client::RpcKind::Check,
+            Consistency::FullyConsistent,
+        )
+        .build()
+        .await
+        .unwrap();
+
+    let resource = ObjectReference::new("document", "default-consistency-1").unwrap();
+    let subject = SubjectReference::new(
+        ObjectReference::new("user", "alice").unwrap(),
+        None::<String>,
+    )
+    .unwrap();
+    consistent
+        .write_relationships(vec![RelationshipUpdate::create(Relationship::new(
+            resource.clone(),
+            "viewer",
+            subject.clone(),
+        ))])
+        .await
+        .expect("write_relationships failed");
+
+    // No per-request consistency: the fully consistent default sees the write.
+    let result = consistent
+        .check_permission(&resource, "view", &subject)
+        .await
+        .expect("check_permission failed");
+    assert_eq!(result, PermissionResult::Allowed);
+}
+
+#[tokio::test]
+async fn check_permission_for_subject_set() {
+    let c = spicedb().await;
+
+    let doc = ObjectReference::new("document", "subject-set-1").unwrap();
+    let members = SubjectReference::set("group", "eng-set", "member").unwrap();
+    let token = c
+        .write_relationships(vec![
+            RelationshipUpdate::create(Relationship::new(doc.clone(), "viewer", members.clone())),
+            RelationshipUpdate::create(
+                Relationship::parse("group:eng-set#member@user:gina").unwrap(),
+            ),
+        ])
+        .await
+        .unwrap();
+
+    let check = |subject: SubjectReference| {
+        let c = &c;
+        let doc = &doc;
+        let token = token.clone();
+        async move {
+            c.check_permission(doc, "view", &subject)
+                .consistency(Consistency::AtLeastAsFresh(token))
+                .await
+                .unwrap()
+        }
+    };
+
+    assert_eq!(check(members).await, PermissionResult::Allowed);
+    assert_eq!(
+        check(SubjectReference::parse("user:gina").unwrap()).await,
+        PermissionResult::Allowed
+    );
+}
+
 #[tokio::test]
 async fn read_relationships() {
     let c = spicedb().await;
@@ -285,210 +443,1125 @@ async fn read_relationships() {
 }
 
 #[tokio::test]
-async fn lookup_resources() {
-    let c = spicedb().await;
+async fn failed_precondition_identified() {
+    use prescience::Precondition;
 
-    let token = c
-        .write_relationships(vec![
-            RelationshipUpdate::create(Relationship::new(
-                ObjectReference::new("document", "lr-1").unwrap(),
-                "viewer",
-                SubjectReference::new(
-                    ObjectReference::new("user", "dave").unwrap(),
-                    None::<String>,
-                )
-                .unwrap(),
-            )),
-            RelationshipUpdate::create(Relationship::new(
-                ObjectReference::new("document", "lr-2").unwrap(),
-                "editor",
-                SubjectReference::new(
-                    ObjectReference::new("user", "dave").unwrap(),
-                    None::<String>,
-                )
-                .unwrap(),
-            )),
-        ])
-        .await
-        .unwrap();
+    let c = spicedb().await;
 
-    let subject = SubjectReference::new(
-        ObjectReference::new("user", "dave").unwrap(),
-        None::<String>,
-    )
+    c.write_relationships(vec![RelationshipUpdate::touch(
+        Relationship::parse("document:precond-1#editor@user:olga").unwrap(),
+    )])
+    .await
     .unwrap();
 
-    let mut stream = c
-        .lookup_resources("document", "view", &subject)
-        .consistency(Consistency::AtLeastAsFresh(token))
-        .send()
+    let err = c
+        .write_relationships(vec![RelationshipUpdate::touch(
+            Relationship::parse("document:precond-1#viewer@user:pat").unwrap(),
+        )])
+        .preconditions(vec![
+            Precondition::must_exist(
+                RelationshipFilter::new("document")
+                    .resource_id("precond-1")
+                    .relation("editor"),
+            ),
+            Precondition::must_not_exist(
+                RelationshipFilter::new("document")
+                    .resource_id("precond-1")
+                    .relation("editor"),
+            ),
+        ])
         .await
-        .expect("lookup_resources failed");
+        .unwrap_err();
 
-    let mut resource_ids = vec![];
-    while let Some(result) = stream.next().await {
-        let item = result.expect("stream item error");
-        resource_ids.push(item.resource_id);
-    }
-    resource_ids.sort();
-    assert!(resource_ids.contains(&"lr-1".to_string()));
-    assert!(resource_ids.contains(&"lr-2".to_string()));
+    assert_eq!(err.code(), Some(tonic::Code::FailedPrecondition));
+    assert_eq!(err.details().unwrap().failed_precondition(), Some(1));
 }
 
 #[tokio::test]
-async fn lookup_subjects() {
+async fn check_permission_debug_trace_renders() {
     let c = spicedb().await;
 
     let token = c
-        .write_relationships(vec![
-            RelationshipUpdate::create(Relationship::new(
-                ObjectReference::new("document", "ls-1").unwrap(),
-                "viewer",
-                SubjectReference::new(ObjectReference::new("user", "eve").unwrap(), None::<String>)
-                    .unwrap(),
-            )),
-            RelationshipUpdate::create(Relationship::new(
-                ObjectReference::new("document", "ls-1").unwrap(),
-                "viewer",
-                SubjectReference::new(
-                    ObjectReference::new("user", "frank").unwrap(),
-                    None::<String>,
-                )
-                .unwrap(),
-            )),
-        ])
+        .write_relationships(vec![RelationshipUpdate::touch(
+            Relationship::parse("document:trace-1#viewer@user:sam").unwrap(),
+        )])
         .await
         .unwrap();
 
-    let resource = ObjectReference::new("document", "ls-1").unwrap();
-    let mut stream = c
-        .lookup_subjects(&resource, "view", "user")
+    let (result, trace) = c
+        .check_permission(
+            &ObjectReference::new("document", "trace-1").unwrap(),
+            "view",
+            &SubjectReference::parse("user:sam").unwrap(),
+        )
         .consistency(Consistency::AtLeastAsFresh(token))
-        .send()
+        .send_with_debug()
         .await
-        .expect("lookup_subjects failed");
+        .unwrap();
 
-    let mut subject_ids = vec![];
-    while let Some(result) = stream.next().await {
-        let item = result.expect("stream item error");
-        subject_ids.push(item.subject_id);
-    }
-    subject_ids.sort();
-    assert!(subject_ids.contains(&"eve".to_string()));
-    assert!(subject_ids.contains(&"frank".to_string()));
+    assert_eq!(result, PermissionResult::Allowed);
+    assert_eq!(trace.result, PermissionResult::Allowed);
+    let rendered = trace.render();
+    assert!(
+        rendered.starts_with("document:trace-1 view = ALLOWED"),
+        "{rendered}"
+    );
+    assert!(rendered.contains("viewer"), "{rendered}");
 }
 
 #[tokio::test]
-async fn delete_relationships() {
+async fn write_dry_run_reports_problems_without_writing() {
+    use prescience::{Precondition, WriteProblem};
+
     let c = spicedb().await;
 
     let token = c
-        .write_relationships(vec![RelationshipUpdate::create(Relationship::new(
-            ObjectReference::new("document", "del-1").unwrap(),
-            "viewer",
-            SubjectReference::new(
-                ObjectReference::new("user", "grace").unwrap(),
-                None::<String>,
-            )
-            .unwrap(),
-        ))])
+        .write_relationships(vec![RelationshipUpdate::touch(
+            Relationship::parse("document:dry-1#viewer@user:quinn").unwrap(),
+        )])
         .await
         .unwrap();
 
-    let result = c
-        .check_permission(
-            &ObjectReference::new("document", "del-1").unwrap(),
-            "view",
-            &SubjectReference::new(
-                ObjectReference::new("user", "grace").unwrap(),
-                None::<String>,
-            )
-            .unwrap(),
-        )
-        .consistency(Consistency::AtLeastAsFresh(token))
+    let updates = [
+        "document:dry-1#viewer@user:quinn",
+        "document:dry-1#view@user:rosa",
+        "document:dry-1#editor@group:eng#member",
+        "document:dry-2#viewer@user:rosa",
+    ]
+    .into_iter()
+    .map(|t| RelationshipUpdate::create(Relationship::parse(t).unwrap()))
+    .collect();
+    let problems = c
+        .write_relationships(updates)
+        .require(Precondition::must_exist(
+            RelationshipFilter::new("document").resource_id("dry-2"),
+        ))
+        .dry_run()
         .await
         .unwrap();
-    assert!(result.is_allowed().unwrap());
 
-    let del_token = c
-        .delete_relationships(
-            RelationshipFilter::new("document")
-                .resource_id("del-1")
-                .relation("viewer"),
-        )
-        .await
-        .unwrap();
+    assert_eq!(
+        problems,
+        [
+            WriteProblem::AlreadyExists { update: 0 },
+            WriteProblem::UnknownRelation {
+                update: 1,
+                object_type: "document".into(),
+                relation: "view".into(),
+            },
+            WriteProblem::SubjectNotAllowed {
+                update: 2,
+                relation: "editor".into(),
+                subject_type: "group#member".into(),
+            },
+            WriteProblem::PreconditionFailed { precondition: 0 },
+        ]
+    );
 
-    let result = c
-        .check_permission(
-            &ObjectReference::new("document", "del-1").unwrap(),
-            "view",
-            &SubjectReference::new(
-                ObjectReference::new("user", "grace").unwrap(),
-                None::<String>,
-            )
-            .unwrap(),
-        )
-        .consistency(Consistency::AtLeastAsFresh(del_token))
+    let exists = c
+        .relationship_exists(&Relationship::parse("document:dry-2#viewer@user:rosa").unwrap())
+        .consistency(Consistency::AtLeastAsFresh(token))
         .await
         .unwrap();
-    assert!(!result.is_allowed().unwrap());
+    assert!(!exists, "dry run must not write");
 }
 
-// ── Watch ─────────────────────────────────────────────────────
-
-#[cfg(feature = "watch")]
 #[tokio::test]
-async fn watch_receives_updates() {
+async fn read_relationships_caveat_context_round_trip() {
+    use std::collections::HashMap;
+
+    use prescience::{Caveat, ContextValue};
+
     let c = spicedb().await;
 
-    let mut stream = c
-        .watch(vec!["document"])
-        .send()
+    let mut context = HashMap::new();
+    context.insert(
+        "cidr".to_string(),
+        ContextValue::String("10.0.0.0/8".into()),
+    );
+    let with_context = Relationship::parse("document:caveat-read-1#viewer@user:carol")
+        .unwrap()
+        .with_caveat(Caveat::new("ip_allowlist", context));
+    let without_context = Relationship::parse("document:caveat-read-1#viewer@user:dave")
+        .unwrap()
+        .with_caveat(Caveat::new("ip_allowlist", HashMap::new()));
+
+    let token = c
+        .write_relationships(vec![
+            RelationshipUpdate::create(with_context.clone()),
+            RelationshipUpdate::create(without_context.clone()),
+        ])
         .await
-        .expect("watch failed");
+        .unwrap();
 
-    let c2 = c.clone();
-    let write_handle = tokio::spawn(async move {
-        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
-        c2.write_relationships(vec![RelationshipUpdate::create(Relationship::new(
-            ObjectReference::new("document", "watch-1").unwrap(),
-            "viewer",
-            SubjectReference::new(ObjectReference::new("user", "hal").unwrap(), None::<String>)
-                .unwrap(),
-        ))])
+    let stream = c
+        .read_relationships(RelationshipFilter::new("document").resource_id("caveat-read-1"))
+        .consistency(Consistency::AtLeastAsFresh(token.clone()))
+        .send()
         .await
         .unwrap();
-    });
+    let mut read: Vec<Relationship> = stream
+        .map(|r| r.unwrap().relationship)
+        .collect::<Vec<_>>()
+        .await;
+    read.sort_by_key(|r| r.subject.to_string());
 
-    let event = tokio::time::timeout(std::time::Duration::from_secs(10), stream.next())
+    assert_eq!(read, vec![with_context.clone(), without_context.clone()]);
+
+    let stream = c
+        .read_relationships(RelationshipFilter::new("document").resource_id("caveat-read-1"))
+        .consistency(Consistency::AtLeastAsFresh(token))
+        .without_caveat_context(true)
+        .send()
         .await
-        .expect("timed out waiting for watch event")
-        .expect("stream ended")
-        .expect("watch event error");
+        .unwrap();
+    let mut stripped: Vec<Relationship> = stream
+        .map(|r| r.unwrap().relationship)
+        .collect::<Vec<_>>()
+        .await;
+    stripped.sort_by_key(|r| r.subject.to_string());
 
-    assert!(!event.updates.is_empty());
-    write_handle.await.unwrap();
+    let with_context = with_context.with_caveat(Caveat::new("ip_allowlist", HashMap::new()));
+    assert_eq!(stripped, vec![with_context, without_context]);
 }
 
-// ── Bulk (experimental) ───────────────────────────────────────
-
-#[cfg(feature = "experimental")]
 #[tokio::test]
-async fn bulk_check_permissions() {
-    use prescience::BulkCheckItem;
+async fn read_relationships_filtered_by_caveat() {
+    use std::collections::HashMap;
+
+    use prescience::Caveat;
 
     let c = spicedb().await;
 
+    let plain = Relationship::parse("document:caveat-filter-1#viewer@user:erin").unwrap();
+    let caveated = Relationship::parse("document:caveat-filter-1#viewer@user:finn")
+        .unwrap()
+        .with_caveat(Caveat::new("ip_allowlist", HashMap::new()));
     let token = c
-        .write_relationships(vec![RelationshipUpdate::create(Relationship::new(
-            ObjectReference::new("document", "bulk-1").unwrap(),
-            "viewer",
-            SubjectReference::new(
-                ObjectReference::new("user", "iris").unwrap(),
-                None::<String>,
-            )
-            .unwrap(),
+        .write_relationships(vec![
+            RelationshipUpdate::create(plain),
+            RelationshipUpdate::create(caveated.clone()),
+        ])
+        .await
+        .unwrap();
+
+    let base = c
+        .read_relationships(RelationshipFilter::new("document").resource_id("caveat-filter-1"))
+        .consistency(Consistency::AtLeastAsFresh(token));
+    let any: Vec<Relationship> = base
+        .clone()
+        .with_caveat_only()
+        .send()
+        .await
+        .unwrap()
+        .map(|r| r.unwrap().relationship)
+        .collect()
+        .await;
+    let other: Vec<Relationship> = base
+        .with_caveat_named("other")
+        .send()
+        .await
+        .unwrap()
+        .map(|r| r.unwrap().relationship)
+        .collect()
+        .await;
+    assert_eq!(any, vec![caveated]);
+    assert!(other.is_empty());
+}
+
+#[tokio::test]
+async fn read_graph_links_resources_and_subjects() {
+    let c = spicedb().await;
+
+    let doc = ObjectReference::new("document", "graph-1").unwrap();
+    let token = c
+        .write_relationships(vec![
+            RelationshipUpdate::create(
+                Relationship::parse("document:graph-1#viewer@user:gus").unwrap(),
+            ),
+            RelationshipUpdate::create(
+                Relationship::parse("document:graph-1#editor@user:hana").unwrap(),
+            ),
+        ])
+        .await
+        .unwrap();
+
+    let graph = c
+        .read_graph(RelationshipFilter::new("document").resource_id("graph-1"))
+        .consistency(Consistency::AtLeastAsFresh(token))
+        .await
+        .expect("read_graph failed");
+    assert_eq!(graph.len(), 2);
+    assert_eq!(graph.subjects_of(&doc).count(), 2);
+    let gus = ObjectReference::new("user", "gus").unwrap();
+    assert_eq!(graph.resources_of(&gus).next().unwrap().resource, doc);
+    assert!(graph
+        .to_dot()
+        .contains("\"document:graph-1\" -> \"user:gus\""));
+}
+
+#[tokio::test]
+async fn caveat_context_relationship_takes_precedence() {
+    use std::collections::HashMap;
+
+    use prescience::{Caveat, ContextValue};
+
+    let c = spicedb().await;
+
+    let caveat = Caveat::new(
+        "ip_allowlist",
+        HashMap::from([(
+            "cidr".to_string(),
+            ContextValue::String("10.0.0.0/8".into()),
+        )]),
+    );
+    let rel = Relationship::parse("document:caveat-merge-1#viewer@user:erin")
+        .unwrap()
+        .with_caveat(caveat.clone());
+    let token = c
+        .write_relationships(vec![RelationshipUpdate::create(rel.clone())])
+        .await
+        .unwrap();
+
+    let check = |request_context: HashMap<String, ContextValue>| {
+        c.check_permission(&rel.resource, "view", &rel.subject)
+            .context(request_context)
+            .consistency(Consistency::AtLeastAsFresh(token.clone()))
+    };
+
+    // The request supplies only the parameter the relationship left open.
+    let inside = HashMap::from([(
+        "user_ip".to_string(),
+        ContextValue::String("10.1.2.3".into()),
+    )]);
+    assert_eq!(check(inside).await.unwrap(), PermissionResult::Allowed);
+
+    // A request-time cidr must not shadow the one written with the relationship.
+    let widened = HashMap::from([
+        (
+            "user_ip".to_string(),
+            ContextValue::String("192.168.1.1".into()),
+        ),
+        ("cidr".to_string(), ContextValue::String("0.0.0.0/0".into())),
+    ]);
+    assert_eq!(
+        caveat.merge_context(&widened)["cidr"],
+        ContextValue::String("10.0.0.0/8".into())
+    );
+    assert_eq!(check(widened).await.unwrap(), PermissionResult::Denied);
+}
+
+#[tokio::test]
+async fn check_with_token_resolves_conditional_at_same_snapshot() {
+    use std::collections::HashMap;
+
+    use prescience::{Caveat, ContextValue};
+
+    let c = spicedb().await;
+
+    let caveat = Caveat::new(
+        "ip_allowlist",
+        HashMap::from([(
+            "cidr".to_string(),
+            ContextValue::String("10.0.0.0/8".into()),
+        )]),
+    );
+    let rel = Relationship::parse("document:caveat-token-1#viewer@user:frank")
+        .unwrap()
+        .with_caveat(caveat);
+    let token = c
+        .write_relationships(vec![RelationshipUpdate::create(rel.clone())])
+        .await
+        .unwrap();
+
+    let (result, checked_at) = c
+        .check_permission(&rel.resource, "view", &rel.subject)
+        .consistency(Consistency::AtLeastAsFresh(token))
+        .send_with_token()
+        .await
+        .unwrap();
+    assert!(matches!(result, PermissionResult::Conditional { .. }));
+
+    let context = HashMap::from([(
+        "user_ip".to_string(),
+        ContextValue::String("10.1.2.3".into()),
+    )]);
+    let (resolved, _) = c
+        .check_permission(&rel.resource, "view", &rel.subject)
+        .context(context)
+        .consistency(Consistency::AtExactSnapshot(checked_at))
+        .send_with_token()
+        .await
+        .unwrap();
+    assert_eq!(resolved, PermissionResult::Allowed);
+}
+
+#[tokio::test]
+async fn read_relationships_subject_id_prefix() {
+    let c = spicedb().await;
+
+    let updates = ["eng-1", "eng-2", "ops-1"]
+        .into_iter()
+        .map(|id| {
+            RelationshipUpdate::create(Relationship::new(
+                ObjectReference::new("document", "prefix-1").unwrap(),
+                "viewer",
+                SubjectReference::new(ObjectReference::new("user", id).unwrap(), None::<String>)
+                    .unwrap(),
+            ))
+        })
+        .collect();
+    let token = c.write_relationships(updates).await.unwrap();
+
+    let filter = RelationshipFilter::new("document").resource_id("prefix-1");
+    let mut stream = c
+        .read_relationships(filter)
+        .consistency(Consistency::AtLeastAsFresh(token))
+        .subject_id_prefix("eng-")
+        .send()
+        .await
+        .expect("read_relationships failed");
+
+    let mut subject_ids = vec![];
+    while let Some(result) = stream.next().await {
+        let item = result.expect("stream item error");
+        subject_ids.push(item.relationship.subject.object().object_id().to_string());
+    }
+    subject_ids.sort();
+    assert_eq!(subject_ids, vec!["eng-1", "eng-2"]);
+}
+
+#[tokio::test]
+async fn read_relationships_by_subject_filter() {
+    let c = spicedb().await;
+
+    let eng = SubjectReference::new(
+        ObjectReference::new("group", "eng").unwrap(),
+        Some("member"),
+    )
+    .unwrap();
+    let token = c
+        .write_relationships(vec![
+            RelationshipUpdate::create(Relationship::new(
+                ObjectReference::new("document", "subject-filter-1").unwrap(),
+                "viewer",
+                eng.clone(),
+            )),
+            RelationshipUpdate::create(Relationship::new(
+                ObjectReference::new("document", "subject-filter-2").unwrap(),
+                "viewer",
+                eng,
+            )),
+            RelationshipUpdate::create(Relationship::new(
+                ObjectReference::new("document", "subject-filter-3").unwrap(),
+                "viewer",
+                SubjectReference::new(ObjectReference::new("user", "eng").unwrap(), None::<String>)
+                    .unwrap(),
+            )),
+        ])
+        .await
+        .unwrap();
+
+    let filter = RelationshipFilter::new("document").subject_filter(
+        SubjectFilter::new("group")
+            .subject_id("eng")
+            .relation("member"),
+    );
+    let mut stream = c
+        .read_relationships(filter)
+        .consistency(Consistency::AtLeastAsFresh(token.clone()))
+        .send()
+        .await
+        .expect("read_relationships failed");
+
+    let mut resource_ids = vec![];
+    while let Some(result) = stream.next().await {
+        let item = result.expect("stream item error");
+        assert_eq!(item.relationship.subject.object().object_type(), "group");
+        assert_eq!(
+            item.relationship.subject.optional_relation(),
+            Some("member")
+        );
+        resource_ids.push(item.relationship.resource.object_id().to_string());
+    }
+    resource_ids.sort();
+    assert_eq!(resource_ids, vec!["subject-filter-1", "subject-filter-2"]);
+
+    // The convenience form matches the same subject without pinning its relation.
+    let filter = RelationshipFilter::new("document").with_subject("group", "eng");
+    let mut stream = c
+        .read_relationships(filter)
+        .consistency(Consistency::AtLeastAsFresh(token))
+        .send()
+        .await
+        .expect("read_relationships failed");
+
+    let mut count = 0;
+    while let Some(result) = stream.next().await {
+        result.expect("stream item error");
+        count += 1;
+    }
+    assert_eq!(count, 2);
+}
+
+#[tokio::test]
+async fn count_relationships() {
+    let c = spicedb().await;
+
+    let updates = ["count-a", "count-b", "count-c"]
+        .into_iter()
+        .map(|id| {
+            RelationshipUpdate::create(Relationship::new(
+                ObjectReference::new("document", "count-1").unwrap(),
+                "viewer",
+                SubjectReference::new(ObjectReference::new("user", id).unwrap(), None::<String>)
+                    .unwrap(),
+            ))
+        })
+        .collect();
+    c.write_relationships(updates).await.unwrap();
+
+    let filter = RelationshipFilter::new("document").resource_id("count-1");
+    let count = c
+        .count_relationships(filter.clone())
+        .await
+        .expect("count_relationships failed");
+    assert_eq!(count, 3);
+
+    let capped = c
+        .count_relationships(filter)
+        .max(2)
+        .await
+        .expect("count_relationships failed");
+    assert_eq!(capped, 2);
+}
+
+#[tokio::test]
+async fn require_permission_returns_token_or_structured_denial() {
+    let c = spicedb().await;
+
+    let doc = ObjectReference::new("document", "require-1").unwrap();
+    let owner = SubjectReference::parse("user:pia").unwrap();
+    let stranger = SubjectReference::parse("user:quinn").unwrap();
+    let written = c
+        .write_relationships(vec![RelationshipUpdate::create(Relationship::new(
+            doc.clone(),
+            "editor",
+            owner.clone(),
+        ))])
+        .await
+        .unwrap();
+
+    c.require_permission(&doc, "edit", &owner)
+        .consistency(Consistency::AtLeastAsFresh(written.clone()))
+        .await
+        .expect("editor denied");
+    let err = c
+        .require_permission(&doc, "edit", &stranger)
+        .consistency(Consistency::AtLeastAsFresh(written))
+        .await
+        .unwrap_err();
+    match err {
+        prescience::Error::PermissionDenied {
+            resource,
+            permission,
+            subject,
+        } => {
+            assert_eq!(*resource, doc);
+            assert_eq!(permission, "edit");
+            assert_eq!(*subject, stranger);
+        }
+        other => panic!("expected PermissionDenied, got {other:?}"),
+    }
+}
+
+#[tokio::test]
+async fn lookup_resources() {
+    let c = spicedb().await;
+
+    let token = c
+        .write_relationships(vec![
+            RelationshipUpdate::create(Relationship::new(
+                ObjectReference::new("document", "lr-1").unwrap(),
+                "viewer",
+                SubjectReference::new(
+                    ObjectReference::new("user", "dave").unwrap(),
+                    None::<String>,
+                )
+                .unwrap(),
+            )),
+            RelationshipUpdate::create(Relationship::new(
+                ObjectReference::new("document", "lr-2").unwrap(),
+                "editor",
+                SubjectReference::new(
+                    ObjectReference::new("user", "dave").unwrap(),
+                    None::<String>,
+                )
+                .unwrap(),
+            )),
+        ])
+        .await
+        .unwrap();
+
+    let subject = SubjectReference::new(
+        ObjectReference::new("user", "dave").unwrap(),
+        None::<String>,
+    )
+    .unwrap();
+
+    let mut stream = c
+        .lookup_resources("document", "view", &subject)
+        .consistency(Consistency::AtLeastAsFresh(token))
+        .send()
+        .await
+        .expect("lookup_resources failed");
+
+    let mut resource_ids = vec![];
+    while let Some(result) = stream.next().await {
+        let item = result.expect("stream item error");
+        assert_eq!(
+            item.resource_reference().unwrap(),
+            ObjectReference::new("document", item.resource_id.as_str()).unwrap()
+        );
+        resource_ids.push(item.resource_id);
+    }
+    resource_ids.sort();
+    assert!(resource_ids.contains(&"lr-1".to_string()));
+    assert!(resource_ids.contains(&"lr-2".to_string()));
+}
+
+#[tokio::test]
+async fn lookup_resources_for_any_merges_subjects() {
+    let c = spicedb().await;
+
+    let token = c
+        .write_relationships(
+            [
+                "document:lra-1#viewer@user:lra-alice",
+                "document:lra-2#viewer@user:lra-bob",
+                "document:lra-3#viewer@user:lra-alice",
+                "document:lra-3#editor@user:lra-bob",
+            ]
+            .into_iter()
+            .map(|t| RelationshipUpdate::create(Relationship::parse(t).unwrap()))
+            .collect(),
+        )
+        .await
+        .unwrap();
+
+    let subjects = vec![
+        SubjectReference::parse("user:lra-alice").unwrap(),
+        SubjectReference::parse("user:lra-bob").unwrap(),
+    ];
+    let stream = c
+        .lookup_resources_for_any("document", "view", subjects)
+        .consistency(Consistency::AtLeastAsFresh(token))
+        .max_in_flight(1)
+        .send()
+        .await
+        .expect("lookup_resources_for_any failed");
+
+    let mut ids: Vec<String> = stream
+        .map(|r| r.expect("stream item error").resource_id)
+        .collect()
+        .await;
+    ids.sort();
+    assert_eq!(ids, ["lra-1", "lra-2", "lra-3"]);
+}
+
+#[tokio::test]
+async fn lookup_subjects() {
+    let c = spicedb().await;
+
+    let token = c
+        .write_relationships(vec![
+            RelationshipUpdate::create(Relationship::new(
+                ObjectReference::new("document", "ls-1").unwrap(),
+                "viewer",
+                SubjectReference::new(ObjectReference::new("user", "eve").unwrap(), None::<String>)
+                    .unwrap(),
+            )),
+            RelationshipUpdate::create(Relationship::new(
+                ObjectReference::new("document", "ls-1").unwrap(),
+                "viewer",
+                SubjectReference::new(
+                    ObjectReference::new("user", "frank").unwrap(),
+                    None::<String>,
+                )
+                .unwrap(),
+            )),
+        ])
+        .await
+        .unwrap();
+
+    let resource = ObjectReference::new("document", "ls-1").unwrap();
+    let mut stream = c
+        .lookup_subjects(&resource, "view", "user")
+        .consistency(Consistency::AtLeastAsFresh(token))
+        .send()
+        .await
+        .expect("lookup_subjects failed");
+
+    let mut subject_ids = vec![];
+    while let Some(result) = stream.next().await {
+        let item = result.expect("stream item error");
+        let subject = item.subject_reference().expect("subject_reference");
+        assert_eq!(subject.object().object_type(), "user");
+        assert_eq!(subject.optional_relation(), None);
+        subject_ids.push(item.subject_id);
+    }
+    subject_ids.sort();
+    assert!(subject_ids.contains(&"eve".to_string()));
+    assert!(subject_ids.contains(&"frank".to_string()));
+}
+
+#[tokio::test]
+async fn delete_relationships() {
+    let c = spicedb().await;
+
+    let token = c
+        .write_relationships(vec![RelationshipUpdate::create(Relationship::new(
+            ObjectReference::new("document", "del-1").unwrap(),
+            "viewer",
+            SubjectReference::new(
+                ObjectReference::new("user", "grace").unwrap(),
+                None::<String>,
+            )
+            .unwrap(),
+        ))])
+        .await
+        .unwrap();
+
+    let result = c
+        .check_permission(
+            &ObjectReference::new("document", "del-1").unwrap(),
+            "view",
+            &SubjectReference::new(
+                ObjectReference::new("user", "grace").unwrap(),
+                None::<String>,
+            )
+            .unwrap(),
+        )
+        .consistency(Consistency::AtLeastAsFresh(token))
+        .await
+        .unwrap();
+    assert!(result.is_allowed().unwrap());
+
+    let del_token = c
+        .delete_relationships(
+            RelationshipFilter::new("document")
+                .resource_id("del-1")
+                .relation("viewer"),
+        )
+        .await
+        .unwrap();
+
+    let result = c
+        .check_permission(
+            &ObjectReference::new("document", "del-1").unwrap(),
+            "view",
+            &SubjectReference::new(
+                ObjectReference::new("user", "grace").unwrap(),
+                None::<String>,
+            )
+            .unwrap(),
+        )
+        .consistency(Consistency::AtLeastAsFresh(del_token))
+        .await
+        .unwrap();
+    assert!(!result.is_allowed().unwrap());
+}
+
+#[tokio::test]
+async fn delete_relationships_many() {
+    let c = spicedb().await;
+
+    c.write_relationships(vec![
+        RelationshipUpdate::create(Relationship::new(
+            ObjectReference::new("document", "delmany-1").unwrap(),
+            "viewer",
+            SubjectReference::new(
+                ObjectReference::new("user", "ivan").unwrap(),
+                None::<String>,
+            )
+            .unwrap(),
+        )),
+        RelationshipUpdate::create(Relationship::new(
+            ObjectReference::new("document", "delmany-2").unwrap(),
+            "editor",
+            SubjectReference::new(
+                ObjectReference::new("user", "ivan").unwrap(),
+                None::<String>,
+            )
+            .unwrap(),
+        )),
+    ])
+    .await
+    .unwrap();
+
+    let del_token = c
+        .delete_relationships_many(vec![
+            RelationshipFilter::new("document").resource_id("delmany-1"),
+            RelationshipFilter::new("document").resource_id("delmany-2"),
+        ])
+        .await
+        .expect("delete_relationships_many failed");
+
+    for id in ["delmany-1", "delmany-2"] {
+        let count = c
+            .count_relationships(RelationshipFilter::new("document").resource_id(id))
+            .consistency(Consistency::AtLeastAsFresh(del_token.clone()))
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    let err = c.delete_relationships_many(vec![]).await.unwrap_err();
+    assert!(matches!(err, prescience::Error::InvalidArgument(_)));
+}
+
+#[tokio::test]
+async fn at_snapshot_pins_reads() {
+    let c = spicedb().await;
+
+    let doc = ObjectReference::new("document", "snap-1").unwrap();
+    let judy = SubjectReference::new(
+        ObjectReference::new("user", "judy").unwrap(),
+        None::<String>,
+    )
+    .unwrap();
+
+    let before = c
+        .write_relationships(vec![RelationshipUpdate::create(Relationship::new(
+            doc.clone(),
+            "viewer",
+            judy.clone(),
+        ))])
+        .await
+        .unwrap();
+    c.write_relationships(vec![RelationshipUpdate::create(Relationship::new(
+        doc.clone(),
+        "editor",
+        judy.clone(),
+    ))])
+    .await
+    .unwrap();
+
+    let snap = c.at_snapshot(before);
+    assert_eq!(
+        snap.check_permission(&doc, "view", &judy).await.unwrap(),
+        PermissionResult::Allowed
+    );
+    assert_eq!(
+        snap.check_permission(&doc, "edit", &judy).await.unwrap(),
+        PermissionResult::Denied
+    );
+}
+
+#[tokio::test]
+async fn write_relationships_stream_in_chunks() {
+    let c = spicedb().await;
+
+    let updates = (0..25).map(|i| {
+        RelationshipUpdate::create(Relationship::new(
+            ObjectReference::new("document", "stream-1").unwrap(),
+            "viewer",
+            SubjectReference::new(
+                ObjectReference::new("user", format!("stream-{i}")).unwrap(),
+                None::<String>,
+            )
+            .unwrap(),
+        ))
+    });
+
+    let token = c
+        .write_relationships_stream(tokio_stream::iter(updates), 10)
+        .await
+        .expect("write_relationships_stream failed");
+
+    let count = c
+        .count_relationships(RelationshipFilter::new("document").resource_id("stream-1"))
+        .consistency(Consistency::AtLeastAsFresh(token))
+        .await
+        .unwrap();
+    assert_eq!(count, 25);
+}
+
+#[tokio::test]
+async fn relationship_exists() {
+    let c = spicedb().await;
+
+    let rel = Relationship::new(
+        ObjectReference::new("document", "exists-1").unwrap(),
+        "viewer",
+        SubjectReference::new(ObjectReference::new("user", "kim").unwrap(), None::<String>)
+            .unwrap(),
+    );
+    let token = c
+        .write_relationships(vec![RelationshipUpdate::create(rel.clone())])
+        .await
+        .unwrap();
+
+    let exists = c
+        .relationship_exists(&rel)
+        .consistency(Consistency::AtLeastAsFresh(token.clone()))
+        .await
+        .expect("relationship_exists failed");
+    assert!(exists);
+
+    let other = Relationship::new(rel.resource.clone(), "editor", rel.subject.clone());
+    let exists = c
+        .relationship_exists(&other)
+        .consistency(Consistency::AtLeastAsFresh(token))
+        .await
+        .expect("relationship_exists failed");
+    assert!(!exists);
+}
+
+// ── Watch ─────────────────────────────────────────────────────
+
+#[cfg(feature = "watch")]
+#[tokio::test]
+async fn watch_receives_updates() {
+    let c = spicedb().await;
+
+    let mut stream = c
+        .watch(vec!["document"])
+        .send()
+        .await
+        .expect("watch failed");
+
+    let c2 = c.clone();
+    let write_handle = tokio::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        c2.write_relationships(vec![RelationshipUpdate::create(Relationship::new(
+            ObjectReference::new("document", "watch-1").unwrap(),
+            "viewer",
+            SubjectReference::new(ObjectReference::new("user", "hal").unwrap(), None::<String>)
+                .unwrap(),
+        ))])
+        .await
+        .unwrap();
+    });
+
+    let event = tokio::time::timeout(std::time::Duration::from_secs(10), stream.next())
+        .await
+        .expect("timed out waiting for watch event")
+        .expect("stream ended")
+        .expect("watch event error");
+
+    assert!(!event.updates.is_empty());
+    write_handle.await.unwrap();
+}
+
+#[cfg(feature = "watch")]
+#[tokio::test]
+async fn watch_initial_checkpoint_has_no_gap() {
+    let c = spicedb().await;
+
+    let before = Relationship::parse("document:watch-initial-1#viewer@user:lena").unwrap();
+    let after = Relationship::parse("document:watch-initial-1#viewer@user:mo").unwrap();
+    c.write_relationships(vec![RelationshipUpdate::create(before.clone())])
+        .await
+        .unwrap();
+
+    let mut stream = c
+        .watch(vec!["document"])
+        .with_initial_checkpoint(true)
+        .send()
+        .await
+        .expect("watch failed");
+    let initial = stream.next().await.unwrap().unwrap();
+    assert!(initial.updates.is_empty());
+
+    c.write_relationships(vec![RelationshipUpdate::create(after.clone())])
+        .await
+        .unwrap();
+
+    let snapshot: Vec<Relationship> = c
+        .read_relationships(RelationshipFilter::new("document").resource_id("watch-initial-1"))
+        .consistency(Consistency::AtExactSnapshot(initial.checkpoint))
+        .send()
+        .await
+        .unwrap()
+        .map(|r| r.unwrap().relationship)
+        .collect()
+        .await;
+    assert_eq!(snapshot, vec![before]);
+
+    tokio::time::timeout(std::time::Duration::from_secs(10), async {
+        loop {
+            let event = stream.next().await.unwrap().unwrap();
+            if event.creates().any(|u| u.relationship == after) {
+                break;
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for the write after the checkpoint");
+}
+
+#[cfg(feature = "watch")]
+#[tokio::test]
+async fn watch_for_duration_ends_and_resumes() {
+    let c = spicedb().await;
+
+    let rel = Relationship::parse("document:watch-window-1#viewer@user:nia").unwrap();
+    let mut stream = c
+        .watch(vec!["document"])
+        .with_initial_checkpoint(true)
+        .for_duration(std::time::Duration::from_secs(1))
+        .send()
+        .await
+        .expect("watch failed");
+    let ended = tokio::time::timeout(std::time::Duration::from_secs(10), async {
+        while let Some(event) = stream.next().await {
+            event.unwrap();
+        }
+    })
+    .await;
+    assert!(ended.is_ok(), "stream did not end after its duration");
+    let checkpoint = stream.last_checkpoint().cloned().expect("no checkpoint");
+
+    c.write_relationships(vec![RelationshipUpdate::create(rel.clone())])
+        .await
+        .unwrap();
+
+    let mut next = c
+        .watch(vec!["document"])
+        .after_token(checkpoint)
+        .for_duration(std::time::Duration::from_secs(10))
+        .send()
+        .await
+        .unwrap();
+    let mut seen = false;
+    while let Some(event) = next.next().await {
+        if event.unwrap().creates().any(|u| u.relationship == rel) {
+            seen = true;
+            break;
+        }
+    }
+    assert!(seen, "the next window missed the write");
+}
+
+#[cfg(feature = "watch")]
+#[tokio::test]
+async fn watch_filtered_applies_per_type_filters() {
+    let c = spicedb().await;
+
+    let mut stream = c
+        .watch_filtered(vec![RelationshipFilter::new("document").relation("editor")])
+        .send()
+        .await
+        .expect("watch failed");
+
+    let ignored = Relationship::parse("document:watch-filter-1#viewer@user:oz").unwrap();
+    let wanted = Relationship::parse("document:watch-filter-1#editor@user:oz").unwrap();
+    c.write_relationships(vec![RelationshipUpdate::create(ignored.clone())])
+        .await
+        .unwrap();
+    c.write_relationships(vec![RelationshipUpdate::create(wanted.clone())])
+        .await
+        .unwrap();
+
+    let event = tokio::time::timeout(std::time::Duration::from_secs(10), stream.next())
+        .await
+        .expect("timed out waiting for watch event")
+        .expect("stream ended")
+        .expect("watch event error");
+    let relationships: Vec<_> = event.updates.iter().map(|u| &u.relationship).collect();
+    assert_eq!(relationships, vec![&wanted]);
+}
+
+#[cfg(feature = "watch")]
+#[tokio::test]
+async fn watch_event_carries_transaction_metadata() {
+    use std::collections::HashMap;
+
+    use prescience::ContextValue;
+
+    let c = spicedb().await;
+
+    let mut stream = c.watch(vec!["document"]).send().await.unwrap();
+
+    let metadata = HashMap::from([(
+        "actor".to_string(),
+        ContextValue::String("admin@example.com".into()),
+    )]);
+    let rel = Relationship::parse("document:watch-meta-1#viewer@user:ida").unwrap();
+    c.write_relationships(vec![RelationshipUpdate::create(rel.clone())])
+        .metadata(metadata.clone())
+        .await
+        .unwrap();
+
+    // Other tests write concurrently; find the event for this write.
+    let event = tokio::time::timeout(std::time::Duration::from_secs(10), async {
+        loop {
+            let event = stream.next().await.expect("stream ended").unwrap();
+            if event.updates.iter().any(|u| u.relationship == rel) {
+                return event;
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for watch event");
+
+    assert_eq!(event.metadata, Some(metadata));
+}
+
+#[cfg(feature = "watch")]
+#[tokio::test]
+async fn watch_with_checkpoints_delivers_checkpoint_only_events() {
+    let c = spicedb().await;
+
+    let mut stream = c
+        .watch(vec!["group"])
+        .with_checkpoints(true)
+        .send()
+        .await
+        .expect("watch failed");
+
+    // A change outside the watched types still moves the revision forward.
+    c.write_relationships(vec![RelationshipUpdate::create(Relationship::new(
+        ObjectReference::new("document", "watch-checkpoint-1").unwrap(),
+        "viewer",
+        SubjectReference::new(ObjectReference::new("user", "ola").unwrap(), None::<String>)
+            .unwrap(),
+    ))])
+    .await
+    .unwrap();
+
+    let event = tokio::time::timeout(std::time::Duration::from_secs(10), async {
+        loop {
+            let event = stream.next().await.unwrap().unwrap();
+            if event.is_checkpoint_only() {
+                break event;
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for a checkpoint-only event");
+    assert!(event.updates.is_empty());
+}
+
+// ── Bulk (experimental) ───────────────────────────────────────
+
+#[cfg(feature = "experimental")]
+#[tokio::test]
+async fn bulk_check_permissions() {
+    use prescience::BulkCheckItem;
+
+    let c = spicedb().await;
+
+    let token = c
+        .write_relationships(vec![RelationshipUpdate::create(Relationship::new(
+            ObjectReference::new("document", "bulk-1").unwrap(),
+            "viewer",
+            SubjectReference::new(
+                ObjectReference::new("user", "iris").unwrap(),
+                None::<String>,
+            )
+            .unwrap(),
         ))])
         .await
         .unwrap();
@@ -522,3 +1595,571 @@ async fn bulk_check_permissions() {
     assert!(results[0].as_ref().unwrap().is_allowed().unwrap());
     assert!(!results[1].as_ref().unwrap().is_allowed().unwrap());
 }
+
+#[cfg(feature = "experimental")]
+#[tokio::test]
+async fn check_all_and_check_any() {
+    use prescience::BulkCheckItem;
+
+    let c = spicedb().await;
+
+    let doc = ObjectReference::new("document", "combined-1").unwrap();
+    let jill = SubjectReference::parse("user:jill").unwrap();
+    let token = c
+        .write_relationships(vec![RelationshipUpdate::create(
+            Relationship::parse("document:combined-1#viewer@user:jill").unwrap(),
+        )])
+        .await
+        .unwrap();
+    let items = || {
+        vec![
+            BulkCheckItem::new(doc.clone(), "view", jill.clone()),
+            BulkCheckItem::new(doc.clone(), "edit", jill.clone()),
+        ]
+    };
+
+    let all = c
+        .check_all(items())
+        .consistency(Consistency::AtLeastAsFresh(token.clone()))
+        .await
+        .unwrap();
+    assert!(!all);
+
+    let any = c
+        .check_any(items())
+        .consistency(Consistency::AtLeastAsFresh(token))
+        .await
+        .unwrap();
+    assert!(any);
+
+    assert!(matches!(
+        c.check_all(vec![]).await,
+        Err(prescience::Error::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        c.check_any(vec![]).await,
+        Err(prescience::Error::InvalidArgument(_))
+    ));
+}
+
+#[cfg(feature = "experimental")]
+#[tokio::test]
+async fn check_first_allowed() {
+    let c = spicedb().await;
+
+    let doc = ObjectReference::new("document", "first-allowed-1").unwrap();
+    let kim = SubjectReference::parse("user:kim").unwrap();
+    let token = c
+        .write_relationships(vec![RelationshipUpdate::create(
+            Relationship::parse("document:first-allowed-1#viewer@user:kim").unwrap(),
+        )])
+        .await
+        .unwrap();
+
+    let first = c
+        .check_first_allowed(&doc, &kim, vec!["edit", "view"])
+        .consistency(Consistency::AtLeastAsFresh(token.clone()))
+        .await
+        .unwrap();
+    assert_eq!(first.as_deref(), Some("view"));
+
+    let none = c
+        .check_first_allowed(&doc, &kim, vec!["edit"])
+        .consistency(Consistency::AtLeastAsFresh(token))
+        .await
+        .unwrap();
+    assert_eq!(none, None);
+}
+
+#[cfg(feature = "experimental")]
+#[tokio::test]
+async fn relationship_counter() {
+    let c = spicedb().await;
+
+    let updates = ["ann", "ben", "cat"]
+        .into_iter()
+        .map(|id| {
+            RelationshipUpdate::create(Relationship::new(
+                ObjectReference::new("group", "counted-1").unwrap(),
+                "member",
+                SubjectReference::new(ObjectReference::new("user", id).unwrap(), None::<String>)
+                    .unwrap(),
+            ))
+        })
+        .collect();
+    c.write_relationships(updates).await.unwrap();
+
+    c.register_relationship_counter(
+        "counted-1-members",
+        RelationshipFilter::new("group")
+            .resource_id("counted-1")
+            .relation("member"),
+    )
+    .await
+    .expect("register counter failed");
+
+    // The counter is computed in the background; poll until it settles.
+    let mut count = None;
+    for _ in 0..50 {
+        match c.count("counted-1-members").await {
+            Ok(value) if value.count == 3 => {
+                count = Some(value.count);
+                break;
+            }
+            Ok(_) => {}
+            Err(prescience::Error::CounterStillCalculating { .. }) => {}
+            Err(e) => panic!("count failed: {e}"),
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+    }
+    assert_eq!(count, Some(3));
+}
+
+#[tokio::test]
+async fn wait_for_token_after_write() {
+    let c = spicedb().await;
+
+    let token = c
+        .write_relationships(vec![RelationshipUpdate::touch(Relationship::new(
+            ObjectReference::new("document", "wait-1").unwrap(),
+            "viewer",
+            SubjectReference::new(
+                ObjectReference::new("user", "wendy").unwrap(),
+                None::<String>,
+            )
+            .unwrap(),
+        ))])
+        .await
+        .unwrap();
+
+    c.wait_for_token(&token, std::time::Duration::from_secs(5))
+        .await
+        .expect("token should become readable");
+}
+
+#[tokio::test]
+async fn check_subjects_via_expand() {
+    let c = spicedb().await;
+
+    let doc = ObjectReference::new("document", "expand-check-1").unwrap();
+    let subject = |s: &str| SubjectReference::parse(s).unwrap();
+    let token = c
+        .write_relationships(vec![
+            RelationshipUpdate::create(Relationship::new(
+                doc.clone(),
+                "viewer",
+                subject("user:xena"),
+            )),
+            RelationshipUpdate::create(Relationship::new(
+                doc.clone(),
+                "viewer",
+                subject("group:expand-team#member"),
+            )),
+            RelationshipUpdate::create(Relationship::new(
+                ObjectReference::new("group", "expand-team").unwrap(),
+                "member",
+                subject("user:yuri"),
+            )),
+        ])
+        .await
+        .unwrap();
+
+    let results = c
+        .check_subjects_via_expand(
+            &doc,
+            "view",
+            vec![
+                subject("user:xena"),
+                subject("user:yuri"),
+                subject("user:zed"),
+            ],
+        )
+        .consistency(Consistency::AtLeastAsFresh(token))
+        .await
+        .expect("check_subjects_via_expand failed");
+
+    assert_eq!(
+        results,
+        vec![
+            PermissionResult::Allowed,
+            PermissionResult::Allowed,
+            PermissionResult::Denied,
+        ]
+    );
+}
+
+#[tokio::test]
+async fn check_subjects_via_expand_keeps_caveated_grants_conditional() {
+    use std::collections::HashMap;
+
+    use prescience::{Caveat, ContextValue};
+
+    let c = spicedb().await;
+
+    let caveat = Caveat::new(
+        "ip_allowlist",
+        HashMap::from([(
+            "cidr".to_string(),
+            ContextValue::String("10.0.0.0/8".into()),
+        )]),
+    );
+    let rel = Relationship::parse("document:expand-check-2#viewer@user:wendy")
+        .unwrap()
+        .with_caveat(caveat);
+    let token = c
+        .write_relationships(vec![RelationshipUpdate::create(rel.clone())])
+        .await
+        .unwrap();
+
+    let results = c
+        .check_subjects_via_expand(
+            &rel.resource,
+            "view",
+            vec![
+                rel.subject.clone(),
+                SubjectReference::parse("user:zed").unwrap(),
+            ],
+        )
+        .consistency(Consistency::AtLeastAsFresh(token))
+        .await
+        .expect("check_subjects_via_expand failed");
+
+    assert!(matches!(results[0], PermissionResult::Conditional { .. }));
+    assert_eq!(results[1], PermissionResult::Denied);
+}
+
+#[cfg(feature = "experimental")]
+#[tokio::test]
+async fn bulk_import_conflict_strategies() {
+    use prescience::ImportConflictStrategy;
+
+    let c = spicedb().await;
+    let rels = |ids: &[&str]| {
+        let rels: Vec<Relationship> = ids
+            .iter()
+            .map(|id| Relationship::parse(&format!("document:import-1#viewer@user:{id}")).unwrap())
+            .collect();
+        tokio_stream::iter(rels)
+    };
+
+    let loaded = c
+        .bulk_import_relationships(rels(&["ivy", "jack"]))
+        .send()
+        .await
+        .expect("initial import failed");
+    assert_eq!(loaded, 2);
+
+    let err = c
+        .bulk_import_relationships(rels(&["jack", "kim"]))
+        .send()
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Some(tonic::Code::AlreadyExists));
+
+    let loaded = c
+        .bulk_import_relationships(rels(&["jack", "kim"]))
+        .conflict_strategy(ImportConflictStrategy::Skip)
+        .send()
+        .await
+        .expect("skip import failed");
+    assert_eq!(loaded, 1);
+    assert_eq!(loaded.skipped, 1);
+
+    let loaded = c
+        .bulk_import_relationships(rels(&["ivy", "kim", "lee"]))
+        .conflict_strategy(ImportConflictStrategy::Touch)
+        .send()
+        .await
+        .expect("touch import failed");
+    assert_eq!(loaded, 3);
+}
+
+#[cfg(feature = "experimental")]
+#[tokio::test]
+async fn caveat_definition_from_reflection() {
+    use prescience::CaveatParamType;
+
+    let c = spicedb().await;
+
+    let caveat = c
+        .caveat_definition("ip_allowlist")
+        .await
+        .expect("reflection failed")
+        .expect("caveat should exist");
+    assert_eq!(caveat.name(), "ip_allowlist");
+    assert_eq!(caveat.parameters()["user_ip"], CaveatParamType::IpAddress);
+    assert_eq!(caveat.parameters()["cidr"], CaveatParamType::String);
+
+    assert!(c
+        .caveat_definition("no_such_caveat")
+        .await
+        .unwrap()
+        .is_none());
+}
+
+#[cfg(feature = "experimental")]
+#[tokio::test]
+async fn check_resources_across_batches() {
+    let c = spicedb().await;
+
+    let kai = SubjectReference::parse("user:kai").unwrap();
+    let token = c
+        .write_relationships(vec![RelationshipUpdate::create(
+            Relationship::parse("document:batch-10020#viewer@user:kai").unwrap(),
+        )])
+        .await
+        .unwrap();
+
+    // More than one bulk check's worth of resources.
+    let docs: Vec<ObjectReference> = (0..10_050)
+        .map(|i| ObjectReference::new("document", format!("batch-{}", i)).unwrap())
+        .collect();
+    let results = c
+        .check_resources(docs, "view", &kai)
+        .consistency(Consistency::AtLeastAsFresh(token))
+        .max_in_flight(2)
+        .await
+        .expect("check_resources failed");
+
+    assert_eq!(results.len(), 10_050);
+    let allowed: Vec<usize> = results
+        .iter()
+        .enumerate()
+        .filter(|(_, r)| r.as_ref().unwrap() == &PermissionResult::Allowed)
+        .map(|(i, _)| i)
+        .collect();
+    assert_eq!(allowed, vec![10_020]);
+}
+
+#[cfg(feature = "experimental")]
+#[tokio::test]
+async fn check_resources_with_per_item_context() {
+    use std::collections::HashMap;
+
+    use prescience::{Caveat, ContextValue};
+
+    let c = spicedb().await;
+
+    let lux = SubjectReference::parse("user:lux").unwrap();
+    let mut office = HashMap::new();
+    office.insert(
+        "cidr".to_string(),
+        ContextValue::String("10.0.0.0/8".into()),
+    );
+    let caveated = |id: &str| {
+        Relationship::parse(&format!("document:{}#viewer@user:lux", id))
+            .unwrap()
+            .with_caveat(Caveat::new("ip_allowlist", office.clone()))
+    };
+    let token = c
+        .write_relationships(vec![
+            RelationshipUpdate::create(caveated("ctx-inside")),
+            RelationshipUpdate::create(caveated("ctx-outside")),
+            RelationshipUpdate::create(
+                Relationship::parse("document:ctx-plain#viewer@user:lux").unwrap(),
+            ),
+        ])
+        .await
+        .unwrap();
+
+    let ip = |ip: &str| {
+        let mut context = HashMap::new();
+        context.insert("user_ip".to_string(), ContextValue::String(ip.into()));
+        context
+    };
+    let doc = |id: &str| ObjectReference::new("document", id).unwrap();
+    let results = c
+        .check_resources_with_context(
+            vec![
+                (doc("ctx-inside"), ip("10.1.2.3")),
+                (doc("ctx-outside"), ip("192.168.0.1")),
+                (doc("ctx-plain"), HashMap::new()),
+            ],
+            "view",
+            &lux,
+        )
+        .consistency(Consistency::AtLeastAsFresh(token))
+        .await
+        .expect("check_resources_with_context failed");
+    let results: Vec<PermissionResult> = results.into_iter().map(|r| r.unwrap()).collect();
+    assert_eq!(
+        results,
+        vec![
+            PermissionResult::Allowed,
+            PermissionResult::Denied,
+            PermissionResult::Allowed
+        ]
+    );
+}
+
+#[cfg(feature = "experimental")]
+#[tokio::test]
+async fn bulk_export_at_exact_snapshot_ignores_concurrent_writes() {
+    use std::collections::BTreeSet;
+
+    let c = spicedb().await;
+
+    let rel =
+        |i: usize| Relationship::parse(&format!("document:snapshot-1#viewer@user:s{}", i)).unwrap();
+    // More than one export batch's worth, written in two calls.
+    c.write_relationships(RelationshipUpdate::create_all((0..750).map(rel)))
+        .await
+        .unwrap();
+    let snapshot = c
+        .write_relationships(RelationshipUpdate::create_all((750..1500).map(rel)))
+        .await
+        .unwrap();
+
+    let mut stream = c
+        .bulk_export_relationships(RelationshipFilter::new("document").resource_id("snapshot-1"))
+        .consistency(Consistency::AtExactSnapshot(snapshot))
+        .send()
+        .await
+        .unwrap();
+    let mut exported = BTreeSet::new();
+    exported.insert(stream.next().await.unwrap().unwrap().to_zed_string());
+
+    // Mutate the exported set while the export is in progress.
+    let writer = c.clone();
+    tokio::spawn(async move {
+        writer
+            .write_relationships(
+                (1500..1800)
+                    .map(|i| RelationshipUpdate::create(rel(i)))
+                    .chain((0..300).map(|i| RelationshipUpdate::delete(rel(i))))
+                    .collect(),
+            )
+            .await
+            .unwrap();
+    })
+    .await
+    .unwrap();
+
+    while let Some(item) = stream.next().await {
+        exported.insert(item.unwrap().to_zed_string());
+    }
+    let expected: BTreeSet<String> = (0..1500).map(|i| rel(i).to_zed_string()).collect();
+    assert_eq!(exported, expected);
+}
+
+#[cfg(feature = "experimental")]
+#[tokio::test]
+async fn export_map_reimport() {
+    let c = spicedb().await;
+
+    // Enough relationships to span several export and import batches.
+    let updates = RelationshipUpdate::create_all((0..600).map(|i| {
+        Relationship::parse(&format!("document:reimport-src#viewer@user:u{}", i)).unwrap()
+    }));
+    c.write_relationships(updates).await.unwrap();
+
+    let imported = c
+        .bulk_export_relationships(RelationshipFilter::new("document").resource_id("reimport-src"))
+        .consistency(Consistency::FullyConsistent)
+        .reimport_to(&c, |mut rel| {
+            rel.resource = ObjectReference::new("document", "reimport-dst").unwrap();
+            rel
+        })
+        .await
+        .expect("reimport failed");
+    assert_eq!(imported, 600);
+
+    let stream = c
+        .read_relationships(RelationshipFilter::new("document").resource_id("reimport-dst"))
+        .consistency(Consistency::FullyConsistent)
+        .send()
+        .await
+        .unwrap();
+    let copied: Vec<_> = stream.collect::<Vec<_>>().await;
+    assert_eq!(copied.len(), 600);
+    assert!(copied
+        .iter()
+        .all(|r| r.as_ref().unwrap().relationship.relation == "viewer"));
+
+    // Running it again hits the copies and imports nothing.
+    let err = c
+        .bulk_export_relationships(RelationshipFilter::new("document").resource_id("reimport-src"))
+        .reimport_to(&c, |mut rel| {
+            rel.resource = ObjectReference::new("document", "reimport-dst").unwrap();
+            rel
+        })
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Some(tonic::Code::AlreadyExists));
+}
+
+#[cfg(feature = "experimental")]
+#[tokio::test]
+async fn validate_schema() {
+    let c = spicedb().await;
+
+    c.validate_schema(TEST_SCHEMA).await.expect("valid schema");
+
+    let err = c
+        .validate_schema("definition document {\n    relation viewer: usr\n}")
+        .await
+        .unwrap_err();
+    assert_eq!(err.code(), Some(tonic::Code::InvalidArgument));
+
+    // Validation never writes: the stored schema is unchanged.
+    let (schema, _) = c.read_schema().await.unwrap();
+    assert!(schema.contains("caveat ip_allowlist"));
+}
+
+#[cfg(feature = "experimental")]
+#[tokio::test]
+async fn explain_permission() {
+    use prescience::{ExplanationKind, SchemaRule};
+
+    let c = spicedb().await;
+
+    let doc = ObjectReference::new("document", "explain-1").unwrap();
+    let explanation = c.explain_permission(&doc, "view").await.unwrap();
+    assert_eq!(explanation.root.relation, "view");
+    assert_eq!(explanation.root.rule, Some(SchemaRule::Permission));
+    let ExplanationKind::Union { children } = &explanation.root.kind else {
+        panic!("view should expand to a union: {}", explanation);
+    };
+    let viewer = children
+        .iter()
+        .find(|n| n.relation == "viewer")
+        .expect("viewer branch");
+    let Some(SchemaRule::Relation { subject_types }) = &viewer.rule else {
+        panic!("viewer should be a relation: {}", explanation);
+    };
+    assert!(subject_types.contains(&"group#member".to_string()));
+    assert!(explanation
+        .to_string()
+        .starts_with("document:explain-1#view = union of"));
+}
+
+#[cfg(feature = "experimental")]
+#[tokio::test]
+async fn effective_permissions() {
+    let c = spicedb().await;
+
+    let doc = ObjectReference::new("document", "effective-1").unwrap();
+    let jane = SubjectReference::new(
+        ObjectReference::new("user", "jane").unwrap(),
+        None::<String>,
+    )
+    .unwrap();
+    let token = c
+        .write_relationships(vec![RelationshipUpdate::create(Relationship::new(
+            doc.clone(),
+            "viewer",
+            jane.clone(),
+        ))])
+        .await
+        .unwrap();
+
+    let perms = c
+        .effective_permissions(&doc, &jane, vec!["view", "edit"])
+        .consistency(Consistency::AtLeastAsFresh(token))
+        .await
+        .expect("effective_permissions failed");
+
+    assert_eq!(perms.len(), 2);
+    assert_eq!(perms["view"], PermissionResult::Allowed);
+    assert_eq!(perms["edit"], PermissionResult::Denied);
+}